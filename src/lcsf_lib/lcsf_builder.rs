@@ -0,0 +1,946 @@
+//! Build a LcsfValidCmd following its descriptor, setting attributes by id or name in any order
+//!
+//! author: Jean-Roland Gosse
+//!
+//! This file is part of LCSF Stack Rust.
+//! Spec details at <https://jean-roland.github.io/LCSF_Doc/>
+//! You should have received a copy of the GNU Lesser General Public License
+//! along with this program. If not, see <https://www.gnu.org/licenses/>
+
+use std::time::Duration;
+use std::time::SystemTime;
+
+use crate::lcsf_lib::lcsf_validator;
+use crate::lcsf_lib::lcsf_view;
+use lcsf_validator::LcsfAttCardinality;
+use lcsf_validator::LcsfAttDesc;
+use lcsf_validator::LcsfCmdDesc;
+use lcsf_validator::LcsfDataType;
+use lcsf_validator::LcsfElemType;
+use lcsf_validator::LcsfProtDesc;
+use lcsf_validator::LcsfValidAtt;
+use lcsf_validator::LcsfValidAttPayload;
+use lcsf_validator::LcsfValidCmd;
+use lcsf_validator::LcsfValidInfo;
+use lcsf_view::LcsfArrayElem;
+use lcsf_view::LcsfValueErrorEnum;
+
+/// Attribute key enum, an attribute id or its descriptor name
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum LcsfAttKey<'k> {
+    Id(u16),
+    Name(&'k str),
+}
+
+impl From<u16> for LcsfAttKey<'_> {
+    fn from(att_id: u16) -> Self {
+        LcsfAttKey::Id(att_id)
+    }
+}
+
+impl<'k> From<&'k str> for LcsfAttKey<'k> {
+    fn from(name: &'k str) -> Self {
+        LcsfAttKey::Name(name)
+    }
+}
+
+/// Value to set enum, converted following the attribute data type
+#[derive(Debug)]
+pub enum LcsfSetValue<'v, 'a> {
+    Uint(u64),
+    Int(i64),
+    Float(f64),
+    Str(&'v str),
+    Bytes(&'v [u8]),
+    /// Packed little endian array elements
+    Array(LcsfElemType, Vec<u8>),
+    Timestamp(SystemTime),
+    Duration(Duration),
+    /// Nested command, with its protocol id
+    Message(u16, LcsfValidCmd),
+    /// Sub-attributes, from [LcsfAttBuilder::subatt_builder]
+    Subatt(LcsfAttBuilder<'a>),
+}
+
+impl From<u8> for LcsfSetValue<'_, '_> {
+    fn from(value: u8) -> Self {
+        LcsfSetValue::Uint(value as u64)
+    }
+}
+
+impl From<u16> for LcsfSetValue<'_, '_> {
+    fn from(value: u16) -> Self {
+        LcsfSetValue::Uint(value as u64)
+    }
+}
+
+impl From<u32> for LcsfSetValue<'_, '_> {
+    fn from(value: u32) -> Self {
+        LcsfSetValue::Uint(value as u64)
+    }
+}
+
+impl From<u64> for LcsfSetValue<'_, '_> {
+    fn from(value: u64) -> Self {
+        LcsfSetValue::Uint(value)
+    }
+}
+
+impl From<i32> for LcsfSetValue<'_, '_> {
+    fn from(value: i32) -> Self {
+        LcsfSetValue::Int(value as i64)
+    }
+}
+
+impl From<i64> for LcsfSetValue<'_, '_> {
+    fn from(value: i64) -> Self {
+        LcsfSetValue::Int(value)
+    }
+}
+
+impl From<f32> for LcsfSetValue<'_, '_> {
+    fn from(value: f32) -> Self {
+        LcsfSetValue::Float(value as f64)
+    }
+}
+
+impl From<f64> for LcsfSetValue<'_, '_> {
+    fn from(value: f64) -> Self {
+        LcsfSetValue::Float(value)
+    }
+}
+
+impl From<SystemTime> for LcsfSetValue<'_, '_> {
+    fn from(value: SystemTime) -> Self {
+        LcsfSetValue::Timestamp(value)
+    }
+}
+
+impl From<Duration> for LcsfSetValue<'_, '_> {
+    fn from(value: Duration) -> Self {
+        LcsfSetValue::Duration(value)
+    }
+}
+
+impl From<(u16, LcsfValidCmd)> for LcsfSetValue<'_, '_> {
+    fn from((prot_id, nested_cmd): (u16, LcsfValidCmd)) -> Self {
+        LcsfSetValue::Message(prot_id, nested_cmd)
+    }
+}
+
+impl<'v> From<&'v str> for LcsfSetValue<'v, '_> {
+    fn from(value: &'v str) -> Self {
+        LcsfSetValue::Str(value)
+    }
+}
+
+impl<'v> From<&'v [u8]> for LcsfSetValue<'v, '_> {
+    fn from(value: &'v [u8]) -> Self {
+        LcsfSetValue::Bytes(value)
+    }
+}
+
+#[allow(dead_code)]
+impl LcsfSetValue<'_, '_> {
+    /// Create an Array value, slices of u8 convert to Bytes instead
+    ///
+    /// elem_arr: array elements
+    pub fn array<T: LcsfArrayElem>(elem_arr: &[T]) -> Self {
+        let mut data = Vec::with_capacity(elem_arr.len() * T::ELEM_TYPE.size());
+        elem_arr.iter().for_each(|elem| elem.extend_le(&mut data));
+        LcsfSetValue::Array(T::ELEM_TYPE, data)
+    }
+}
+
+impl<'a> From<LcsfAttBuilder<'a>> for LcsfSetValue<'_, 'a> {
+    fn from(value: LcsfAttBuilder<'a>) -> Self {
+        LcsfSetValue::Subatt(value)
+    }
+}
+
+/// Lcsf build error enum, attributes are designated by their id path
+#[derive(Debug, PartialEq, Clone)]
+pub enum LcsfBuildErrorEnum {
+    /// Attribute key not found in the descriptor, as (parent path, key)
+    UnknownAtt(Vec<u16>, String),
+    /// Value doesn't match the attribute data type, see [LcsfValueErrorEnum]
+    WrongValue(Vec<u16>, LcsfValueErrorEnum),
+    /// Repeated attribute count out of its descriptor limits
+    WrongAttCount(Vec<u16>),
+    /// Number of set sub-attributes not allowed by the exclusive choice
+    WrongOneofNb(Vec<u16>),
+    /// Absent attribute required by a sibling attribute condition
+    MissRequiredAtt(Vec<u16>),
+    /// All the missing mandatory attributes
+    MissMandatoryAtt(Vec<Vec<u16>>),
+}
+
+/// Attribute entry of a builder
+#[derive(Debug)]
+enum LcsfBuildEntry<'a> {
+    Att(LcsfValidAtt),
+    Subatt(LcsfAttBuilder<'a>),
+}
+
+/// Lcsf attribute array builder structure
+#[derive(Debug)]
+pub struct LcsfAttBuilder<'a> {
+    /// Id path of the built array
+    att_path: Vec<u16>,
+    att_desc_arr: &'a [(u16, LcsfAttDesc)],
+    /// Set entries, one list per descriptor
+    entry_arr: Vec<Vec<LcsfBuildEntry<'a>>>,
+}
+
+/// Lcsf command builder structure
+#[derive(Debug)]
+pub struct LcsfCmdBuilder<'a> {
+    cmd_id: u16,
+    att_builder: LcsfAttBuilder<'a>,
+}
+
+#[allow(dead_code)]
+impl<'a> LcsfAttBuilder<'a> {
+    /// Create a builder for an attribute array
+    ///
+    /// att_path: id path of the array, empty for a command
+    ///
+    /// att_desc_arr: (id, attribute descriptor) array reference
+    fn new(att_path: Vec<u16>, att_desc_arr: &'a [(u16, LcsfAttDesc)]) -> Self {
+        LcsfAttBuilder {
+            att_path,
+            att_desc_arr,
+            entry_arr: att_desc_arr.iter().map(|_| Vec::new()).collect(),
+        }
+    }
+
+    /// Return the descriptor index of an attribute key
+    ///
+    /// key: attribute id or name
+    fn find_idx(&self, key: LcsfAttKey) -> Result<usize, LcsfBuildErrorEnum> {
+        let (found_idx, key_str) = match key {
+            LcsfAttKey::Id(att_id) => (
+                self.att_desc_arr.iter().position(|(id, _)| *id == att_id),
+                format!("{att_id:#04x}"),
+            ),
+            LcsfAttKey::Name(name) => (
+                self.att_desc_arr
+                    .iter()
+                    .position(|(_, att_desc)| !name.is_empty() && att_desc.name == name),
+                name.to_string(),
+            ),
+        };
+        found_idx.ok_or(LcsfBuildErrorEnum::UnknownAtt(
+            self.att_path.clone(),
+            key_str,
+        ))
+    }
+
+    /// Return the id path of a descriptor index
+    ///
+    /// idx: descriptor index
+    fn get_path(&self, idx: usize) -> Vec<u16> {
+        let mut att_path = self.att_path.clone();
+        att_path.push(self.att_desc_arr[idx].0);
+        att_path
+    }
+
+    /// Return a builder for the sub-attributes of an attribute, to set with set()
+    ///
+    /// key: attribute id or name
+    pub fn subatt_builder<'k>(
+        &self,
+        key: impl Into<LcsfAttKey<'k>>,
+    ) -> Result<LcsfAttBuilder<'a>, LcsfBuildErrorEnum> {
+        let idx = self.find_idx(key.into())?;
+        let att_desc = &self.att_desc_arr[idx].1;
+        if att_desc.data_type != LcsfDataType::Subattributes {
+            return Err(LcsfBuildErrorEnum::WrongValue(
+                self.get_path(idx),
+                LcsfValueErrorEnum::WrongDataType,
+            ));
+        }
+        Ok(LcsfAttBuilder::new(
+            self.get_path(idx),
+            &att_desc.subatt_desc_arr,
+        ))
+    }
+
+    /// Set an attribute value, checked against its data type.
+    /// Values of a repeated attribute are added as new occurrences,
+    /// values of other attributes replace the previous one.
+    /// Floats set the physical value of scaled attributes.
+    ///
+    /// key: attribute id or name
+    ///
+    /// value: attribute value
+    pub fn set<'k, 'v>(
+        &mut self,
+        key: impl Into<LcsfAttKey<'k>>,
+        value: impl Into<LcsfSetValue<'v, 'a>>,
+    ) -> Result<&mut Self, LcsfBuildErrorEnum> {
+        let idx = self.find_idx(key.into())?;
+        let att_desc = &self.att_desc_arr[idx].1;
+        let data_type = att_desc.data_type;
+        // Convert value
+        let entry = match value.into() {
+            LcsfSetValue::Subatt(builder) => {
+                // Builder must come from this attribute
+                if !std::ptr::eq(builder.att_desc_arr, att_desc.subatt_desc_arr.as_slice()) {
+                    Err(LcsfValueErrorEnum::WrongDataType)
+                } else {
+                    Ok(LcsfBuildEntry::Subatt(builder))
+                }
+            }
+            LcsfSetValue::Uint(value) => {
+                LcsfValidAtt::from_uint(data_type, value).map(LcsfBuildEntry::Att)
+            }
+            LcsfSetValue::Int(value) => {
+                LcsfValidAtt::from_i64(data_type, value).map(LcsfBuildEntry::Att)
+            }
+            LcsfSetValue::Float(value) => match &att_desc.scale {
+                Some(scale) => LcsfValidAtt::from_scaled(data_type, scale, value),
+                None => LcsfValidAtt::from_f64(data_type, value),
+            }
+            .map(LcsfBuildEntry::Att),
+            LcsfSetValue::Str(value) => {
+                LcsfValidAtt::from_str(data_type, value).map(LcsfBuildEntry::Att)
+            }
+            LcsfSetValue::Bytes(value) => {
+                LcsfValidAtt::from_bytes(data_type, value).map(LcsfBuildEntry::Att)
+            }
+            LcsfSetValue::Timestamp(value) => {
+                LcsfValidAtt::from_timestamp(data_type, value).map(LcsfBuildEntry::Att)
+            }
+            LcsfSetValue::Duration(value) => {
+                LcsfValidAtt::from_duration(data_type, value).map(LcsfBuildEntry::Att)
+            }
+            LcsfSetValue::Array(elem_type, data) => {
+                LcsfValidAtt::from_packed(data_type, elem_type, data).map(LcsfBuildEntry::Att)
+            }
+            LcsfSetValue::Message(prot_id, nested_cmd) => {
+                // Protocol must be accepted by the attribute
+                if !att_desc.inner_prot_id_arr.is_empty()
+                    && !att_desc.inner_prot_id_arr.contains(&prot_id)
+                {
+                    Err(LcsfValueErrorEnum::OutOfRange)
+                } else {
+                    LcsfValidAtt::from_message(data_type, prot_id, nested_cmd)
+                        .map(LcsfBuildEntry::Att)
+                }
+            }
+        }
+        .map_err(|err| LcsfBuildErrorEnum::WrongValue(self.get_path(idx), err))?;
+        // Note entry
+        if att_desc.cardinality == LcsfAttCardinality::Single {
+            self.entry_arr[idx].clear();
+        }
+        self.entry_arr[idx].push(entry);
+        Ok(self)
+    }
+
+    /// Recursively build the attribute array, noting missing mandatory attributes,
+    /// filling absent attributes with their default value and checking exclusive choices
+    /// and conditional requirements
+    ///
+    /// miss_att_arr: missing attribute paths array reference
+    ///
+    /// default_att_arr: defaulted attribute paths array reference
+    fn build_rec(
+        &self,
+        miss_att_arr: &mut Vec<Vec<u16>>,
+        default_att_arr: &mut Vec<Vec<u16>>,
+    ) -> Result<Vec<LcsfValidAtt>, LcsfBuildErrorEnum> {
+        let mut valid_att_arr: Vec<LcsfValidAtt> = Vec::new();
+        for (idx, (_, att_desc)) in self.att_desc_arr.iter().enumerate() {
+            let entry_list = &self.entry_arr[idx];
+            // Check missing attribute
+            if entry_list.is_empty() && !att_desc.is_optional {
+                miss_att_arr.push(self.get_path(idx));
+            }
+            // Build entries
+            let mut item_arr: Vec<LcsfValidAtt> = Vec::new();
+            for entry in entry_list {
+                item_arr.push(match entry {
+                    LcsfBuildEntry::Att(valid_att) => valid_att.clone(),
+                    LcsfBuildEntry::Subatt(builder) => {
+                        let subatt_arr = builder.build_rec(miss_att_arr, default_att_arr)?;
+                        if !att_desc.oneof.check(&subatt_arr) {
+                            return Err(LcsfBuildErrorEnum::WrongOneofNb(self.get_path(idx)));
+                        }
+                        LcsfValidAtt {
+                            payload: LcsfValidAttPayload::SubattArr(subatt_arr),
+                        }
+                    }
+                });
+            }
+            match att_desc.cardinality {
+                LcsfAttCardinality::Single => {
+                    let valid_att = match (item_arr.pop(), &att_desc.default_value) {
+                        (Some(valid_att), _) => valid_att,
+                        (None, Some(default_value)) => {
+                            default_att_arr.push(self.get_path(idx));
+                            LcsfValidAtt {
+                                payload: LcsfValidAttPayload::Data(default_value.clone()),
+                            }
+                        }
+                        (None, None) => LcsfValidAtt {
+                            payload: LcsfValidAttPayload::Absent,
+                        },
+                    };
+                    valid_att_arr.push(valid_att);
+                }
+                LcsfAttCardinality::Repeated {
+                    min_count,
+                    max_count,
+                } => {
+                    let item_nb = item_arr.len();
+                    if item_nb > 0
+                        && (min_count.is_some_and(|min| item_nb < min)
+                            || max_count.is_some_and(|max| item_nb > max))
+                    {
+                        return Err(LcsfBuildErrorEnum::WrongAttCount(self.get_path(idx)));
+                    }
+                    valid_att_arr.push(LcsfValidAtt {
+                        payload: LcsfValidAttPayload::List(item_arr),
+                    });
+                }
+            }
+        }
+        // Check conditional requirements
+        if let Some(&idx) =
+            lcsf_validator::find_missing_required(self.att_desc_arr, &valid_att_arr).first()
+        {
+            return Err(LcsfBuildErrorEnum::MissRequiredAtt(self.get_path(idx)));
+        }
+        Ok(valid_att_arr)
+    }
+}
+
+#[allow(dead_code)]
+impl<'a> LcsfCmdBuilder<'a> {
+    /// Create a command builder
+    ///
+    /// cmd_id: command id value
+    ///
+    /// cmd_desc: command descriptor reference
+    pub fn new(cmd_id: u16, cmd_desc: &'a LcsfCmdDesc) -> Self {
+        LcsfCmdBuilder {
+            cmd_id,
+            att_builder: LcsfAttBuilder::new(Vec::new(), &cmd_desc.att_desc_arr),
+        }
+    }
+
+    /// Create a command builder from the command name
+    ///
+    /// prot_desc: protocol descriptor reference
+    ///
+    /// name: command name
+    pub fn from_name(prot_desc: &'a LcsfProtDesc, name: &str) -> Option<Self> {
+        let (cmd_id, cmd_desc) = prot_desc.get_cmd_by_name(name)?;
+        Some(LcsfCmdBuilder::new(cmd_id, cmd_desc))
+    }
+
+    /// Return a builder for the sub-attributes of an attribute, see [LcsfAttBuilder::subatt_builder]
+    ///
+    /// key: attribute id or name
+    pub fn subatt_builder<'k>(
+        &self,
+        key: impl Into<LcsfAttKey<'k>>,
+    ) -> Result<LcsfAttBuilder<'a>, LcsfBuildErrorEnum> {
+        self.att_builder.subatt_builder(key)
+    }
+
+    /// Set an attribute value, see [LcsfAttBuilder::set]
+    ///
+    /// key: attribute id or name
+    ///
+    /// value: attribute value
+    pub fn set<'k, 'v>(
+        &mut self,
+        key: impl Into<LcsfAttKey<'k>>,
+        value: impl Into<LcsfSetValue<'v, 'a>>,
+    ) -> Result<&mut Self, LcsfBuildErrorEnum> {
+        self.att_builder.set(key, value)?;
+        Ok(self)
+    }
+
+    /// Build the command, absent optional attributes are filled automatically,
+    /// with their default value if they have one
+    pub fn build(&self) -> Result<LcsfValidCmd, LcsfBuildErrorEnum> {
+        self.build_with_info().map(|(valid_cmd, _)| valid_cmd)
+    }
+
+    /// Build the command like build(), also returns the defaulted attribute paths,
+    /// see [LcsfValidInfo]
+    pub fn build_with_info(&self) -> Result<(LcsfValidCmd, LcsfValidInfo), LcsfBuildErrorEnum> {
+        let mut miss_att_arr: Vec<Vec<u16>> = Vec::new();
+        let mut info = LcsfValidInfo::default();
+        let att_arr = self
+            .att_builder
+            .build_rec(&mut miss_att_arr, &mut info.default_att_arr)?;
+        if !miss_att_arr.is_empty() {
+            return Err(LcsfBuildErrorEnum::MissMandatoryAtt(miss_att_arr));
+        }
+        let valid_cmd = LcsfValidCmd {
+            cmd_id: self.cmd_id,
+            att_arr,
+        };
+        Ok((valid_cmd, info))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcsf_lib::lcsf_transcoder::LcsfModeEnum;
+    use crate::lcsf_lib::lcsf_validator::LcsfAttCond;
+    use crate::lcsf_lib::lcsf_validator::LcsfAttScale;
+    use crate::lcsf_lib::lcsf_validator::LcsfOneof;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        static ref TEST_PROT_DESC: LcsfProtDesc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x12,
+                LcsfCmdDesc {
+                    name: "CMD".into(),
+                    att_desc_arr: vec![
+                        (
+                            0x01,
+                            LcsfAttDesc {
+                                name: "sa1".into(),
+                                data_type: LcsfDataType::Uint16,
+                                ..Default::default()
+                            }
+                        ),
+                        (
+                            0x02,
+                            LcsfAttDesc {
+                                name: "sa2".into(),
+                                is_optional: true,
+                                data_type: LcsfDataType::String,
+                                ..Default::default()
+                            }
+                        ),
+                        (
+                            0x03,
+                            LcsfAttDesc {
+                                name: "ca1".into(),
+                                data_type: LcsfDataType::Subattributes,
+                                subatt_desc_arr: vec![
+                                    (
+                                        0x00,
+                                        LcsfAttDesc {
+                                            name: "sa3".into(),
+                                            data_type: LcsfDataType::Float32,
+                                            ..Default::default()
+                                        }
+                                    ),
+                                    (
+                                        0x01,
+                                        LcsfAttDesc {
+                                            name: "sa4".into(),
+                                            data_type: LcsfDataType::ByteArray,
+                                            ..Default::default()
+                                        }
+                                    ),
+                                ],
+                                ..Default::default()
+                            }
+                        ),
+                        (
+                            0x04,
+                            LcsfAttDesc {
+                                name: "sa5".into(),
+                                is_optional: true,
+                                data_type: LcsfDataType::Uint8,
+                                cardinality: LcsfAttCardinality::Repeated {
+                                    min_count: Some(2),
+                                    max_count: Some(3),
+                                },
+                                ..Default::default()
+                            }
+                        ),
+                    ],
+                    ..Default::default()
+                }
+            )],
+            ..Default::default()
+        };
+    }
+
+    fn data_att(data: &[u8]) -> LcsfValidAtt {
+        LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(data.to_vec()),
+        }
+    }
+
+    #[test]
+    fn test_cmd_builder() {
+        let mut builder = LcsfCmdBuilder::from_name(&TEST_PROT_DESC, "CMD").unwrap();
+        assert!(LcsfCmdBuilder::from_name(&TEST_PROT_DESC, "OTHER").is_none());
+        // Test missing attributes
+        assert_eq!(
+            builder.build(),
+            Err(LcsfBuildErrorEnum::MissMandatoryAtt(vec![
+                vec![0x01],
+                vec![0x03]
+            ]))
+        );
+        let mut ca1_builder = builder.subatt_builder("ca1").unwrap();
+        ca1_builder.set(0x01u16, &[0xaa, 0xbb][..]).unwrap();
+        builder.set("ca1", ca1_builder).unwrap();
+        assert_eq!(
+            builder.build(),
+            Err(LcsfBuildErrorEnum::MissMandatoryAtt(vec![
+                vec![0x01],
+                vec![0x03, 0x00]
+            ]))
+        );
+        // Test set in any order
+        let mut ca1_builder = builder.subatt_builder(0x03u16).unwrap();
+        ca1_builder
+            .set("sa4", &[0xaa, 0xbb][..])
+            .unwrap()
+            .set("sa3", 1.5f32)
+            .unwrap();
+        builder
+            .set("ca1", ca1_builder)
+            .unwrap()
+            .set("sa1", 0x1234u16)
+            .unwrap();
+        let valid_cmd = builder.build().unwrap();
+        assert_eq!(
+            valid_cmd,
+            LcsfValidCmd {
+                cmd_id: 0x12,
+                att_arr: vec![
+                    data_att(&[0x34, 0x12]),
+                    LcsfValidAtt {
+                        payload: LcsfValidAttPayload::Absent,
+                    },
+                    LcsfValidAtt {
+                        payload: LcsfValidAttPayload::SubattArr(vec![
+                            data_att(&1.5f32.to_le_bytes()),
+                            data_att(&[0xaa, 0xbb]),
+                        ]),
+                    },
+                    LcsfValidAtt {
+                        payload: LcsfValidAttPayload::List(Vec::new()),
+                    },
+                ],
+            }
+        );
+        let cmd_desc = &TEST_PROT_DESC.cmd_desc_arr[0].1;
+        assert!(lcsf_validator::encode_valid(0xab, cmd_desc, &valid_cmd).is_ok());
+        // Test replace and repeated attributes
+        builder.set("sa1", 7).unwrap().set("sa2", "Hi").unwrap();
+        builder.set("sa5", 1u8).unwrap();
+        assert_eq!(
+            builder.build(),
+            Err(LcsfBuildErrorEnum::WrongAttCount(vec![0x04]))
+        );
+        builder.set("sa5", 2u8).unwrap();
+        let valid_cmd = builder.build().unwrap();
+        assert_eq!(valid_cmd.att_arr[0], data_att(&[7]));
+        assert_eq!(valid_cmd.att_arr[1], data_att(b"Hi\0"));
+        assert_eq!(
+            valid_cmd.att_arr[3],
+            LcsfValidAtt {
+                payload: LcsfValidAttPayload::List(vec![data_att(&[1]), data_att(&[2])]),
+            }
+        );
+        assert!(lcsf_validator::encode_valid(0xab, cmd_desc, &valid_cmd).is_ok());
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        let cmd_desc = &TEST_PROT_DESC.cmd_desc_arr[0].1;
+        let mut builder = LcsfCmdBuilder::new(0x12, cmd_desc);
+        // Test unknown attributes
+        assert_eq!(
+            builder.set("sa9", 1).unwrap_err(),
+            LcsfBuildErrorEnum::UnknownAtt(Vec::new(), "sa9".into())
+        );
+        assert_eq!(
+            builder.set(0x09u16, 1).unwrap_err(),
+            LcsfBuildErrorEnum::UnknownAtt(Vec::new(), "0x09".into())
+        );
+        let mut ca1_builder = builder.subatt_builder("ca1").unwrap();
+        assert_eq!(
+            ca1_builder.set("sa1", 1).unwrap_err(),
+            LcsfBuildErrorEnum::UnknownAtt(vec![0x03], "sa1".into())
+        );
+        // Test wrong values
+        assert_eq!(
+            builder.set("sa1", 0x10000u32).unwrap_err(),
+            LcsfBuildErrorEnum::WrongValue(vec![0x01], LcsfValueErrorEnum::OutOfRange)
+        );
+        assert_eq!(
+            builder.set("sa1", -1).unwrap_err(),
+            LcsfBuildErrorEnum::WrongValue(vec![0x01], LcsfValueErrorEnum::OutOfRange)
+        );
+        assert_eq!(
+            builder.set("sa2", 1).unwrap_err(),
+            LcsfBuildErrorEnum::WrongValue(vec![0x02], LcsfValueErrorEnum::WrongDataType)
+        );
+        assert_eq!(
+            ca1_builder.set("sa3", 0.1).unwrap_err(),
+            LcsfBuildErrorEnum::WrongValue(vec![0x03, 0x00], LcsfValueErrorEnum::OutOfRange)
+        );
+        assert_eq!(
+            builder
+                .set("sa2", LcsfSetValue::array(&[1u16, 2]))
+                .unwrap_err(),
+            LcsfBuildErrorEnum::WrongValue(vec![0x02], LcsfValueErrorEnum::WrongDataType)
+        );
+        assert_eq!(
+            builder.set("sa1", Duration::ZERO).unwrap_err(),
+            LcsfBuildErrorEnum::WrongValue(vec![0x01], LcsfValueErrorEnum::WrongDataType)
+        );
+        assert_eq!(
+            builder.subatt_builder("sa1").unwrap_err(),
+            LcsfBuildErrorEnum::WrongValue(vec![0x01], LcsfValueErrorEnum::WrongDataType)
+        );
+        assert_eq!(
+            builder.set("sa1", ca1_builder).unwrap_err(),
+            LcsfBuildErrorEnum::WrongValue(vec![0x01], LcsfValueErrorEnum::WrongDataType)
+        );
+    }
+
+    #[test]
+    fn test_scaled_builder() {
+        let cmd_desc = LcsfCmdDesc {
+            att_desc_arr: vec![(
+                0x01,
+                LcsfAttDesc {
+                    name: "volt".into(),
+                    data_type: LcsfDataType::Uint16,
+                    scale: Some(LcsfAttScale {
+                        factor: 0.001,
+                        offset: 0.0,
+                        unit: "V".into(),
+                    }),
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let mut builder = LcsfCmdBuilder::new(0x12, &cmd_desc);
+        // Test floats set the physical value, integers the raw value
+        builder.set("volt", 3.5).unwrap();
+        assert_eq!(builder.build().unwrap().att_arr[0], data_att(&[0xac, 0x0d]));
+        builder.set("volt", 12u16).unwrap();
+        assert_eq!(builder.build().unwrap().att_arr[0], data_att(&[12]));
+        assert_eq!(
+            builder.set("volt", 65.6).unwrap_err(),
+            LcsfBuildErrorEnum::WrongValue(vec![0x01], LcsfValueErrorEnum::OutOfRange)
+        );
+        assert_eq!(
+            builder.set("volt", -1.0).unwrap_err(),
+            LcsfBuildErrorEnum::WrongValue(vec![0x01], LcsfValueErrorEnum::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_default_builder() {
+        let cmd_desc = LcsfCmdDesc {
+            att_desc_arr: vec![
+                (
+                    0x01,
+                    LcsfAttDesc {
+                        name: "gain".into(),
+                        is_optional: true,
+                        data_type: LcsfDataType::Uint8,
+                        default_value: Some(vec![0x05]),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    0x02,
+                    LcsfAttDesc {
+                        name: "ca1".into(),
+                        data_type: LcsfDataType::Subattributes,
+                        subatt_desc_arr: vec![(
+                            0x00,
+                            LcsfAttDesc {
+                                name: "mode".into(),
+                                is_optional: true,
+                                data_type: LcsfDataType::Uint16,
+                                default_value: Some(vec![0x01]),
+                                ..Default::default()
+                            },
+                        )],
+                        ..Default::default()
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+        let mut builder = LcsfCmdBuilder::new(0x12, &cmd_desc);
+        let ca1_builder = builder.subatt_builder("ca1").unwrap();
+        builder.set("ca1", ca1_builder).unwrap();
+        // Test absent attributes are filled with their default value
+        let (valid_cmd, valid_info) = builder.build_with_info().unwrap();
+        assert_eq!(valid_cmd.att_arr[0], data_att(&[0x05]));
+        assert_eq!(
+            valid_cmd.att_arr[1],
+            LcsfValidAtt {
+                payload: LcsfValidAttPayload::SubattArr(vec![data_att(&[0x01])]),
+            }
+        );
+        assert_eq!(
+            valid_info.default_att_arr,
+            vec![vec![0x01], vec![0x02, 0x00]]
+        );
+        assert!(lcsf_validator::encode_valid(0xab, &cmd_desc, &valid_cmd).is_ok());
+        // Test set attributes are kept
+        builder.set("gain", 9u8).unwrap();
+        let (valid_cmd, valid_info) = builder.build_with_info().unwrap();
+        assert_eq!(valid_cmd.att_arr[0], data_att(&[0x09]));
+        assert_eq!(valid_info.default_att_arr, vec![vec![0x02, 0x00]]);
+        assert_eq!(builder.build(), Ok(valid_cmd));
+    }
+
+    #[test]
+    fn test_oneof_builder() {
+        let choice_desc = |oneof: LcsfOneof| LcsfCmdDesc {
+            att_desc_arr: vec![(
+                0x01,
+                LcsfAttDesc {
+                    name: "choice".into(),
+                    data_type: LcsfDataType::Subattributes,
+                    oneof,
+                    subatt_desc_arr: vec![
+                        (
+                            0x00,
+                            LcsfAttDesc {
+                                name: "a".into(),
+                                is_optional: true,
+                                data_type: LcsfDataType::Uint8,
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            0x01,
+                            LcsfAttDesc {
+                                name: "b".into(),
+                                is_optional: true,
+                                data_type: LcsfDataType::Uint8,
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        // Test exactly one member
+        let cmd_desc = choice_desc(LcsfOneof::ExactlyOne);
+        let mut builder = LcsfCmdBuilder::new(0x12, &cmd_desc);
+        let mut choice_builder = builder.subatt_builder("choice").unwrap();
+        builder.set("choice", choice_builder).unwrap();
+        assert_eq!(
+            builder.build(),
+            Err(LcsfBuildErrorEnum::WrongOneofNb(vec![0x01]))
+        );
+        choice_builder = builder.subatt_builder("choice").unwrap();
+        choice_builder.set("a", 1u8).unwrap();
+        builder.set("choice", choice_builder).unwrap();
+        let valid_cmd = builder.build().unwrap();
+        assert!(lcsf_validator::encode_valid(0xab, &cmd_desc, &valid_cmd).is_ok());
+        choice_builder = builder.subatt_builder("choice").unwrap();
+        choice_builder.set("a", 1u8).unwrap().set("b", 2u8).unwrap();
+        builder.set("choice", choice_builder).unwrap();
+        assert_eq!(
+            builder.build(),
+            Err(LcsfBuildErrorEnum::WrongOneofNb(vec![0x01]))
+        );
+        // Test at most one member
+        let cmd_desc = choice_desc(LcsfOneof::AtMostOne);
+        let mut builder = LcsfCmdBuilder::new(0x12, &cmd_desc);
+        choice_builder = builder.subatt_builder("choice").unwrap();
+        builder.set("choice", choice_builder).unwrap();
+        assert!(builder.build().is_ok());
+        choice_builder = builder.subatt_builder("choice").unwrap();
+        choice_builder.set("a", 1u8).unwrap().set("b", 2u8).unwrap();
+        builder.set("choice", choice_builder).unwrap();
+        assert_eq!(
+            builder.build(),
+            Err(LcsfBuildErrorEnum::WrongOneofNb(vec![0x01]))
+        );
+    }
+
+    #[test]
+    fn test_required_builder() {
+        let cmd_desc = LcsfCmdDesc {
+            att_desc_arr: vec![
+                (
+                    0x01,
+                    LcsfAttDesc {
+                        name: "mode".into(),
+                        data_type: LcsfDataType::Uint8,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    0x02,
+                    LcsfAttDesc {
+                        name: "ext".into(),
+                        is_optional: true,
+                        data_type: LcsfDataType::Uint32,
+                        required_if: Some(LcsfAttCond::Equals(0x01, 0xff)),
+                        ..Default::default()
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+        let mut builder = LcsfCmdBuilder::new(0x12, &cmd_desc);
+        // Test condition not met
+        builder.set("mode", 1u8).unwrap();
+        assert!(builder.build().is_ok());
+        // Test condition met
+        builder.set("mode", 0xffu8).unwrap();
+        assert_eq!(
+            builder.build(),
+            Err(LcsfBuildErrorEnum::MissRequiredAtt(vec![0x02]))
+        );
+        builder.set("ext", 7u32).unwrap();
+        let valid_cmd = builder.build().unwrap();
+        assert!(lcsf_validator::encode_valid(0xab, &cmd_desc, &valid_cmd).is_ok());
+    }
+
+    #[test]
+    fn test_nested_builder() {
+        let cmd_desc = LcsfCmdDesc {
+            att_desc_arr: vec![(
+                0x01,
+                LcsfAttDesc {
+                    name: "tunnel".into(),
+                    data_type: LcsfDataType::Message(LcsfModeEnum::Small),
+                    inner_prot_id_arr: vec![0x56],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let nested_cmd = LcsfValidCmd {
+            cmd_id: 0x02,
+            att_arr: vec![data_att(&[0x2a])],
+        };
+        let mut builder = LcsfCmdBuilder::new(0x12, &cmd_desc);
+        builder.set("tunnel", (0x56, nested_cmd.clone())).unwrap();
+        assert_eq!(
+            builder.build().unwrap().att_arr[0].payload,
+            LcsfValidAttPayload::Message(0x56, Box::new(nested_cmd.clone()))
+        );
+        // Test undeclared inner protocol
+        assert_eq!(
+            builder.set("tunnel", (0x55, nested_cmd)).unwrap_err(),
+            LcsfBuildErrorEnum::WrongValue(vec![0x01], LcsfValueErrorEnum::OutOfRange)
+        );
+    }
+}
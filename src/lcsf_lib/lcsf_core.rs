@@ -8,23 +8,77 @@
 //! along with this program. If not, see <https://www.gnu.org/licenses/>
 
 use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::Mutex;
 
 use crate::lcsf_lib::lcsf_error;
 use crate::lcsf_lib::lcsf_transcoder;
 use crate::lcsf_lib::lcsf_validator;
 use lcsf_error::LCSF_EP_PROT_DESC;
 use lcsf_error::LcsfEpLocEnum;
+use lcsf_transcoder::LcsfDecodeErrorEnum;
 use lcsf_transcoder::LcsfModeEnum;
 use lcsf_transcoder::LcsfRawMsg;
-use lcsf_validator::LcsfCmdDesc;
+use lcsf_validator::LcsfDescIssue;
 use lcsf_validator::LcsfProtDesc;
+use lcsf_validator::LcsfRole;
 use lcsf_validator::LcsfValidCmd;
+use lcsf_validator::LcsfValidInfo;
+use lcsf_validator::LcsfValidateError;
+use lcsf_validator::LcsfValidateErrorEnum;
 
 /// Callback prototype to process a valid command
 pub type ProtCallback = fn(&LcsfCore, &LcsfValidCmd);
 /// Callback prototype to send generated lcsf error messages
 pub type SendErrCallback = fn(&[u8]);
 
+/// Protocol descriptor argument of add_protocol(), converted to a shared descriptor
+pub trait IntoLcsfProtDesc {
+    /// Convert to a shared protocol descriptor
+    fn into_prot_desc(self) -> Arc<LcsfProtDesc>;
+}
+
+impl IntoLcsfProtDesc for LcsfProtDesc {
+    fn into_prot_desc(self) -> Arc<LcsfProtDesc> {
+        Arc::new(self)
+    }
+}
+
+impl IntoLcsfProtDesc for Arc<LcsfProtDesc> {
+    fn into_prot_desc(self) -> Arc<LcsfProtDesc> {
+        self
+    }
+}
+
+impl IntoLcsfProtDesc for &'static LcsfProtDesc {
+    fn into_prot_desc(self) -> Arc<LcsfProtDesc> {
+        Arc::new(self.clone())
+    }
+}
+
+/// Static descriptors behind a deref, like the generated lazy_static ones
+impl<T: Deref<Target = LcsfProtDesc>> IntoLcsfProtDesc for &'static T {
+    fn into_prot_desc(self) -> Arc<LcsfProtDesc> {
+        Arc::new((**self).clone())
+    }
+}
+
+/// Protocol registration error enum
+#[derive(Debug, PartialEq, Clone)]
+pub enum LcsfRegisterErrorEnum {
+    /// Protocol id reserved for the lcsf error protocol
+    ReservedProtId,
+    /// Protocol id too large for the representation mode
+    ProtIdOutOfRange,
+    /// Protocol id already registered, replace it explicitly with replace_protocol()
+    DuplicateProtId,
+    /// Protocol id not registered
+    UnknownProtId,
+    /// Inconsistent protocol descriptor, see [LcsfDescIssue]
+    InvalidDesc(Vec<LcsfDescIssue>),
+}
+
 /// Main lcsf structure
 #[derive(Debug)]
 pub struct LcsfCore {
@@ -32,12 +86,16 @@ pub struct LcsfCore {
     do_gen_err: bool,
     /// Lcsf representation mode to use
     lcsf_mode: LcsfModeEnum,
+    /// Local role, commands in the wrong direction are rejected
+    role: LcsfRole,
     /// Send callback for lcsf error
     fn_send_err: SendErrCallback,
-    /// Protocol descriptions hash map
-    prot_desc_map: HashMap<u16, &'static LcsfProtDesc>,
+    /// Protocol descriptions hash map, shared with the registering code
+    prot_desc_map: HashMap<u16, Arc<LcsfProtDesc>>,
     /// Protocol callbacks hash map
     prot_cb_map: HashMap<u16, ProtCallback>,
+    /// Info of the last validated command, see [LcsfValidInfo]
+    rx_info: Mutex<LcsfValidInfo>,
 }
 
 /// Default function to process received errors,
@@ -65,6 +123,16 @@ fn def_send_error(buff: &[u8]) {
     );
 }
 
+/// Return the lcsf error protocol id of a representation mode
+///
+/// mode: lcsf representation mode, see [LcsfModeEnum]
+fn get_err_prot_id(mode: LcsfModeEnum) -> u16 {
+    match mode {
+        LcsfModeEnum::Small => lcsf_error::LCSF_EP_PROT_ID_SMALL,
+        LcsfModeEnum::Normal => lcsf_error::LCSF_EP_PROT_ID_NORMAL,
+    }
+}
+
 impl LcsfCore {
     /// Create an instance of a LcsfCore
     ///
@@ -74,16 +142,15 @@ impl LcsfCore {
     ///
     /// do_gen_err: control lcsf error packet generation
     pub fn new(mode: LcsfModeEnum, do_gen_err: bool) -> Self {
-        let err_prot_id = match mode {
-            LcsfModeEnum::Small => lcsf_error::LCSF_EP_PROT_ID_SMALL,
-            LcsfModeEnum::Normal => lcsf_error::LCSF_EP_PROT_ID_NORMAL,
-        };
+        let err_prot_id = get_err_prot_id(mode);
         LcsfCore {
             do_gen_err,
             lcsf_mode: mode,
+            role: LcsfRole::Any,
             fn_send_err: def_send_error,
-            prot_desc_map: HashMap::from([(err_prot_id, &LCSF_EP_PROT_DESC as &LcsfProtDesc)]),
+            prot_desc_map: HashMap::from([(err_prot_id, Arc::new(LCSF_EP_PROT_DESC.clone()))]),
             prot_cb_map: HashMap::from([(err_prot_id, def_process_error as ProtCallback)]),
+            rx_info: Mutex::new(LcsfValidInfo::default()),
         }
     }
 
@@ -92,29 +159,143 @@ impl LcsfCore {
     /// rx_err_cb: new receive error callback
     /// tx_err_cb: new send error message callback
     pub fn update_err_cb(&mut self, rx_err_cb: ProtCallback, tx_err_cb: SendErrCallback) {
-        let err_prot_id = match self.lcsf_mode {
-            LcsfModeEnum::Small => lcsf_error::LCSF_EP_PROT_ID_SMALL,
-            LcsfModeEnum::Normal => lcsf_error::LCSF_EP_PROT_ID_NORMAL,
-        };
+        let err_prot_id = get_err_prot_id(self.lcsf_mode);
         self.prot_cb_map.insert(err_prot_id, rx_err_cb);
         self.fn_send_err = tx_err_cb;
     }
 
-    /// Add a protocol
+    /// Change the local role, checked against the command directions
+    ///
+    /// role: new local role, see [LcsfRole]
+    pub fn set_role(&mut self, role: LcsfRole) {
+        self.role = role;
+    }
+
+    /// Check a protocol can be registered with the core
     ///
     /// prot_id: protocol id
     ///
     /// prot_desc: protocol descriptor reference
+    fn check_protocol(
+        &self,
+        prot_id: u16,
+        prot_desc: &LcsfProtDesc,
+    ) -> Result<(), LcsfRegisterErrorEnum> {
+        if prot_id == get_err_prot_id(self.lcsf_mode) {
+            return Err(LcsfRegisterErrorEnum::ReservedProtId);
+        }
+        if self.lcsf_mode == LcsfModeEnum::Small && prot_id > 0xFF {
+            return Err(LcsfRegisterErrorEnum::ProtIdOutOfRange);
+        }
+        prot_desc
+            .check(self.lcsf_mode)
+            .map_err(LcsfRegisterErrorEnum::InvalidDesc)
+    }
+
+    /// Add a protocol, panics if try_add_protocol() refuses it
+    ///
+    /// prot_id: protocol id
+    ///
+    /// prot_desc: protocol descriptor, static reference, owned or shared through an Arc
     ///
     /// prot_cb: protocol callback
     pub fn add_protocol(
         &mut self,
         prot_id: u16,
-        prot_desc: &'static LcsfProtDesc,
+        prot_desc: impl IntoLcsfProtDesc,
         prot_cb: ProtCallback,
     ) {
+        if let Err(err) = self.try_add_protocol(prot_id, prot_desc, prot_cb) {
+            panic!("add_protocol failed for protocol {prot_id:#x} with err {err:?}");
+        }
+    }
+
+    /// Add a protocol, refused if its id is reserved or already registered,
+    /// or if its descriptor is inconsistent for the core mode
+    ///
+    /// prot_id: protocol id
+    ///
+    /// prot_desc: protocol descriptor, static reference, owned or shared through an Arc
+    ///
+    /// prot_cb: protocol callback
+    pub fn try_add_protocol(
+        &mut self,
+        prot_id: u16,
+        prot_desc: impl IntoLcsfProtDesc,
+        prot_cb: ProtCallback,
+    ) -> Result<(), LcsfRegisterErrorEnum> {
+        let prot_desc = prot_desc.into_prot_desc();
+        self.check_protocol(prot_id, &prot_desc)?;
+        if self.prot_desc_map.contains_key(&prot_id) {
+            return Err(LcsfRegisterErrorEnum::DuplicateProtId);
+        }
         self.prot_desc_map.insert(prot_id, prot_desc);
         self.prot_cb_map.insert(prot_id, prot_cb);
+        Ok(())
+    }
+
+    /// Replace a registered protocol, with the same checks as try_add_protocol()
+    ///
+    /// prot_id: protocol id
+    ///
+    /// prot_desc: new protocol descriptor, static reference, owned or shared through an Arc
+    ///
+    /// prot_cb: new protocol callback
+    pub fn replace_protocol(
+        &mut self,
+        prot_id: u16,
+        prot_desc: impl IntoLcsfProtDesc,
+        prot_cb: ProtCallback,
+    ) -> Result<(), LcsfRegisterErrorEnum> {
+        let prot_desc = prot_desc.into_prot_desc();
+        self.check_protocol(prot_id, &prot_desc)?;
+        if !self.prot_desc_map.contains_key(&prot_id) {
+            return Err(LcsfRegisterErrorEnum::UnknownProtId);
+        }
+        self.prot_desc_map.insert(prot_id, prot_desc);
+        self.prot_cb_map.insert(prot_id, prot_cb);
+        Ok(())
+    }
+
+    /// Remove a registered protocol, the lcsf error protocol can't be removed
+    ///
+    /// prot_id: protocol id
+    pub fn remove_protocol(&mut self, prot_id: u16) -> Result<(), LcsfRegisterErrorEnum> {
+        if prot_id == get_err_prot_id(self.lcsf_mode) {
+            return Err(LcsfRegisterErrorEnum::ReservedProtId);
+        }
+        if self.prot_desc_map.remove(&prot_id).is_none() {
+            return Err(LcsfRegisterErrorEnum::UnknownProtId);
+        }
+        self.prot_cb_map.remove(&prot_id);
+        Ok(())
+    }
+
+    /// Return a shared reference to a registered protocol descriptor
+    ///
+    /// prot_id: protocol id
+    #[allow(dead_code)]
+    pub fn get_prot_desc(&self, prot_id: u16) -> Option<Arc<LcsfProtDesc>> {
+        self.prot_desc_map.get(&prot_id).cloned()
+    }
+
+    /// Return the info of the last validated command, to call from a protocol callback
+    #[allow(dead_code)]
+    pub fn get_rx_info(&self) -> LcsfValidInfo {
+        self.rx_info.lock().unwrap().clone()
+    }
+
+    /// Return the sorted ids of the registered protocols, without the lcsf error protocol
+    pub fn get_prot_id_list(&self) -> Vec<u16> {
+        let err_prot_id = get_err_prot_id(self.lcsf_mode);
+        let mut prot_id_list: Vec<u16> = self
+            .prot_desc_map
+            .keys()
+            .copied()
+            .filter(|prot_id| *prot_id != err_prot_id)
+            .collect();
+        prot_id_list.sort_unstable();
+        prot_id_list
     }
 
     /// Process an incoming lcsf message
@@ -139,43 +320,94 @@ impl LcsfCore {
             Ok(msg) => msg,
         };
         // Send to validator
-        let (valid_msg, prot_id) = match lcsf_validator::validate_msg(&self.prot_desc_map, &raw_msg)
-        {
-            Err(err) => {
-                println!("validate_msg failed with err {err:?}");
-                if self.do_gen_err {
-                    // Generate and send error
-                    let buff = lcsf_error::encode_error(
-                        self.lcsf_mode,
-                        LcsfEpLocEnum::ValidationError,
-                        err as u8,
-                    );
-                    (self.fn_send_err)(&buff);
+        let (valid_msg, prot_id) =
+            match lcsf_validator::validate_msg_as(&self.prot_desc_map, self.role, &raw_msg) {
+                Err(err) => {
+                    println!("validate_msg failed with err {err}");
+                    if self.do_gen_err {
+                        // Generate and send error
+                        let buff = lcsf_error::encode_error(
+                            self.lcsf_mode,
+                            LcsfEpLocEnum::ValidationError,
+                            err.kind as u8,
+                        );
+                        (self.fn_send_err)(&buff);
+                    }
+                    return false;
                 }
-                return false;
-            }
-            Ok((msg, id)) => (msg, id),
-        };
+                Ok((msg, id, info)) => {
+                    *self.rx_info.lock().unwrap() = info;
+                    (msg, id)
+                }
+            };
         // Dispatch command
         let prot_cb = self.prot_cb_map.get(&prot_id).unwrap();
         prot_cb(self, &valid_msg);
         true
     }
 
-    /// Send an outgoing valid command
+    /// Check an incoming lcsf message against the registered protocols and return every
+    /// validation error found, without dispatching it or generating error messages
+    ///
+    /// buff: buffer reference
+    pub fn diagnose_buff(
+        &self,
+        buff: &[u8],
+    ) -> Result<Vec<LcsfValidateError>, LcsfDecodeErrorEnum> {
+        let raw_msg = lcsf_transcoder::decode_buff(self.lcsf_mode, buff)?;
+        match lcsf_validator::validate_msg_all_as(&self.prot_desc_map, self.role, &raw_msg) {
+            Err(err_arr) => Ok(err_arr),
+            Ok(_) => Ok(Vec::new()),
+        }
+    }
+
+    /// Send an outgoing valid command, panics if try_send_cmd() fails
     ///
     /// prot_id: protocol id
     ///
     /// valid_cmd: valid command reference
     pub fn send_cmd(&self, prot_id: u16, valid_cmd: &LcsfValidCmd) -> Vec<u8> {
+        match self.try_send_cmd(prot_id, valid_cmd) {
+            Ok(buff) => buff,
+            Err(err) => panic!("send_cmd failed with err {err}"),
+        }
+    }
+
+    /// Send an outgoing valid command, returns the encoded buffer or the encoding error
+    ///
+    /// prot_id: protocol id
+    ///
+    /// valid_cmd: valid command reference
+    pub fn try_send_cmd(
+        &self,
+        prot_id: u16,
+        valid_cmd: &LcsfValidCmd,
+    ) -> Result<Vec<u8>, LcsfValidateError> {
+        let cmd_error = |kind| LcsfValidateError {
+            kind,
+            cmd_id: valid_cmd.cmd_id,
+            att_path: Vec::new(),
+            data_type: None,
+            shape: None,
+            nested: None,
+        };
         // Retrieve cmd desc
-        let prot_desc = self.prot_desc_map.get(&prot_id).unwrap();
-        let cmd_desc_map: HashMap<u16, LcsfCmdDesc> =
-            prot_desc.cmd_desc_arr.iter().cloned().collect();
-        let cmd_desc = cmd_desc_map.get(&valid_cmd.cmd_id).unwrap();
-        let raw_msg = lcsf_validator::encode_valid(prot_id, cmd_desc, valid_cmd).unwrap();
+        let prot_desc = self
+            .prot_desc_map
+            .get(&prot_id)
+            .ok_or(cmd_error(LcsfValidateErrorEnum::UnknownProtId))?;
+        let cmd_desc = prot_desc
+            .get_cmd_desc(valid_cmd.cmd_id)
+            .ok_or(cmd_error(LcsfValidateErrorEnum::UnknownCmdId))?;
+        let raw_msg = lcsf_validator::encode_valid_as(
+            &self.prot_desc_map,
+            self.role,
+            prot_id,
+            cmd_desc,
+            valid_cmd,
+        )?;
         // Return buffer
-        lcsf_transcoder::encode_buff(self.lcsf_mode, &raw_msg)
+        Ok(lcsf_transcoder::encode_buff(self.lcsf_mode, &raw_msg))
     }
 
     /// Process an incoming lcsf message, when you want to bypass protocol handling
@@ -213,6 +445,11 @@ impl LcsfCore {
 mod tests {
     use super::*;
     use lazy_static::lazy_static;
+    use lcsf_validator::LcsfCmdDesc;
+    use lcsf_validator::LcsfCmdDirection;
+    use lcsf_validator::LcsfValidAtt;
+    use lcsf_validator::LcsfValidAttPayload;
+    use std::collections::HashSet;
 
     // Mock for SendCallback
     fn dummy_send_callback(_: &[u8]) {
@@ -228,8 +465,20 @@ mod tests {
                 0x12,
                 LcsfCmdDesc {
                     att_desc_arr: Vec::new(),
+                    ..Default::default()
+                }
+            ),],
+            ..Default::default()
+        };
+        static ref TEST_DIR_PROT_DESC: LcsfProtDesc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x12,
+                LcsfCmdDesc {
+                    direction: LcsfCmdDirection::ToDevice,
+                    ..Default::default()
                 }
-            ),]
+            ),],
+            ..Default::default()
         };
         static ref TEST_VALID_CMD: LcsfValidCmd = LcsfValidCmd {
             cmd_id: 0x12,
@@ -291,7 +540,7 @@ mod tests {
     fn test_add_protocol() {
         let mut lcsf_core = LcsfCore::new(LcsfModeEnum::Normal, false);
         // Add protocol
-        lcsf_core.add_protocol(0xab, &TEST_PROT_DESC, dummy_prot_callback);
+        lcsf_core.add_protocol(0xab, TEST_PROT_DESC.clone(), dummy_prot_callback);
         // Check values
         let prot_desc = lcsf_core.prot_desc_map.get(&0xab).unwrap();
         let callback = lcsf_core.prot_cb_map.get(&0xab).unwrap();
@@ -299,6 +548,135 @@ mod tests {
         if !std::ptr::fn_addr_eq(*callback, dummy_prot_callback as ProtCallback) {
             panic!("Invalid tx callback pointer");
         }
+        // Test static descriptor references, like the generated ones
+        lcsf_core.add_protocol(0xac, &TEST_PROT_DESC, dummy_prot_callback);
+        let static_desc: &'static LcsfProtDesc = Box::leak(Box::new(TEST_PROT_DESC.clone()));
+        lcsf_core.add_protocol(0xad, static_desc, dummy_prot_callback);
+        assert_eq!(
+            **lcsf_core.prot_desc_map.get(&0xac).unwrap(),
+            *TEST_PROT_DESC
+        );
+        assert_eq!(
+            **lcsf_core.prot_desc_map.get(&0xad).unwrap(),
+            *TEST_PROT_DESC
+        );
+        // Test inconsistent descriptor
+        let bad_prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x100,
+                LcsfCmdDesc {
+                    att_desc_arr: Vec::new(),
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let mut lcsf_core = LcsfCore::new(LcsfModeEnum::Small, false);
+        match lcsf_core.try_add_protocol(0xac, bad_prot_desc, dummy_prot_callback) {
+            Ok(_) => panic!("try_add_protocol should have failed"),
+            Err(err) => assert!(matches!(err, LcsfRegisterErrorEnum::InvalidDesc(_))),
+        }
+        assert!(!lcsf_core.prot_desc_map.contains_key(&0xac));
+        assert!(!lcsf_core.prot_cb_map.contains_key(&0xac));
+        // Test reserved and out of range ids
+        assert_eq!(
+            lcsf_core.try_add_protocol(0xff, TEST_PROT_DESC.clone(), dummy_prot_callback),
+            Err(LcsfRegisterErrorEnum::ReservedProtId)
+        );
+        assert_eq!(
+            lcsf_core.try_add_protocol(0x1ab, TEST_PROT_DESC.clone(), dummy_prot_callback),
+            Err(LcsfRegisterErrorEnum::ProtIdOutOfRange)
+        );
+        let mut lcsf_core = LcsfCore::new(LcsfModeEnum::Normal, false);
+        assert_eq!(
+            lcsf_core.try_add_protocol(0xffff, TEST_PROT_DESC.clone(), dummy_prot_callback),
+            Err(LcsfRegisterErrorEnum::ReservedProtId)
+        );
+        assert_eq!(
+            lcsf_core.try_add_protocol(0x1ab, TEST_PROT_DESC.clone(), dummy_prot_callback),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "DuplicateProtId")]
+    fn test_add_protocol_panic() {
+        let mut lcsf_core = LcsfCore::new(LcsfModeEnum::Small, false);
+        lcsf_core.add_protocol(0xab, TEST_PROT_DESC.clone(), dummy_prot_callback);
+        lcsf_core.add_protocol(0xab, TEST_PROT_DESC.clone(), dummy_prot_callback);
+    }
+
+    // Second mock for ProtCallback
+    fn other_prot_callback(_: &LcsfCore, _: &LcsfValidCmd) {}
+
+    #[test]
+    fn test_replace_remove_protocol() {
+        let mut lcsf_core = LcsfCore::new(LcsfModeEnum::Small, false);
+        assert_eq!(lcsf_core.get_prot_id_list(), Vec::<u16>::new());
+        // Test duplicate
+        lcsf_core.add_protocol(0xab, TEST_PROT_DESC.clone(), dummy_prot_callback);
+        assert_eq!(
+            lcsf_core.try_add_protocol(0xab, TEST_PROT_DESC.clone(), other_prot_callback),
+            Err(LcsfRegisterErrorEnum::DuplicateProtId)
+        );
+        // Test replace
+        assert_eq!(
+            lcsf_core.replace_protocol(0xac, TEST_PROT_DESC.clone(), other_prot_callback),
+            Err(LcsfRegisterErrorEnum::UnknownProtId)
+        );
+        assert_eq!(
+            lcsf_core.replace_protocol(0xff, TEST_PROT_DESC.clone(), other_prot_callback),
+            Err(LcsfRegisterErrorEnum::ReservedProtId)
+        );
+        lcsf_core
+            .replace_protocol(0xab, TEST_PROT_DESC.clone(), other_prot_callback)
+            .unwrap();
+        let callback = lcsf_core.prot_cb_map.get(&0xab).unwrap();
+        if !std::ptr::fn_addr_eq(*callback, other_prot_callback as ProtCallback) {
+            panic!("Invalid protocol callback pointer");
+        }
+        // Test list
+        lcsf_core.add_protocol(0x12, TEST_PROT_DESC.clone(), dummy_prot_callback);
+        assert_eq!(lcsf_core.get_prot_id_list(), vec![0x12, 0xab]);
+        // Test remove
+        assert_eq!(
+            lcsf_core.remove_protocol(0xff),
+            Err(LcsfRegisterErrorEnum::ReservedProtId)
+        );
+        assert_eq!(
+            lcsf_core.remove_protocol(0xac),
+            Err(LcsfRegisterErrorEnum::UnknownProtId)
+        );
+        assert_eq!(lcsf_core.remove_protocol(0xab), Ok(()));
+        assert!(!lcsf_core.prot_cb_map.contains_key(&0xab));
+        assert_eq!(lcsf_core.get_prot_id_list(), vec![0x12]);
+    }
+
+    #[test]
+    fn test_shared_prot_desc() {
+        let mut lcsf_core = LcsfCore::new(LcsfModeEnum::Small, false);
+        let prot_desc = Arc::new(TEST_PROT_DESC.clone());
+        // Test shared descriptor
+        lcsf_core.add_protocol(0xab, Arc::clone(&prot_desc), dummy_prot_callback);
+        assert_eq!(Arc::strong_count(&prot_desc), 2);
+        assert!(Arc::ptr_eq(
+            &lcsf_core.get_prot_desc(0xab).unwrap(),
+            &prot_desc
+        ));
+        assert_eq!(lcsf_core.get_prot_desc(0xac), None);
+        // Test swap and drop
+        let mut new_prot_desc = TEST_PROT_DESC.clone();
+        new_prot_desc.name = "NEW".into();
+        lcsf_core
+            .replace_protocol(0xab, new_prot_desc, dummy_prot_callback)
+            .unwrap();
+        assert_eq!(Arc::strong_count(&prot_desc), 1);
+        assert_eq!(lcsf_core.get_prot_desc(0xab).unwrap().name, "NEW");
+        lcsf_core.remove_protocol(0xab).unwrap();
+        assert_eq!(lcsf_core.get_prot_desc(0xab), None);
+        // Test equal descriptors hash the same
+        let desc_set = HashSet::from([TEST_PROT_DESC.clone(), (*prot_desc).clone()]);
+        assert_eq!(desc_set.len(), 1);
     }
 
     use std::sync::atomic::{AtomicBool, Ordering};
@@ -315,7 +693,7 @@ mod tests {
     fn test_receive_buff() {
         let mut lcsf_core = LcsfCore::new(LcsfModeEnum::Small, false);
         // Add protocol
-        lcsf_core.add_protocol(0xab, &TEST_PROT_DESC, test_prot_callback);
+        lcsf_core.add_protocol(0xab, TEST_PROT_DESC.clone(), test_prot_callback);
         // Test function
         assert!(lcsf_core.receive_buff(&TEST_BUFF));
         // Check value
@@ -323,14 +701,99 @@ mod tests {
         assert!(is_valid);
     }
 
+    static INFO_IS_VALID: AtomicBool = AtomicBool::new(false);
+
+    fn test_info_callback(core: &LcsfCore, _: &LcsfValidCmd) {
+        let rx_info = core.get_rx_info();
+        if rx_info.unknown_att_arr.len() == 1 && rx_info.unknown_att_arr[0].0 == vec![0x01] {
+            INFO_IS_VALID.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_rx_info() {
+        let mut lcsf_core = LcsfCore::new(LcsfModeEnum::Small, false);
+        // Add protocol keeping unknown attributes
+        let mut prot_desc = TEST_PROT_DESC.clone();
+        prot_desc.policy.ignore_unknown_att = true;
+        prot_desc.policy.keep_unknown_att = true;
+        lcsf_core.add_protocol(0xab, prot_desc, test_info_callback);
+        // Test unknown attribute is available to the callback
+        assert!(lcsf_core.receive_buff(&[0xab, 0x12, 0x01, 0x01, 0x01, 0x2a]));
+        assert!(INFO_IS_VALID.load(Ordering::SeqCst));
+        // Test info is replaced by the next command
+        assert!(lcsf_core.receive_buff(&TEST_BUFF));
+        assert_eq!(lcsf_core.get_rx_info(), LcsfValidInfo::default());
+    }
+
+    #[test]
+    fn test_diagnose_buff() {
+        let mut lcsf_core = LcsfCore::new(LcsfModeEnum::Small, false);
+        // Add protocol
+        lcsf_core.add_protocol(0xab, TEST_PROT_DESC.clone(), dummy_prot_callback);
+        // Test function
+        assert_eq!(lcsf_core.diagnose_buff(&TEST_BUFF), Ok(Vec::new()));
+        assert_eq!(
+            lcsf_core.diagnose_buff(&[0xab, 0x12]),
+            Err(LcsfDecodeErrorEnum::FormatErr)
+        );
+        let err_arr = lcsf_core
+            .diagnose_buff(&[0xab, 0x12, 0x02, 0x01, 0x01, 0x00, 0x02, 0x01, 0x00])
+            .unwrap();
+        assert_eq!(err_arr.len(), 3);
+        assert_eq!(err_arr[1].att_path, vec![0x01]);
+        assert_eq!(err_arr[2].att_path, vec![0x02]);
+    }
+
     #[test]
     fn test_send_cmd() {
         let mut lcsf_core = LcsfCore::new(LcsfModeEnum::Small, false);
         // Add protocol
-        lcsf_core.add_protocol(0xab, &TEST_PROT_DESC, dummy_prot_callback);
+        lcsf_core.add_protocol(0xab, TEST_PROT_DESC.clone(), dummy_prot_callback);
         // Test function
         let buff = lcsf_core.send_cmd(0xab, &TEST_VALID_CMD);
         assert_eq!(buff, *TEST_BUFF);
+        assert_eq!(
+            lcsf_core.try_send_cmd(0xab, &TEST_VALID_CMD),
+            Ok(TEST_BUFF.clone())
+        );
+        // Test unknown protocol and command
+        let err = lcsf_core.try_send_cmd(0xac, &TEST_VALID_CMD).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::UnknownProtId);
+        let unknown_cmd = LcsfValidCmd {
+            cmd_id: 0x13,
+            att_arr: Vec::new(),
+        };
+        let err = lcsf_core.try_send_cmd(0xab, &unknown_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::UnknownCmdId);
+        assert_eq!(err.cmd_id, 0x13);
+        // Test invalid command
+        let bad_cmd = LcsfValidCmd {
+            cmd_id: 0x12,
+            att_arr: vec![LcsfValidAtt {
+                payload: LcsfValidAttPayload::Absent,
+            }],
+        };
+        let err = lcsf_core.try_send_cmd(0xab, &bad_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongAttNb);
+    }
+
+    #[test]
+    fn test_cmd_direction() {
+        let mut lcsf_core = LcsfCore::new(LcsfModeEnum::Small, false);
+        lcsf_core.add_protocol(0xab, TEST_DIR_PROT_DESC.clone(), dummy_prot_callback);
+        // Test host rejects commands sent to the device
+        lcsf_core.set_role(LcsfRole::Host);
+        assert!(!lcsf_core.receive_buff(&TEST_BUFF));
+        let err_arr = lcsf_core.diagnose_buff(&TEST_BUFF).unwrap();
+        assert_eq!(err_arr[0].kind, LcsfValidateErrorEnum::WrongDirection);
+        assert_eq!(lcsf_core.send_cmd(0xab, &TEST_VALID_CMD), *TEST_BUFF);
+        // Test device receives them but can't send them
+        lcsf_core.set_role(LcsfRole::Device);
+        assert!(lcsf_core.receive_buff(&TEST_BUFF));
+        let err = lcsf_core.try_send_cmd(0xab, &TEST_VALID_CMD).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongDirection);
+        assert_eq!(err.cmd_id, 0x12);
     }
 
     static ERR_IS_VALID: AtomicBool = AtomicBool::new(false);
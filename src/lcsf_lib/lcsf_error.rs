@@ -36,29 +36,40 @@ pub enum LcsfEpLocEnum {
 lazy_static! {
     /// Lcsf ep protocol description
     pub static ref LCSF_EP_PROT_DESC: LcsfProtDesc = LcsfProtDesc {
+        name: "LCSF_EP".into(),
+        description: "Lcsf error protocol".into(),
         cmd_desc_arr: vec![(
             0x00,
             LcsfCmdDesc {
+                name: "ERROR".into(),
                 att_desc_arr: vec![
                     (
                         0x00,
                         LcsfAttDesc {
+                            name: "ERROR_LOC".into(),
+                            description: "Error location, see LcsfEpLocEnum".into(),
                             is_optional: false,
                             data_type: LcsfDataType::Uint8,
                             subatt_desc_arr: Vec::new(),
+                            ..Default::default()
                         }
                     ),
                     (
                         0x01,
                         LcsfAttDesc {
+                            name: "ERROR_TYPE".into(),
+                            description: "Error type, depends on location".into(),
                             is_optional: false,
                             data_type: LcsfDataType::Uint8,
                             subatt_desc_arr: Vec::new(),
+                            ..Default::default()
                         }
                     ),
-                ]
+                ],
+                ..Default::default()
             }
-        ),]
+        ),],
+        ..Default::default()
     };
 }
 
@@ -146,6 +157,13 @@ pub fn process_error(valid_cmd: &LcsfValidCmd) -> (&str, &str) {
                 3 => "Too many attributes received",
                 4 => "Missing mandatory attribute",
                 5 => "Wrong attribute data type",
+                6 => "Attribute out of range",
+                7 => "Duplicate attribute id",
+                8 => "Wrong attribute repetition count",
+                10 => "Wrong command direction",
+                11 => "Wrong exclusive choice",
+                12 => "Missing conditionally required attribute",
+                13 => "Too deep nested command",
                 _ => "Unknown",
             };
         }
@@ -286,8 +304,64 @@ mod tests {
         });
         (loc_str, type_str) = process_error(&valid_cmd);
         assert_eq!(loc_str, "Validator");
+        assert_eq!(type_str, "Attribute out of range");
+
+        valid_cmd.att_arr.pop();
+        valid_cmd.att_arr.push(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(vec![0x07]),
+        });
+        (loc_str, type_str) = process_error(&valid_cmd);
+        assert_eq!(loc_str, "Validator");
+        assert_eq!(type_str, "Duplicate attribute id");
+
+        valid_cmd.att_arr.pop();
+        valid_cmd.att_arr.push(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(vec![0x08]),
+        });
+        (loc_str, type_str) = process_error(&valid_cmd);
+        assert_eq!(loc_str, "Validator");
+        assert_eq!(type_str, "Wrong attribute repetition count");
+
+        valid_cmd.att_arr.pop();
+        valid_cmd.att_arr.push(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(vec![0x09]),
+        });
+        (loc_str, type_str) = process_error(&valid_cmd);
+        assert_eq!(loc_str, "Validator");
         assert_eq!(type_str, "Unknown");
 
+        valid_cmd.att_arr.pop();
+        valid_cmd.att_arr.push(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(vec![0x0a]),
+        });
+        (loc_str, type_str) = process_error(&valid_cmd);
+        assert_eq!(loc_str, "Validator");
+        assert_eq!(type_str, "Wrong command direction");
+
+        valid_cmd.att_arr.pop();
+        valid_cmd.att_arr.push(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(vec![0x0b]),
+        });
+        (loc_str, type_str) = process_error(&valid_cmd);
+        assert_eq!(loc_str, "Validator");
+        assert_eq!(type_str, "Wrong exclusive choice");
+
+        valid_cmd.att_arr.pop();
+        valid_cmd.att_arr.push(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(vec![0x0c]),
+        });
+        (loc_str, type_str) = process_error(&valid_cmd);
+        assert_eq!(loc_str, "Validator");
+        assert_eq!(type_str, "Missing conditionally required attribute");
+
+        valid_cmd.att_arr.pop();
+        valid_cmd.att_arr.push(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(vec![0x0d]),
+        });
+        (loc_str, type_str) = process_error(&valid_cmd);
+        assert_eq!(loc_str, "Validator");
+        assert_eq!(type_str, "Too deep nested command");
+
         valid_cmd = LcsfValidCmd {
             cmd_id: LCSF_EP_ERR_CMD_ID,
             att_arr: vec![
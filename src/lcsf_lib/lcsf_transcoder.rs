@@ -11,7 +11,7 @@ use core::slice::Iter;
 
 /// Lcsf representation mode enum
 #[allow(dead_code)]
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub enum LcsfModeEnum {
     /// Smaller size lcsf (1 byte / field)
     Small = 0,
@@ -62,6 +62,24 @@ pub struct LcsfRawMsg {
     pub att_arr: Vec<(u16, LcsfRawAtt)>,
 }
 
+impl LcsfModeEnum {
+    /// Return the size of a message header (bytes)
+    pub fn msg_header_size(self) -> usize {
+        match self {
+            LcsfModeEnum::Small => 3,
+            LcsfModeEnum::Normal => 6,
+        }
+    }
+
+    /// Return the size of an attribute header (bytes)
+    pub fn att_header_size(self) -> usize {
+        match self {
+            LcsfModeEnum::Small => 2,
+            LcsfModeEnum::Normal => 4,
+        }
+    }
+}
+
 // *** Decoder ***
 
 /// Fetch a lcsf message header struct from a buffer iterator
@@ -293,7 +311,8 @@ fn fill_att_header(lcsf_mode: LcsfModeEnum, att_id: u16, att: &LcsfRawAtt) -> Ve
     buffer
 }
 
-/// Recursively encode a LcsfRawAtt into a buffer
+/// Recursively encode a LcsfRawAtt into a buffer, an attribute without payload, like a
+/// sub-attribute array with no present member, is encoded as a lone header
 ///
 /// lcsf_mode: parsing mode to use, see [LcsfModeEnum]
 ///
@@ -302,10 +321,6 @@ fn fill_att_header(lcsf_mode: LcsfModeEnum, att_id: u16, att: &LcsfRawAtt) -> Ve
 /// att: attribute to encode reference
 fn encode_att_rec(lcsf_mode: LcsfModeEnum, att_id: u16, att: &LcsfRawAtt) -> Vec<u8> {
     let mut buffer: Vec<u8> = Vec::new();
-    // Skip empty raw attributes
-    if att.payload_size == 0 {
-        return buffer;
-    }
     // Fill attribute header
     buffer.extend(fill_att_header(lcsf_mode, att_id, att));
     // Check payload type
@@ -521,6 +536,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_empty_att() {
+        let empty_msg = LcsfRawMsg {
+            prot_id: 0xab,
+            cmd_id: 0x12,
+            att_nb: 2,
+            att_arr: vec![
+                (
+                    0x01,
+                    LcsfRawAtt {
+                        has_subatt: true,
+                        payload_size: 0,
+                        payload: LcsfRawAttPayload::SubattArr(Vec::new()),
+                    },
+                ),
+                (
+                    0x02,
+                    LcsfRawAtt {
+                        has_subatt: false,
+                        payload_size: 1,
+                        payload: LcsfRawAttPayload::Data(vec![0x0a]),
+                    },
+                ),
+            ],
+        };
+        // Empty attributes keep their header, so att_nb matches the encoded attributes
+        let small_buff = encode_buff(LcsfModeEnum::Small, &empty_msg);
+        assert_eq!(small_buff, [0xab, 0x12, 0x02, 0x81, 0x00, 0x02, 0x01, 0x0a]);
+        assert_eq!(
+            decode_buff(LcsfModeEnum::Small, &small_buff),
+            Ok(empty_msg.clone())
+        );
+        let normal_buff = encode_buff(LcsfModeEnum::Normal, &empty_msg);
+        assert_eq!(
+            normal_buff,
+            [
+                0xab, 0x00, 0x12, 0x00, 0x02, 0x00, 0x01, 0x80, 0x00, 0x00, 0x02, 0x00, 0x01, 0x00,
+                0x0a
+            ]
+        );
+        assert_eq!(
+            decode_buff(LcsfModeEnum::Normal, &normal_buff),
+            Ok(empty_msg)
+        );
+    }
+
     #[test]
     fn test_encode_buff() {
         // Test small
@@ -8,17 +8,52 @@
 //! along with this program. If not, see <https://www.gnu.org/licenses/>
 
 use core::mem::size_of;
+use std::borrow::Borrow;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use crate::lcsf_lib::lcsf_transcoder;
+use lcsf_transcoder::LcsfModeEnum;
 use lcsf_transcoder::LcsfRawAtt;
 use lcsf_transcoder::LcsfRawAttPayload;
 use lcsf_transcoder::LcsfRawMsg;
 
+/// Packed array element type enum, elements are little endian
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum LcsfElemType {
+    Uint8,
+    Uint16,
+    Uint32,
+    Uint64,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+}
+
+/// Time resolution enum, duration of one raw unit
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum LcsfTimeRes {
+    Millis,
+    Micros,
+    Nanos,
+}
+
 /// Attribute data type enum
 #[allow(dead_code)]
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
 pub enum LcsfDataType {
+    #[default]
     Uint8,
     Uint16,
     Uint32,
@@ -28,27 +63,375 @@ pub enum LcsfDataType {
     ByteArray,
     String,
     Subattributes,
+    /// Packed array of fixed size elements, see [LcsfElemType]
+    Array(LcsfElemType),
+    /// Point in time, VLE encoded number of res units since epoch (seconds since the Unix epoch)
+    Timestamp {
+        res: LcsfTimeRes,
+        epoch: u64,
+    },
+    /// Time span, VLE encoded number of res units
+    Duration(LcsfTimeRes),
+    /// Nested lcsf message encoded in the given mode, see [LcsfAttDesc::inner_prot_id_arr]
+    Message(LcsfModeEnum),
+}
+
+impl LcsfElemType {
+    /// Return the size of an element (bytes)
+    pub fn size(&self) -> usize {
+        match self {
+            LcsfElemType::Uint8 | LcsfElemType::Int8 => size_of::<u8>(),
+            LcsfElemType::Uint16 | LcsfElemType::Int16 => size_of::<u16>(),
+            LcsfElemType::Uint32 | LcsfElemType::Int32 => size_of::<u32>(),
+            LcsfElemType::Uint64 | LcsfElemType::Int64 => size_of::<u64>(),
+            LcsfElemType::Float32 => size_of::<f32>(),
+            LcsfElemType::Float64 => size_of::<f64>(),
+        }
+    }
+
+    /// Decode an integer element without loss, None for float elements or a wrong element size
+    ///
+    /// elem: element reference, of the element size
+    pub fn decode_int(&self, elem: &[u8]) -> Option<i128> {
+        if elem.len() != self.size() {
+            return None;
+        }
+        let mut buff = [0u8; size_of::<u64>()];
+        buff[..elem.len()].copy_from_slice(elem);
+        let value = u64::from_le_bytes(buff);
+        // Shift to sign extend signed elements
+        let shift = 64 - 8 * elem.len();
+        match self {
+            LcsfElemType::Uint8
+            | LcsfElemType::Uint16
+            | LcsfElemType::Uint32
+            | LcsfElemType::Uint64 => Some(value as i128),
+            LcsfElemType::Int8
+            | LcsfElemType::Int16
+            | LcsfElemType::Int32
+            | LcsfElemType::Int64 => Some((((value << shift) as i64) >> shift) as i128),
+            LcsfElemType::Float32 | LcsfElemType::Float64 => None,
+        }
+    }
+
+    /// Decode an element as a float value, None for a wrong element size
+    ///
+    /// elem: element reference, of the element size
+    pub fn decode_f64(&self, elem: &[u8]) -> Option<f64> {
+        match self {
+            LcsfElemType::Float32 => elem
+                .try_into()
+                .ok()
+                .map(|buff| f32::from_le_bytes(buff) as f64),
+            LcsfElemType::Float64 => elem.try_into().ok().map(f64::from_le_bytes),
+            _ => self.decode_int(elem).map(|value| value as f64),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl LcsfTimeRes {
+    /// Return the duration of a raw value
+    ///
+    /// raw: raw value, in resolution units
+    pub fn to_duration(self, raw: u64) -> Duration {
+        match self {
+            LcsfTimeRes::Millis => Duration::from_millis(raw),
+            LcsfTimeRes::Micros => Duration::from_micros(raw),
+            LcsfTimeRes::Nanos => Duration::from_nanos(raw),
+        }
+    }
+
+    /// Return the raw value of a duration, truncated to the resolution, None if too large
+    ///
+    /// duration: duration value
+    pub fn to_raw(self, duration: Duration) -> Option<u64> {
+        let raw = match self {
+            LcsfTimeRes::Millis => duration.as_millis(),
+            LcsfTimeRes::Micros => duration.as_micros(),
+            LcsfTimeRes::Nanos => duration.as_nanos(),
+        };
+        u64::try_from(raw).ok()
+    }
+
+    /// Return the point in time of a raw value, None if it can't be represented
+    ///
+    /// epoch: epoch, in seconds since the Unix epoch
+    ///
+    /// raw: raw value, in resolution units since epoch
+    pub fn to_time(self, epoch: u64, raw: u64) -> Option<SystemTime> {
+        UNIX_EPOCH
+            .checked_add(Duration::from_secs(epoch))?
+            .checked_add(self.to_duration(raw))
+    }
+
+    /// Return the raw value of a point in time, truncated to the resolution, None if before
+    /// epoch or too large
+    ///
+    /// epoch: epoch, in seconds since the Unix epoch
+    ///
+    /// time: point in time
+    pub fn to_raw_time(self, epoch: u64, time: SystemTime) -> Option<u64> {
+        let epoch_time = UNIX_EPOCH.checked_add(Duration::from_secs(epoch))?;
+        self.to_raw(time.duration_since(epoch_time).ok()?)
+    }
+}
+
+/// Lcsf attribute constraints structure, unset fields are not checked.
+/// Integer values are compared to the bounds without loss, and non finite float values fail any
+/// set bound. NaN bounds are ignored. Bounds are compared bitwise for Eq and Hash.
+#[derive(Debug, Clone, Default)]
+pub struct LcsfAttConstraints {
+    /// Minimum value, for integer and float data types and each Array element
+    pub min_value: Option<f64>,
+    /// Maximum value, for integer and float data types and each Array element
+    pub max_value: Option<f64>,
+    /// Minimum payload length, in bytes for ByteArray and String, in elements for Array
+    pub min_len: Option<usize>,
+    /// Maximum payload length, in bytes for ByteArray and String, in elements for Array
+    pub max_len: Option<usize>,
+    /// Exact payload length, in bytes for ByteArray and String, in elements for Array
+    pub fixed_len: Option<usize>,
+}
+
+/// Lcsf attribute scale structure, physical value is raw value * factor + offset.
+/// Factor and offset are compared bitwise for Eq and Hash.
+#[derive(Debug, Clone, Default)]
+pub struct LcsfAttScale {
+    /// Physical value of one raw unit, finite and not zero
+    pub factor: f64,
+    /// Physical value of a raw zero
+    pub offset: f64,
+    /// Physical unit, e.g. "V", empty if unitless
+    pub unit: String,
+}
+
+/// Lcsf attribute cardinality enum
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub enum LcsfAttCardinality {
+    /// Attribute is present at most once
+    #[default]
+    Single,
+    /// Attribute can be present several times, with optional count limits
+    Repeated {
+        min_count: Option<usize>,
+        max_count: Option<usize>,
+    },
+}
+
+/// Lcsf exclusive choice enum, number of sub-attributes present at once
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub enum LcsfOneof {
+    /// Sub-attributes are independent
+    #[default]
+    Disabled,
+    /// Exactly one sub-attribute is present
+    ExactlyOne,
+    /// At most one sub-attribute is present
+    AtMostOne,
+}
+
+/// Lcsf attribute condition enum, state of a sibling attribute a requirement depends on
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum LcsfAttCond {
+    /// Sibling attribute with this id is present
+    Present(u16),
+    /// Sibling integer attribute with this id is present with this raw value
+    Equals(u16, u64),
 }
 
 /// Lcsf attribute descriptor structure
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
 pub struct LcsfAttDesc {
+    /// Attribute name, empty if unnamed
+    pub name: String,
+    /// Attribute description, empty if undocumented
+    pub description: String,
     /// Indicates attribute is optional or not
     pub is_optional: bool,
     pub data_type: LcsfDataType,
     pub subatt_desc_arr: Vec<(u16, LcsfAttDesc)>,
+    /// Value and length constraints, see [LcsfAttConstraints]
+    pub constraints: LcsfAttConstraints,
+    /// Number of times the attribute can be present, see [LcsfAttCardinality]
+    pub cardinality: LcsfAttCardinality,
+    /// Physical value conversion of integer attributes, constraints still apply to the raw value
+    pub scale: Option<LcsfAttScale>,
+    /// Accepted protocol ids of Message attributes, empty accepts any registered protocol
+    pub inner_prot_id_arr: Vec<u16>,
+    /// Encoded value of an absent optional single attribute, filled on reception
+    pub default_value: Option<Vec<u8>>,
+    /// Attribute group providing the sub-attributes, empty if none, see [LcsfAttGroup]
+    pub group: String,
+    /// Exclusive choice between the sub-attributes, all optional, see [LcsfOneof]
+    pub oneof: LcsfOneof,
+    /// Sibling attribute condition making this optional attribute mandatory, see [LcsfAttCond]
+    pub required_if: Option<LcsfAttCond>,
+}
+
+/// Lcsf command direction enum
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub enum LcsfCmdDirection {
+    /// Command sent by both sides
+    #[default]
+    Both,
+    /// Command sent by the host to the device
+    ToDevice,
+    /// Command sent by the device to the host
+    FromDevice,
+}
+
+/// Lcsf local role enum, decides which command directions are received and sent
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub enum LcsfRole {
+    /// Any direction is accepted
+    #[default]
+    Any,
+    /// Receives ToDevice commands, sends FromDevice commands
+    Device,
+    /// Receives FromDevice commands, sends ToDevice commands
+    Host,
+}
+
+impl LcsfRole {
+    /// Check the role can receive a command of a given direction
+    ///
+    /// direction: command direction
+    pub fn can_receive(self, direction: LcsfCmdDirection) -> bool {
+        match self {
+            LcsfRole::Any => true,
+            LcsfRole::Device => direction != LcsfCmdDirection::FromDevice,
+            LcsfRole::Host => direction != LcsfCmdDirection::ToDevice,
+        }
+    }
+
+    /// Check the role can send a command of a given direction
+    ///
+    /// direction: command direction
+    pub fn can_send(self, direction: LcsfCmdDirection) -> bool {
+        match self {
+            LcsfRole::Any => true,
+            LcsfRole::Device => direction != LcsfCmdDirection::ToDevice,
+            LcsfRole::Host => direction != LcsfCmdDirection::FromDevice,
+        }
+    }
 }
 
 /// Lcsf command descriptor structure
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
 pub struct LcsfCmdDesc {
+    /// Command name, empty if unnamed
+    pub name: String,
+    /// Command description, empty if undocumented
+    pub description: String,
+    pub att_desc_arr: Vec<(u16, LcsfAttDesc)>,
+    /// Command direction, checked against the local role, see [LcsfRole]
+    pub direction: LcsfCmdDirection,
+    /// Attribute group providing the attributes, empty if none, see [LcsfAttGroup]
+    pub group: String,
+}
+
+/// Lcsf attribute group structure, a named attribute set shared by several commands or
+/// attributes. They reference it by name with an empty attribute array, filled by
+/// [LcsfProtDesc::expand_groups].
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct LcsfAttGroup {
+    /// Group name, referenced by the command and attribute descriptors
+    pub name: String,
     pub att_desc_arr: Vec<(u16, LcsfAttDesc)>,
 }
 
+/// Lcsf validation policy structure, default is strict validation
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct LcsfValidatePolicy {
+    /// Ignore received attributes missing from the descriptor instead of rejecting the message
+    pub ignore_unknown_att: bool,
+    /// Note ignored attributes in the valid command, see [LcsfValidCmd]
+    pub keep_unknown_att: bool,
+    /// Check received String attributes are valid UTF-8 text, their NUL terminator is always checked
+    pub check_utf8: bool,
+    /// Omit sent attributes equal to their default value, see [LcsfAttDesc::default_value]
+    pub omit_default_att: bool,
+}
+
 /// Lcsf protocol descriptor structure
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
 pub struct LcsfProtDesc {
+    /// Protocol name, empty if unnamed
+    pub name: String,
+    /// Protocol description, empty if undocumented
+    pub description: String,
     pub cmd_desc_arr: Vec<(u16, LcsfCmdDesc)>,
+    /// Validation policy of received commands, see [LcsfValidatePolicy]
+    pub policy: LcsfValidatePolicy,
+    /// Attribute groups referenced by the descriptors, see [LcsfAttGroup]
+    pub group_arr: Vec<LcsfAttGroup>,
+}
+
+/// Maximum attribute nesting depth of a protocol descriptor
+pub const LCSF_MAX_ATT_DEPTH: usize = 16;
+
+/// Maximum number of nested command layers in a message
+pub const LCSF_MAX_MSG_DEPTH: usize = 8;
+
+/// Lcsf descriptor issue enum
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum LcsfDescIssueEnum {
+    /// Command id defined more than once
+    DuplicateCmdId,
+    /// Command id too large for the representation mode
+    CmdIdOutOfRange,
+    /// Attribute id defined more than once in the same array
+    DuplicateAttId,
+    /// Attribute id too large for the representation mode, collides with the sub-attribute flag
+    AttIdOutOfRange,
+    /// Subattributes data type without sub-attribute descriptors
+    EmptySubattArr,
+    /// Data type with sub-attribute descriptors
+    UnexpectedSubattArr,
+    /// Attribute nesting deeper than LCSF_MAX_ATT_DEPTH
+    TooDeep,
+    /// Value bounds non finite or reversed, length limits reversed or inconsistent, or
+    /// constraints the data type doesn't use
+    InvalidConstraints,
+    /// Scale on a non integer data type, or with a zero or non finite factor
+    InvalidScale,
+    /// Default value on a mandatory, repeated, Subattributes or Message attribute, or not
+    /// matching its data type and constraints
+    InvalidDefault,
+    /// Group not defined in the protocol descriptor
+    UnknownGroup,
+    /// Attribute array differs from its group, see [LcsfProtDesc::expand_groups]
+    GroupMismatch,
+    /// Exclusive choice on a non Subattributes attribute, or with mandatory or defaulted
+    /// sub-attributes
+    InvalidOneof,
+    /// Condition on a mandatory or defaulted attribute, or on an unknown sibling, itself, or a
+    /// repeated or non integer sibling for a value condition
+    InvalidCondition,
+}
+
+/// Lcsf descriptor issue structure
+#[derive(Debug, PartialEq, Clone)]
+pub struct LcsfDescIssue {
+    /// Id of the command containing the issue
+    pub cmd_id: u16,
+    /// Id path of the faulty attribute, empty for command issues
+    pub att_path: Vec<u16>,
+    pub issue: LcsfDescIssueEnum,
+}
+
+/// Lcsf encoded size bounds structure, in bytes
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct LcsfSizeBounds {
+    /// Smallest encoded size
+    pub min_size: usize,
+    /// Largest encoded size, None if unbounded
+    pub max_size: Option<usize>,
 }
 
 /// Lcsf valid attribute payload union
@@ -56,6 +439,12 @@ pub struct LcsfProtDesc {
 pub enum LcsfValidAttPayload {
     Data(Vec<u8>),
     SubattArr(Vec<LcsfValidAtt>),
+    /// Occurrences of a repeated attribute, each one a Data or SubattArr payload
+    List(Vec<LcsfValidAtt>),
+    /// Optional single attribute not present in the command
+    Absent,
+    /// Validated nested command of a Message attribute, with its protocol id
+    Message(u16, Box<LcsfValidCmd>),
 }
 
 /// Lcsf valid attribute structure
@@ -71,6 +460,18 @@ pub struct LcsfValidCmd {
     pub att_arr: Vec<LcsfValidAtt>,
 }
 
+/// Lcsf valid command info structure, returned next to a received or built command.
+/// Paths of nested message attributes go through the Message attribute id.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct LcsfValidInfo {
+    /// Ignored unknown attributes as (id path, attribute) tuples, the path ends with the unknown id.
+    /// Only filled on reception when the policy keeps them.
+    pub unknown_att_arr: Vec<(Vec<u16>, LcsfRawAtt)>,
+    /// Id paths of the absent optional attributes filled with their default value, the
+    /// attributes are encoded as regular ones
+    pub default_att_arr: Vec<Vec<u16>>,
+}
+
 /// Lcsf decoding error enum
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum LcsfValidateErrorEnum {
@@ -86,392 +487,3973 @@ pub enum LcsfValidateErrorEnum {
     MissMandatoryAtt = 0x04,
     /// Wrong attribute data type
     WrongAttDataType = 0x05,
+    /// Attribute value or length out of range
+    AttOutOfRange = 0x06,
+    /// Same attribute id received more than once
+    DuplicateAttId = 0x07,
+    /// Repeated attribute count out of range
+    WrongAttCount = 0x08,
+    /// Valid attribute array size differs from its descriptor, encoding only
+    WrongAttNb = 0x09,
+    /// Command direction not allowed for the local role
+    WrongDirection = 0x0a,
+    /// Number of present sub-attributes not allowed by the exclusive choice
+    WrongOneofNb = 0x0b,
+    /// Missing attribute required by a sibling attribute condition
+    MissRequiredAtt = 0x0c,
+    /// Nested commands deeper than [LCSF_MAX_MSG_DEPTH]
+    TooDeepMsg = 0x0d,
 }
 
-// *** Validate raw ***
+/// Lcsf attribute shape enum, summarizes a received or encoded attribute
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum LcsfAttShape {
+    /// Data payload of given size (bytes)
+    Data(usize),
+    /// Sub-attribute payload of given sub-attribute number
+    SubattArr(usize),
+    /// Occurrences of a repeated attribute, of given number
+    List(usize),
+    /// Attribute not present
+    Absent,
+    /// Nested command
+    Message,
+}
 
-/// Validate the data size of received attribute payload
+/// Lcsf validation and encoding error structure
+#[derive(Debug, PartialEq, Clone)]
+pub struct LcsfValidateError {
+    pub kind: LcsfValidateErrorEnum,
+    /// Id of the faulty command
+    pub cmd_id: u16,
+    /// Id path of the faulty attribute or attribute array, empty for command errors
+    pub att_path: Vec<u16>,
+    /// Data type expected by the descriptor, if the attribute is known
+    pub data_type: Option<LcsfDataType>,
+    /// Shape of the faulty attribute or attribute array, see [LcsfAttShape]
+    pub shape: Option<LcsfAttShape>,
+    /// Error of the nested command, if the faulty attribute is a Message attribute
+    pub nested: Option<Box<LcsfValidateError>>,
+}
+
+// *** Descriptor lookup ***
+
+/// Find an attribute descriptor by id in a descriptor array
 ///
-/// data_size: size of the data
+/// att_desc_arr: (id, attribute descriptor) array reference
 ///
-/// data_type: type of the data
-fn validate_data_type(data_size: usize, data_type: LcsfDataType) -> bool {
-    // Check data type
-    match data_type {
-        LcsfDataType::Uint8 => data_size == size_of::<u8>(),
-        LcsfDataType::Uint16 => data_size != 0 && data_size <= size_of::<u16>(),
-        LcsfDataType::Uint32 => data_size != 0 && data_size <= size_of::<u32>(),
-        LcsfDataType::Uint64 => data_size != 0 && data_size <= size_of::<u64>(),
-        LcsfDataType::Float32 => data_size == size_of::<f32>(),
-        LcsfDataType::Float64 => data_size == size_of::<f64>(),
-        LcsfDataType::ByteArray => data_size > 0,
-        LcsfDataType::String => data_size > 0,
-        LcsfDataType::Subattributes => false,
-    }
+/// att_id: attribute id value
+fn find_att_desc(att_desc_arr: &[(u16, LcsfAttDesc)], att_id: u16) -> Option<&LcsfAttDesc> {
+    att_desc_arr
+        .iter()
+        .find(|(id, _)| *id == att_id)
+        .map(|(_, att_desc)| att_desc)
 }
 
-/// Recursively validate & received attribute and its payload
+/// Find an attribute (id, descriptor) by name in a descriptor array
 ///
-/// att_id: attribute id value
+/// att_desc_arr: (id, attribute descriptor) array reference
 ///
-/// att_desc: attribute descriptor reference
+/// name: attribute name
+fn find_att_desc_by_name<'a>(
+    att_desc_arr: &'a [(u16, LcsfAttDesc)],
+    name: &str,
+) -> Option<(u16, &'a LcsfAttDesc)> {
+    att_desc_arr
+        .iter()
+        .find(|(_, att_desc)| !name.is_empty() && att_desc.name == name)
+        .map(|(att_id, att_desc)| (*att_id, att_desc))
+}
+
+/// Find an attribute group by name in a group array, None for an empty name
 ///
-/// rx_att_arr: received (id, attribute) array reference
+/// group_arr: attribute group array reference
 ///
-fn validate_att_rec(
-    att_id: u16,
-    att_desc: &LcsfAttDesc,
-    rx_att_arr: &[(u16, LcsfRawAtt)],
-) -> Result<(usize, LcsfValidAtt), LcsfValidateErrorEnum> {
-    let mut valid_att = LcsfValidAtt {
-        payload: LcsfValidAttPayload::Data(Vec::new()),
-    };
-    let mut local_payload_size: usize = 0; // To avoid de-structuring to get vec.len()
+/// name: group name
+fn find_group<'a>(group_arr: &'a [LcsfAttGroup], name: &str) -> Option<&'a LcsfAttGroup> {
+    group_arr
+        .iter()
+        .find(|att_group| !name.is_empty() && att_group.name == name)
+}
 
-    // Check for attribute in received array
-    let rx_att_map: HashMap<u16, LcsfRawAtt> = rx_att_arr.iter().cloned().collect();
-    let rx_att = match rx_att_map.get(&att_id) {
-        None => {
-            // Attribute missing, check optional
-            if !att_desc.is_optional {
-                return Err(LcsfValidateErrorEnum::MissMandatoryAtt);
-            } else {
-                return Ok((local_payload_size, valid_att));
-            }
-        }
-        Some(att) => att,
-    };
-    // Attribute present, check payload type
-    if att_desc.data_type == LcsfDataType::Subattributes {
-        // Check data type
-        if !rx_att.has_subatt {
-            return Err(LcsfValidateErrorEnum::WrongAttDataType);
-        }
-        // Payload de-structuring
-        if let LcsfRawAttPayload::SubattArr(rx_subatt_arr) = &rx_att.payload {
-            valid_att.payload = LcsfValidAttPayload::SubattArr(Vec::new());
-            let mut subatt_count: usize = 0;
+impl LcsfProtDesc {
+    /// Return a command descriptor from its id
+    ///
+    /// cmd_id: command id value
+    pub fn get_cmd_desc(&self, cmd_id: u16) -> Option<&LcsfCmdDesc> {
+        self.cmd_desc_arr
+            .iter()
+            .find(|(id, _)| *id == cmd_id)
+            .map(|(_, cmd_desc)| cmd_desc)
+    }
 
-            // Too many attributes case
-            if rx_subatt_arr.len() > att_desc.subatt_desc_arr.len() {
-                return Err(LcsfValidateErrorEnum::TooManyAtt);
-            }
-            // Parse through the sub-descriptor list
-            for (sub_id, sub_desc) in &att_desc.subatt_desc_arr {
-                // Process attribute
-                let (sub_payload_size, valid_subatt) =
-                    validate_att_rec(*sub_id, sub_desc, rx_subatt_arr)?;
-                // Count sub-attribute presence
-                if sub_payload_size > 0 {
-                    subatt_count += 1;
-                }
-                // Add sub-attribute to array
-                if let LcsfValidAttPayload::SubattArr(valid_subatt_arr) = &mut valid_att.payload {
-                    valid_subatt_arr.push(valid_subatt);
-                    local_payload_size += 1;
-                };
-            }
-            // Unrecognized attribute case
-            if subatt_count < rx_subatt_arr.len() {
-                return Err(LcsfValidateErrorEnum::UnknownAttId);
-            }
-        };
-    } else {
-        // Check data type
-        if !validate_data_type(rx_att.payload_size as usize, att_desc.data_type) {
-            return Err(LcsfValidateErrorEnum::WrongAttDataType);
-        }
-        // Note data and data size
-        if let LcsfRawAttPayload::Data(rx_data) = &rx_att.payload
-            && let LcsfValidAttPayload::Data(valid_data) = &mut valid_att.payload
-        {
-            *valid_data = rx_data.clone();
-            local_payload_size = rx_data.len();
-        };
+    /// Return a command (id, descriptor) from its name
+    ///
+    /// name: command name
+    pub fn get_cmd_by_name(&self, name: &str) -> Option<(u16, &LcsfCmdDesc)> {
+        self.cmd_desc_arr
+            .iter()
+            .find(|(_, cmd_desc)| !name.is_empty() && cmd_desc.name == name)
+            .map(|(cmd_id, cmd_desc)| (*cmd_id, cmd_desc))
+    }
+}
+
+#[allow(dead_code)]
+impl LcsfCmdDesc {
+    /// Return an attribute descriptor from its id
+    ///
+    /// att_id: attribute id value
+    pub fn get_att_desc(&self, att_id: u16) -> Option<&LcsfAttDesc> {
+        find_att_desc(&self.att_desc_arr, att_id)
+    }
+
+    /// Return an attribute (id, descriptor) from its name
+    ///
+    /// name: attribute name
+    pub fn get_att_by_name(&self, name: &str) -> Option<(u16, &LcsfAttDesc)> {
+        find_att_desc_by_name(&self.att_desc_arr, name)
+    }
+}
+
+#[allow(dead_code)]
+impl LcsfAttDesc {
+    /// Return a sub-attribute descriptor from its id
+    ///
+    /// att_id: sub-attribute id value
+    pub fn get_subatt_desc(&self, att_id: u16) -> Option<&LcsfAttDesc> {
+        find_att_desc(&self.subatt_desc_arr, att_id)
+    }
+
+    /// Return a sub-attribute (id, descriptor) from its name
+    ///
+    /// name: sub-attribute name
+    pub fn get_subatt_by_name(&self, name: &str) -> Option<(u16, &LcsfAttDesc)> {
+        find_att_desc_by_name(&self.subatt_desc_arr, name)
+    }
+}
+
+impl PartialEq for LcsfAttConstraints {
+    fn eq(&self, other: &Self) -> bool {
+        self.min_value.map(f64::to_bits) == other.min_value.map(f64::to_bits)
+            && self.max_value.map(f64::to_bits) == other.max_value.map(f64::to_bits)
+            && self.min_len == other.min_len
+            && self.max_len == other.max_len
+            && self.fixed_len == other.fixed_len
+    }
+}
+
+impl Eq for LcsfAttConstraints {}
+
+impl Hash for LcsfAttConstraints {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.min_value.map(f64::to_bits).hash(state);
+        self.max_value.map(f64::to_bits).hash(state);
+        self.min_len.hash(state);
+        self.max_len.hash(state);
+        self.fixed_len.hash(state);
+    }
+}
+
+impl PartialEq for LcsfAttScale {
+    fn eq(&self, other: &Self) -> bool {
+        self.factor.to_bits() == other.factor.to_bits()
+            && self.offset.to_bits() == other.offset.to_bits()
+            && self.unit == other.unit
+    }
+}
+
+impl Eq for LcsfAttScale {}
+
+impl Hash for LcsfAttScale {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.factor.to_bits().hash(state);
+        self.offset.to_bits().hash(state);
+        self.unit.hash(state);
+    }
+}
+
+#[allow(dead_code)]
+impl LcsfAttScale {
+    /// Return the physical value of a raw value
+    ///
+    /// raw: raw integer value
+    pub fn to_physical(&self, raw: u64) -> f64 {
+        raw as f64 * self.factor + self.offset
+    }
+
+    /// Return the raw value closest to a physical value, None if it can't be represented
+    ///
+    /// value: physical value
+    pub fn to_raw(&self, value: f64) -> Option<u64> {
+        let raw = ((value - self.offset) / self.factor).round();
+        // u64::MAX as f64 rounds up to 2^64, itself out of range
+        (raw >= 0.0 && raw < u64::MAX as f64).then_some(raw as u64)
+    }
+
+    /// Return the number of decimals needed to print physical values
+    fn get_precision(&self) -> usize {
+        [self.factor, self.offset]
+            .iter()
+            .map(|value| {
+                let value_str = value.to_string();
+                value_str.split_once('.').map_or(0, |(_, dec)| dec.len())
+            })
+            .max()
+            .unwrap()
     }
-    Ok((local_payload_size, valid_att))
 }
 
-/// Validate a received lcsf raw message
+/// Check if a data type is an integer data type
 ///
-/// prot_desc_map: (protocol id, protocol descriptor) hash map reference
+/// data_type: attribute data type from descriptor
+fn is_uint_type(data_type: LcsfDataType) -> bool {
+    matches!(
+        data_type,
+        LcsfDataType::Uint8 | LcsfDataType::Uint16 | LcsfDataType::Uint32 | LcsfDataType::Uint64
+    )
+}
+
+// *** Check descriptor ***
+
+/// Check attribute constraints are consistent and used by the data type
 ///
-/// rx_msg: received message reference
-pub fn validate_msg(
-    prot_desc_map: &HashMap<u16, &LcsfProtDesc>,
-    rx_msg: &LcsfRawMsg,
-) -> Result<(LcsfValidCmd, u16), LcsfValidateErrorEnum> {
-    let mut valid_cmd = LcsfValidCmd {
-        cmd_id: 0,
-        att_arr: Vec::new(),
-    };
-    // Check protocol id valid
-    let prot_desc = match prot_desc_map.get(&rx_msg.prot_id) {
-        None => return Err(LcsfValidateErrorEnum::UnknownProtId),
-        Some(desc) => desc,
-    };
-    // Check command id valid
-    let cmd_desc_map: HashMap<u16, LcsfCmdDesc> = prot_desc.cmd_desc_arr.iter().cloned().collect();
-    let cmd_desc = match cmd_desc_map.get(&rx_msg.cmd_id) {
-        None => return Err(LcsfValidateErrorEnum::UnknownCmdId),
-        Some(desc) => desc,
+/// data_type: attribute data type from descriptor
+///
+/// constraints: attribute constraints reference
+fn is_constraints_valid(data_type: LcsfDataType, constraints: &LcsfAttConstraints) -> bool {
+    let (has_value, has_len) = match data_type {
+        LcsfDataType::Uint8
+        | LcsfDataType::Uint16
+        | LcsfDataType::Uint32
+        | LcsfDataType::Uint64
+        | LcsfDataType::Float32
+        | LcsfDataType::Float64
+        | LcsfDataType::Timestamp { .. }
+        | LcsfDataType::Duration(_) => (true, false),
+        LcsfDataType::ByteArray | LcsfDataType::String | LcsfDataType::Message(_) => (false, true),
+        LcsfDataType::Array(_) => (true, true),
+        LcsfDataType::Subattributes => (false, false),
     };
-    // Note data
-    valid_cmd.cmd_id = rx_msg.cmd_id;
-    // Check rx attributes array length
-    if rx_msg.att_arr.len() > cmd_desc.att_desc_arr.len() {
-        return Err(LcsfValidateErrorEnum::TooManyAtt);
+    let value_arr = [constraints.min_value, constraints.max_value];
+    let len_arr = [
+        constraints.min_len,
+        constraints.max_len,
+        constraints.fixed_len,
+    ];
+    // Check the data type uses the set constraints
+    if (!has_value && value_arr.iter().any(Option::is_some))
+        || (!has_len && len_arr.iter().any(Option::is_some))
+    {
+        return false;
     }
-    // Validate attributes
-    let mut att_count = 0;
-    for (att_id, att_desc) in &cmd_desc.att_desc_arr {
-        let (att_size, valid_att) = validate_att_rec(*att_id, att_desc, &rx_msg.att_arr)?;
-        valid_cmd.att_arr.push(valid_att);
-        // Count attribute presence
-        if att_size > 0 {
-            att_count += 1;
-        }
+    // Check bounds
+    if value_arr.iter().flatten().any(|value| !value.is_finite()) {
+        return false;
     }
-    // Unrecognized attribute case
-    if att_count < rx_msg.att_arr.len() {
-        return Err(LcsfValidateErrorEnum::UnknownAttId);
+    if let (Some(min), Some(max)) = (constraints.min_value, constraints.max_value)
+        && min > max
+    {
+        return false;
+    }
+    if let (Some(min), Some(max)) = (constraints.min_len, constraints.max_len)
+        && min > max
+    {
+        return false;
     }
-    Ok((valid_cmd, rx_msg.prot_id))
+    constraints.fixed_len.is_none_or(|fixed| {
+        constraints.min_len.is_none_or(|min| fixed >= min)
+            && constraints.max_len.is_none_or(|max| fixed <= max)
+    })
 }
 
-// *** Encode valid ***
-
-/// Count the number of non-empty valid attributes
+/// Check an attribute descriptor array against the group it references, returns the issue
+/// found if any
 ///
-/// att_arr: attribute array reference
-fn cnt_non_empty_att(att_arr: &[LcsfValidAtt]) -> u16 {
-    let mut cnt: u16 = 0;
-    for att in att_arr {
-        match &att.payload {
-            LcsfValidAttPayload::SubattArr(subatt_arr) => {
-                if !subatt_arr.is_empty() {
-                    cnt += 1;
-                }
-            }
-            LcsfValidAttPayload::Data(data) => {
-                if !data.is_empty() {
-                    cnt += 1;
-                }
-            }
+/// group_arr: protocol attribute groups reference
+///
+/// group: referenced group name, empty if none
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+fn check_group(
+    group_arr: &[LcsfAttGroup],
+    group: &str,
+    att_desc_arr: &[(u16, LcsfAttDesc)],
+) -> Option<LcsfDescIssueEnum> {
+    if group.is_empty() {
+        return None;
+    }
+    match find_group(group_arr, group) {
+        None => Some(LcsfDescIssueEnum::UnknownGroup),
+        Some(att_group) if att_group.att_desc_arr != att_desc_arr => {
+            Some(LcsfDescIssueEnum::GroupMismatch)
         }
+        Some(_) => None,
     }
-    cnt
 }
 
-/// Check payload size validity for given data type
+/// Recursively check an attribute descriptor array, noting issues found
 ///
-/// data_type: payload data type from descriptor
+/// cmd_id: id of the command being checked
 ///
-/// data: payload reference
-fn check_data_type(data_type: LcsfDataType, data: &[u8]) -> bool {
-    match data_type {
-        LcsfDataType::Uint8 => data.len() == std::mem::size_of::<u8>(),
-        LcsfDataType::Uint16 => !data.is_empty() && data.len() <= std::mem::size_of::<u16>(),
-        LcsfDataType::Uint32 => !data.is_empty() && data.len() <= std::mem::size_of::<u32>(),
-        LcsfDataType::Uint64 => !data.is_empty() && data.len() <= std::mem::size_of::<u64>(),
-        LcsfDataType::Float32 => data.len() == std::mem::size_of::<f32>(),
-        LcsfDataType::Float64 => data.len() == std::mem::size_of::<f64>(),
-        LcsfDataType::ByteArray => !data.is_empty(),
-        LcsfDataType::String => !data.is_empty(),
-        LcsfDataType::Subattributes => false,
+/// att_path: id path of the array being checked
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// max_att_id: largest attribute id of the representation mode
+///
+/// group_arr: protocol attribute groups reference
+///
+/// issue_arr: found issues array reference
+fn check_att_desc_arr(
+    cmd_id: u16,
+    att_path: &mut Vec<u16>,
+    att_desc_arr: &[(u16, LcsfAttDesc)],
+    max_att_id: u16,
+    group_arr: &[LcsfAttGroup],
+    issue_arr: &mut Vec<LcsfDescIssue>,
+) {
+    let mut id_set: HashSet<u16> = HashSet::new();
+    for (att_id, att_desc) in att_desc_arr {
+        att_path.push(*att_id);
+        let mut note_issue = |issue: LcsfDescIssueEnum| {
+            issue_arr.push(LcsfDescIssue {
+                cmd_id,
+                att_path: att_path.clone(),
+                issue,
+            })
+        };
+        // Check id
+        if !id_set.insert(*att_id) {
+            note_issue(LcsfDescIssueEnum::DuplicateAttId);
+        }
+        if *att_id > max_att_id {
+            note_issue(LcsfDescIssueEnum::AttIdOutOfRange);
+        }
+        // Check constraints
+        if !is_constraints_valid(att_desc.data_type, &att_desc.constraints) {
+            note_issue(LcsfDescIssueEnum::InvalidConstraints);
+        }
+        // Check scale
+        if let Some(scale) = &att_desc.scale
+            && (!is_uint_type(att_desc.data_type)
+                || scale.factor == 0.0
+                || !scale.factor.is_finite()
+                || !scale.offset.is_finite())
+        {
+            note_issue(LcsfDescIssueEnum::InvalidScale);
+        }
+        // Check default value
+        if let Some(default_value) = &att_desc.default_value
+            && (!att_desc.is_optional
+                || att_desc.cardinality != LcsfAttCardinality::Single
+                || matches!(
+                    att_desc.data_type,
+                    LcsfDataType::Subattributes | LcsfDataType::Message(_)
+                )
+                || !check_data_type(att_desc.data_type, default_value)
+                || (att_desc.data_type == LcsfDataType::String
+                    && !check_string(default_value, false))
+                || !check_constraints(default_value, att_desc.data_type, &att_desc.constraints))
+        {
+            note_issue(LcsfDescIssueEnum::InvalidDefault);
+        }
+        // Check group
+        if let Some(issue) = check_group(group_arr, &att_desc.group, &att_desc.subatt_desc_arr) {
+            note_issue(issue);
+        }
+        // Check exclusive choice
+        if att_desc.oneof != LcsfOneof::Disabled
+            && (att_desc.data_type != LcsfDataType::Subattributes
+                || att_desc.subatt_desc_arr.iter().any(|(_, subatt_desc)| {
+                    !subatt_desc.is_optional || subatt_desc.default_value.is_some()
+                }))
+        {
+            note_issue(LcsfDescIssueEnum::InvalidOneof);
+        }
+        // Check condition
+        if let Some(cond) = &att_desc.required_if
+            && (!att_desc.is_optional
+                || att_desc.default_value.is_some()
+                || cond.sibling_id() == *att_id
+                || match (cond, find_att_desc(att_desc_arr, cond.sibling_id())) {
+                    (_, None) => true,
+                    (LcsfAttCond::Present(_), Some(_)) => false,
+                    (LcsfAttCond::Equals(..), Some(sibling_desc)) => {
+                        sibling_desc.cardinality != LcsfAttCardinality::Single
+                            || !is_uint_type(sibling_desc.data_type)
+                    }
+                })
+        {
+            note_issue(LcsfDescIssueEnum::InvalidCondition);
+        }
+        // Check sub-attributes
+        if att_desc.data_type == LcsfDataType::Subattributes {
+            if att_desc.subatt_desc_arr.is_empty() {
+                note_issue(LcsfDescIssueEnum::EmptySubattArr);
+            } else if att_path.len() >= LCSF_MAX_ATT_DEPTH {
+                note_issue(LcsfDescIssueEnum::TooDeep);
+            } else {
+                check_att_desc_arr(
+                    cmd_id,
+                    att_path,
+                    &att_desc.subatt_desc_arr,
+                    max_att_id,
+                    group_arr,
+                    issue_arr,
+                );
+            }
+        } else if !att_desc.subatt_desc_arr.is_empty() {
+            note_issue(LcsfDescIssueEnum::UnexpectedSubattArr);
+        }
+        att_path.pop();
     }
 }
 
-/// Fill a raw attribute info from a valid attribute
+/// Recursively fill the empty sub-attribute arrays of attributes referencing a group
 ///
-/// data_type: attribute data type from descriptor
+/// group_arr: protocol attribute groups reference
 ///
-/// valid_att: valid attribute reference
-fn fill_att_info(data_type: LcsfDataType, valid_att: &LcsfValidAtt) -> Option<LcsfRawAtt> {
-    let mut raw_att = LcsfRawAtt {
-        has_subatt: false,
-        payload_size: 0,
-        payload: LcsfRawAttPayload::Data(Vec::new()),
-    };
-    // Check sub-attribute type
-    if data_type == LcsfDataType::Subattributes {
-        if let LcsfValidAttPayload::SubattArr(subatt_arr) = &valid_att.payload {
-            if subatt_arr.is_empty() {
-                return None;
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// depth: nesting depth of the array, expansion stops at LCSF_MAX_ATT_DEPTH
+fn expand_att_desc_arr(
+    group_arr: &[LcsfAttGroup],
+    att_desc_arr: &mut [(u16, LcsfAttDesc)],
+    depth: usize,
+) {
+    for (_, att_desc) in att_desc_arr {
+        if att_desc.subatt_desc_arr.is_empty()
+            && let Some(att_group) = find_group(group_arr, &att_desc.group)
+        {
+            att_desc.subatt_desc_arr = att_group.att_desc_arr.clone();
+        }
+        if depth < LCSF_MAX_ATT_DEPTH {
+            expand_att_desc_arr(group_arr, &mut att_desc.subatt_desc_arr, depth + 1);
+        }
+    }
+}
+
+impl LcsfProtDesc {
+    /// Fill the empty attribute arrays of commands and attributes referencing a group, to call
+    /// once when declaring the descriptor
+    #[allow(dead_code)]
+    pub fn expand_groups(mut self) -> Self {
+        // Expand groups first so their copies are complete
+        let src_group_arr = self.group_arr.clone();
+        for att_group in &mut self.group_arr {
+            expand_att_desc_arr(&src_group_arr, &mut att_group.att_desc_arr, 1);
+        }
+        for (_, cmd_desc) in &mut self.cmd_desc_arr {
+            if cmd_desc.att_desc_arr.is_empty()
+                && let Some(att_group) = find_group(&self.group_arr, &cmd_desc.group)
+            {
+                cmd_desc.att_desc_arr = att_group.att_desc_arr.clone();
+            }
+            expand_att_desc_arr(&self.group_arr, &mut cmd_desc.att_desc_arr, 1);
+        }
+        self
+    }
+
+    /// Check the descriptor consistency for a representation mode, returns all found issues
+    ///
+    /// mode: lcsf representation mode, see [LcsfModeEnum]
+    pub fn check(&self, mode: LcsfModeEnum) -> Result<(), Vec<LcsfDescIssue>> {
+        let mut issue_arr: Vec<LcsfDescIssue> = Vec::new();
+        let mut id_set: HashSet<u16> = HashSet::new();
+        // Attribute id msb is the sub-attribute flag
+        let (max_cmd_id, max_att_id) = match mode {
+            LcsfModeEnum::Small => (0xFF, 0x7F),
+            LcsfModeEnum::Normal => (0xFFFF, 0x7FFF),
+        };
+        for (cmd_id, cmd_desc) in &self.cmd_desc_arr {
+            let mut note_issue = |issue: LcsfDescIssueEnum| {
+                issue_arr.push(LcsfDescIssue {
+                    cmd_id: *cmd_id,
+                    att_path: Vec::new(),
+                    issue,
+                })
+            };
+            // Check id
+            if !id_set.insert(*cmd_id) {
+                note_issue(LcsfDescIssueEnum::DuplicateCmdId);
+            }
+            if *cmd_id > max_cmd_id {
+                note_issue(LcsfDescIssueEnum::CmdIdOutOfRange);
+            }
+            // Check group
+            if let Some(issue) =
+                check_group(&self.group_arr, &cmd_desc.group, &cmd_desc.att_desc_arr)
+            {
+                note_issue(issue);
+            }
+            // Check attributes
+            check_att_desc_arr(
+                *cmd_id,
+                &mut Vec::new(),
+                &cmd_desc.att_desc_arr,
+                max_att_id,
+                &self.group_arr,
+                &mut issue_arr,
+            );
+        }
+        if issue_arr.is_empty() {
+            Ok(())
+        } else {
+            Err(issue_arr)
+        }
+    }
+}
+
+// *** Size bounds ***
+
+impl LcsfSizeBounds {
+    /// Return the bounds of two consecutive parts
+    ///
+    /// other: bounds of the second part
+    fn add(self, other: LcsfSizeBounds) -> LcsfSizeBounds {
+        LcsfSizeBounds {
+            min_size: self.min_size + other.min_size,
+            max_size: self.max_size.zip(other.max_size).map(|(a, b)| a + b),
+        }
+    }
+
+    /// Return the bounds of a repeated part
+    ///
+    /// min_nb: smallest number of repetitions
+    ///
+    /// max_nb: largest number of repetitions, None if unbounded
+    fn repeat(self, min_nb: usize, max_nb: Option<usize>) -> LcsfSizeBounds {
+        LcsfSizeBounds {
+            min_size: self.min_size * min_nb,
+            max_size: self.max_size.zip(max_nb).map(|(size, nb)| size * nb),
+        }
+    }
+
+    /// Return true if the largest encoded size is known
+    #[allow(dead_code)]
+    pub fn is_bounded(&self) -> bool {
+        self.max_size.is_some()
+    }
+}
+
+/// Return the payload length bounds declared by constraints
+///
+/// constraints: attribute constraints reference
+///
+/// def_min_len: smallest length when none is declared
+fn get_len_bounds(constraints: &LcsfAttConstraints, def_min_len: usize) -> LcsfSizeBounds {
+    LcsfSizeBounds {
+        min_size: constraints
+            .fixed_len
+            .or(constraints.min_len)
+            .unwrap_or(def_min_len),
+        max_size: constraints.fixed_len.or(constraints.max_len),
+    }
+}
+
+/// Return the encoded payload size bounds of one attribute occurrence
+///
+/// mode: lcsf representation mode, see [LcsfModeEnum]
+///
+/// att_desc: attribute descriptor reference
+fn get_payload_bounds(mode: LcsfModeEnum, att_desc: &LcsfAttDesc) -> LcsfSizeBounds {
+    let fixed = |min_size: usize, max_size: usize| LcsfSizeBounds {
+        min_size,
+        max_size: Some(max_size),
+    };
+    match att_desc.data_type {
+        // Integers are VLE encoded
+        LcsfDataType::Uint8 => fixed(1, size_of::<u8>()),
+        LcsfDataType::Uint16 => fixed(1, size_of::<u16>()),
+        LcsfDataType::Uint32 => fixed(1, size_of::<u32>()),
+        LcsfDataType::Uint64 | LcsfDataType::Timestamp { .. } | LcsfDataType::Duration(_) => {
+            fixed(1, size_of::<u64>())
+        }
+        LcsfDataType::Float32 => fixed(size_of::<f32>(), size_of::<f32>()),
+        LcsfDataType::Float64 => fixed(size_of::<f64>(), size_of::<f64>()),
+        LcsfDataType::ByteArray | LcsfDataType::String => get_len_bounds(&att_desc.constraints, 1),
+        LcsfDataType::Message(inner_mode) => {
+            get_len_bounds(&att_desc.constraints, inner_mode.msg_header_size())
+        }
+        LcsfDataType::Array(elem_type) => {
+            let len_bounds = get_len_bounds(&att_desc.constraints, 1);
+            len_bounds.repeat(elem_type.size(), Some(elem_type.size()))
+        }
+        LcsfDataType::Subattributes => {
+            get_att_arr_bounds(mode, &att_desc.subatt_desc_arr, att_desc.oneof)
+        }
+    }
+}
+
+/// Return the encoded size bounds of a present attribute, all occurrences and headers included
+///
+/// mode: lcsf representation mode, see [LcsfModeEnum]
+///
+/// att_desc: attribute descriptor reference
+fn get_att_bounds(mode: LcsfModeEnum, att_desc: &LcsfAttDesc) -> LcsfSizeBounds {
+    let header_bounds = LcsfSizeBounds {
+        min_size: mode.att_header_size(),
+        max_size: Some(mode.att_header_size()),
+    };
+    let occ_bounds = header_bounds.add(get_payload_bounds(mode, att_desc));
+    match att_desc.cardinality {
+        LcsfAttCardinality::Single => occ_bounds,
+        LcsfAttCardinality::Repeated {
+            min_count,
+            max_count,
+        } => occ_bounds.repeat(min_count.unwrap_or(1).max(1), max_count),
+    }
+}
+
+/// Return the encoded size bounds of an attribute array, headers included
+///
+/// mode: lcsf representation mode, see [LcsfModeEnum]
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// oneof: exclusive choice between the attributes, see [LcsfOneof]
+fn get_att_arr_bounds(
+    mode: LcsfModeEnum,
+    att_desc_arr: &[(u16, LcsfAttDesc)],
+    oneof: LcsfOneof,
+) -> LcsfSizeBounds {
+    let bounds_arr = att_desc_arr
+        .iter()
+        .map(|(_, att_desc)| (att_desc.is_optional, get_att_bounds(mode, att_desc)));
+    match oneof {
+        LcsfOneof::Disabled => bounds_arr.fold(
+            LcsfSizeBounds {
+                min_size: 0,
+                max_size: Some(0),
+            },
+            // Absent optional attributes take no space
+            |bounds, (is_optional, att_bounds)| {
+                bounds.add(att_bounds.repeat(usize::from(!is_optional), Some(1)))
+            },
+        ),
+        LcsfOneof::ExactlyOne | LcsfOneof::AtMostOne => {
+            let bounds_arr: Vec<LcsfSizeBounds> = bounds_arr.map(|(_, bounds)| bounds).collect();
+            LcsfSizeBounds {
+                min_size: match oneof {
+                    LcsfOneof::ExactlyOne => bounds_arr
+                        .iter()
+                        .map(|bounds| bounds.min_size)
+                        .min()
+                        .unwrap_or(0),
+                    _ => 0,
+                },
+                max_size: bounds_arr.iter().try_fold(0, |max_size, bounds| {
+                    bounds.max_size.map(|size| size.max(max_size))
+                }),
+            }
+        }
+    }
+}
+
+impl LcsfCmdDesc {
+    /// Return the encoded size bounds of the command, message header included. Integers take
+    /// their fixed size at most, arrays and strings their declared length limits, and the
+    /// largest size is unbounded if a length or repetition count has no limit.
+    ///
+    /// mode: lcsf representation mode, see [LcsfModeEnum]
+    pub fn size_bounds(&self, mode: LcsfModeEnum) -> LcsfSizeBounds {
+        let header_bounds = LcsfSizeBounds {
+            min_size: mode.msg_header_size(),
+            max_size: Some(mode.msg_header_size()),
+        };
+        header_bounds.add(get_att_arr_bounds(
+            mode,
+            &self.att_desc_arr,
+            LcsfOneof::Disabled,
+        ))
+    }
+}
+
+impl LcsfProtDesc {
+    /// Return the encoded size bounds over all the protocol commands, the largest size is
+    /// unbounded if any command is
+    ///
+    /// mode: lcsf representation mode, see [LcsfModeEnum]
+    pub fn size_bounds(&self, mode: LcsfModeEnum) -> LcsfSizeBounds {
+        let bounds_arr: Vec<LcsfSizeBounds> = self
+            .cmd_desc_arr
+            .iter()
+            .map(|(_, cmd_desc)| cmd_desc.size_bounds(mode))
+            .collect();
+        LcsfSizeBounds {
+            min_size: bounds_arr
+                .iter()
+                .map(|bounds| bounds.min_size)
+                .min()
+                .unwrap_or(0),
+            max_size: bounds_arr.iter().try_fold(0, |max_size, bounds| {
+                bounds.max_size.map(|size| size.max(max_size))
+            }),
+        }
+    }
+}
+
+// *** Validate raw ***
+
+/// Protocol descriptor map, lets the recursive functions look descriptors up whatever the
+/// map value type (reference, Arc or owned descriptor)
+trait LcsfProtDescMap {
+    /// Return a protocol descriptor from its protocol id
+    ///
+    /// prot_id: protocol id value
+    fn get_prot_desc(&self, prot_id: u16) -> Option<&LcsfProtDesc>;
+}
+
+impl<D: Borrow<LcsfProtDesc>> LcsfProtDescMap for HashMap<u16, D> {
+    fn get_prot_desc(&self, prot_id: u16) -> Option<&LcsfProtDesc> {
+        self.get(&prot_id).map(Borrow::borrow)
+    }
+}
+
+/// Nested command error, as (error kind, first error of the nested command if any) tuple
+type LcsfNestedError = (LcsfValidateErrorEnum, Option<Box<LcsfValidateError>>);
+
+/// Validation context, carried through the recursive validation functions
+struct LcsfValidateCtx<'a> {
+    /// Registered protocol descriptors, to validate nested commands
+    prot_desc_map: &'a dyn LcsfProtDescMap,
+    /// Local role, to validate nested commands
+    role: LcsfRole,
+    /// Validation policy of the protocol
+    policy: &'a LcsfValidatePolicy,
+    /// Id of the command being validated
+    cmd_id: u16,
+    /// Id path of the attribute array being validated
+    att_path: Vec<u16>,
+    /// Info of the command being validated, see [LcsfValidInfo]
+    info: LcsfValidInfo,
+    /// Collect every error instead of stopping at the first one
+    collect_err: bool,
+    /// Collected errors, only filled in collect mode
+    err_arr: Vec<LcsfValidateError>,
+    /// Number of nested command layers above the command being validated
+    msg_depth: usize,
+}
+
+impl LcsfValidateCtx<'_> {
+    /// Create an error located at an attribute of the array being validated
+    ///
+    /// kind: error kind
+    ///
+    /// att_id: attribute id value, None for errors on the array itself
+    ///
+    /// data_type: data type expected by the descriptor, if any
+    ///
+    /// shape: shape of the faulty attribute or array, if any
+    fn error(
+        &self,
+        kind: LcsfValidateErrorEnum,
+        att_id: Option<u16>,
+        data_type: Option<LcsfDataType>,
+        shape: Option<LcsfAttShape>,
+    ) -> LcsfValidateError {
+        let mut att_path = self.att_path.clone();
+        att_path.extend(att_id);
+        LcsfValidateError {
+            kind,
+            cmd_id: self.cmd_id,
+            att_path,
+            data_type,
+            shape,
+            nested: None,
+        }
+    }
+
+    /// Report an error, noted in collect mode and returned otherwise
+    ///
+    /// err: error to report
+    fn report(&mut self, err: LcsfValidateError) -> Result<(), LcsfValidateError> {
+        if !self.collect_err {
+            return Err(err);
+        }
+        self.err_arr.push(err);
+        Ok(())
+    }
+}
+
+/// Return the shape of a received attribute
+///
+/// rx_att: received attribute reference
+fn get_raw_shape(rx_att: &LcsfRawAtt) -> LcsfAttShape {
+    if rx_att.has_subatt {
+        LcsfAttShape::SubattArr(rx_att.payload_size as usize)
+    } else {
+        LcsfAttShape::Data(rx_att.payload_size as usize)
+    }
+}
+
+/// Validate the data size of received attribute payload
+///
+/// data_size: size of the data
+///
+/// data_type: type of the data
+fn validate_data_type(data_size: usize, data_type: LcsfDataType) -> bool {
+    // Check data type
+    match data_type {
+        LcsfDataType::Uint8 => data_size == size_of::<u8>(),
+        LcsfDataType::Uint16 => data_size != 0 && data_size <= size_of::<u16>(),
+        LcsfDataType::Uint32 => data_size != 0 && data_size <= size_of::<u32>(),
+        LcsfDataType::Uint64 | LcsfDataType::Timestamp { .. } | LcsfDataType::Duration(_) => {
+            data_size != 0 && data_size <= size_of::<u64>()
+        }
+        LcsfDataType::Float32 => data_size == size_of::<f32>(),
+        LcsfDataType::Float64 => data_size == size_of::<f64>(),
+        LcsfDataType::ByteArray | LcsfDataType::Message(_) => data_size > 0,
+        LcsfDataType::String => data_size > 0,
+        LcsfDataType::Subattributes => false,
+        LcsfDataType::Array(elem_type) => {
+            data_size > 0 && data_size.is_multiple_of(elem_type.size())
+        }
+    }
+}
+
+/// Check a String payload is NUL terminated without interior NUL, and optionally valid UTF-8
+///
+/// data: payload reference
+///
+/// check_utf8: also check the text is valid UTF-8
+pub fn check_string(data: &[u8], check_utf8: bool) -> bool {
+    let Some(text) = data.strip_suffix(&[0]) else {
+        return false;
+    };
+    !text.contains(&0) && (!check_utf8 || std::str::from_utf8(text).is_ok())
+}
+
+/// Find the ids present more than once in a received attribute array, known repeated ids
+/// excepted, unknown ids included
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// rx_att_arr: received (id, attribute) array reference
+fn find_duplicate_ids(
+    att_desc_arr: &[(u16, LcsfAttDesc)],
+    rx_att_arr: &[(u16, LcsfRawAtt)],
+) -> Vec<u16> {
+    let repeated_id_set: HashSet<u16> = att_desc_arr
+        .iter()
+        .filter(|(_, att_desc)| att_desc.cardinality != LcsfAttCardinality::Single)
+        .map(|(att_id, _)| *att_id)
+        .collect();
+    let mut id_set: HashSet<u16> = HashSet::new();
+    let mut dup_id_arr: Vec<u16> = Vec::new();
+    for (att_id, _) in rx_att_arr {
+        if !repeated_id_set.contains(att_id)
+            && !id_set.insert(*att_id)
+            && !dup_id_arr.contains(att_id)
+        {
+            dup_id_arr.push(*att_id);
+        }
+    }
+    dup_id_arr
+}
+
+impl LcsfAttCond {
+    /// Return the id of the sibling attribute the condition depends on
+    fn sibling_id(&self) -> u16 {
+        match self {
+            LcsfAttCond::Present(att_id) | LcsfAttCond::Equals(att_id, _) => *att_id,
+        }
+    }
+
+    /// Check if the condition holds in a valid attribute array
+    ///
+    /// att_desc_arr: (id, attribute descriptor) array reference
+    ///
+    /// valid_att_arr: valid attribute array reference, in descriptor order
+    fn is_met(&self, att_desc_arr: &[(u16, LcsfAttDesc)], valid_att_arr: &[LcsfValidAtt]) -> bool {
+        let Some(((_, sibling_desc), sibling_att)) = att_desc_arr
+            .iter()
+            .zip(valid_att_arr)
+            .find(|((att_id, _), _)| *att_id == self.sibling_id())
+        else {
+            return false;
+        };
+        match self {
+            LcsfAttCond::Present(_) => cnt_present_att(std::slice::from_ref(sibling_att)) > 0,
+            // Sibling data type may not be checked yet, a mismatch doesn't meet the condition
+            LcsfAttCond::Equals(_, value) => matches!(
+                &sibling_att.payload,
+                LcsfValidAttPayload::Data(data)
+                    if check_data_type(sibling_desc.data_type, data) && vle_decode(data) == *value
+            ),
+        }
+    }
+}
+
+/// Find the absent attributes required by a sibling condition in a valid attribute array,
+/// returns their indexes
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// valid_att_arr: valid attribute array reference, in descriptor order
+pub fn find_missing_required(
+    att_desc_arr: &[(u16, LcsfAttDesc)],
+    valid_att_arr: &[LcsfValidAtt],
+) -> Vec<usize> {
+    att_desc_arr
+        .iter()
+        .zip(valid_att_arr)
+        .enumerate()
+        .filter(|(_, ((_, att_desc), valid_att))| {
+            cnt_present_att(std::slice::from_ref(valid_att)) == 0
+                && att_desc
+                    .required_if
+                    .as_ref()
+                    .is_some_and(|cond| cond.is_met(att_desc_arr, valid_att_arr))
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Numeric value checked against value constraints
+enum LcsfNumValue {
+    Int(i128),
+    Float(f64),
+}
+
+impl LcsfNumValue {
+    /// Check the value is within the constraints bounds
+    ///
+    /// constraints: attribute constraints reference
+    fn is_within(&self, constraints: &LcsfAttConstraints) -> bool {
+        // NaN bounds can't be compared, treat them as unset
+        let min_value = constraints.min_value.filter(|min| !min.is_nan());
+        let max_value = constraints.max_value.filter(|max| !max.is_nan());
+        match *self {
+            // Compare integers to the closest integer bounds, f64 can't hold every u64 value
+            LcsfNumValue::Int(value) => {
+                min_value.is_none_or(|min| value >= min.ceil() as i128)
+                    && max_value.is_none_or(|max| value <= max.floor() as i128)
+            }
+            // Comparisons with NaN are always false, reject non finite values instead
+            LcsfNumValue::Float(value) => {
+                (min_value.is_none() && max_value.is_none())
+                    || (value.is_finite()
+                        && min_value.is_none_or(|min| value >= min)
+                        && max_value.is_none_or(|max| value <= max))
+            }
+        }
+    }
+}
+
+/// Check an attribute payload against its descriptor constraints
+///
+/// data: payload reference, its size must already match data_type
+///
+/// data_type: type of the data
+///
+/// constraints: attribute constraints reference
+fn check_constraints(
+    data: &[u8],
+    data_type: LcsfDataType,
+    constraints: &LcsfAttConstraints,
+) -> bool {
+    // Retrieve values and length to check
+    let (value_arr, len) = match data_type {
+        LcsfDataType::Uint8
+        | LcsfDataType::Uint16
+        | LcsfDataType::Uint32
+        | LcsfDataType::Uint64
+        | LcsfDataType::Timestamp { .. }
+        | LcsfDataType::Duration(_) => (vec![LcsfNumValue::Int(vle_decode(data) as i128)], None),
+        LcsfDataType::Float32 => (
+            vec![LcsfNumValue::Float(
+                f32::from_le_bytes(data.try_into().unwrap()) as f64,
+            )],
+            None,
+        ),
+        LcsfDataType::Float64 => (
+            vec![LcsfNumValue::Float(f64::from_le_bytes(
+                data.try_into().unwrap(),
+            ))],
+            None,
+        ),
+        LcsfDataType::ByteArray | LcsfDataType::String | LcsfDataType::Message(_) => {
+            (Vec::new(), Some(data.len()))
+        }
+        LcsfDataType::Array(elem_type) => (
+            data.chunks_exact(elem_type.size())
+                .map(|elem| match elem_type.decode_int(elem) {
+                    Some(value) => LcsfNumValue::Int(value),
+                    // Chunks have the element size, NaN is unreachable
+                    None => LcsfNumValue::Float(elem_type.decode_f64(elem).unwrap_or(f64::NAN)),
+                })
+                .collect(),
+            Some(data.len() / elem_type.size()),
+        ),
+        LcsfDataType::Subattributes => return true,
+    };
+    // Check length constraints
+    if let Some(len) = len
+        && (constraints.fixed_len.is_some_and(|fixed| len != fixed)
+            || constraints.min_len.is_some_and(|min| len < min)
+            || constraints.max_len.is_some_and(|max| len > max))
+    {
+        return false;
+    }
+    // Check value constraints
+    value_arr.iter().all(|value| value.is_within(constraints))
+}
+
+/// Check if a received attribute array has more attributes than its descriptor allows
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// rx_att_nb: number of received attributes
+fn is_too_many_att(att_desc_arr: &[(u16, LcsfAttDesc)], rx_att_nb: usize) -> bool {
+    let mut max_att_nb: usize = 0;
+    for (_, att_desc) in att_desc_arr {
+        match att_desc.cardinality {
+            LcsfAttCardinality::Single => max_att_nb += 1,
+            LcsfAttCardinality::Repeated { max_count, .. } => match max_count {
+                // Unbounded repetition, no limit to check
+                None => return false,
+                Some(max) => max_att_nb += max,
+            },
+        }
+    }
+    rx_att_nb > max_att_nb
+}
+
+/// Validate a received attribute payload, without its presence and cardinality
+///
+/// ctx: validation context reference
+///
+/// att_id: attribute id value
+///
+/// att_desc: attribute descriptor reference
+///
+/// rx_att: received attribute reference
+fn validate_att_payload(
+    ctx: &mut LcsfValidateCtx,
+    att_id: u16,
+    att_desc: &LcsfAttDesc,
+    rx_att: &LcsfRawAtt,
+) -> Result<LcsfValidAtt, LcsfValidateError> {
+    let mut valid_att = LcsfValidAtt {
+        payload: LcsfValidAttPayload::Data(Vec::new()),
+    };
+    let data_type = Some(att_desc.data_type);
+    let shape = Some(get_raw_shape(rx_att));
+    // Check payload type
+    if att_desc.data_type == LcsfDataType::Subattributes {
+        // Check data type
+        if !rx_att.has_subatt {
+            return Err(ctx.error(
+                LcsfValidateErrorEnum::WrongAttDataType,
+                Some(att_id),
+                data_type,
+                shape,
+            ));
+        }
+        // Payload de-structuring
+        if let LcsfRawAttPayload::SubattArr(rx_subatt_arr) = &rx_att.payload {
+            ctx.att_path.push(att_id);
+            let valid_subatt_arr = validate_att_arr(ctx, &att_desc.subatt_desc_arr, rx_subatt_arr);
+            ctx.att_path.pop();
+            let valid_subatt_arr = valid_subatt_arr?;
+            // Check exclusive choice
+            if !att_desc.oneof.check(&valid_subatt_arr) {
+                return Err(ctx.error(
+                    LcsfValidateErrorEnum::WrongOneofNb,
+                    Some(att_id),
+                    data_type,
+                    shape,
+                ));
+            }
+            valid_att.payload = LcsfValidAttPayload::SubattArr(valid_subatt_arr);
+        };
+    } else {
+        // Check data type
+        if rx_att.has_subatt
+            || !validate_data_type(rx_att.payload_size as usize, att_desc.data_type)
+        {
+            return Err(ctx.error(
+                LcsfValidateErrorEnum::WrongAttDataType,
+                Some(att_id),
+                data_type,
+                shape,
+            ));
+        }
+        // Note data
+        if let LcsfRawAttPayload::Data(rx_data) = &rx_att.payload
+            && let LcsfValidAttPayload::Data(valid_data) = &mut valid_att.payload
+        {
+            // Check string format
+            if att_desc.data_type == LcsfDataType::String
+                && !check_string(rx_data, ctx.policy.check_utf8)
+            {
+                return Err(ctx.error(
+                    LcsfValidateErrorEnum::WrongAttDataType,
+                    Some(att_id),
+                    data_type,
+                    shape,
+                ));
+            }
+            // Check constraints
+            if !check_constraints(rx_data, att_desc.data_type, &att_desc.constraints) {
+                return Err(ctx.error(
+                    LcsfValidateErrorEnum::AttOutOfRange,
+                    Some(att_id),
+                    data_type,
+                    shape,
+                ));
+            }
+            // Validate nested command
+            if let LcsfDataType::Message(mode) = att_desc.data_type {
+                match validate_nested(ctx, att_desc, mode, rx_data) {
+                    Err((kind, nested)) => {
+                        let mut err = ctx.error(kind, Some(att_id), data_type, shape);
+                        err.nested = nested;
+                        return Err(err);
+                    }
+                    Ok((prot_id, nested_cmd, nested_info)) => {
+                        // Note nested info under the Message attribute path
+                        let mut att_path = ctx.att_path.clone();
+                        att_path.push(att_id);
+                        let prefix = |path: Vec<u16>| [att_path.as_slice(), &path].concat();
+                        for (path, rx_att) in nested_info.unknown_att_arr {
+                            ctx.info.unknown_att_arr.push((prefix(path), rx_att));
+                        }
+                        for path in nested_info.default_att_arr {
+                            ctx.info.default_att_arr.push(prefix(path));
+                        }
+                        valid_att.payload =
+                            LcsfValidAttPayload::Message(prot_id, Box::new(nested_cmd))
+                    }
+                }
+            } else {
+                *valid_data = rx_data.clone();
+            }
+        };
+    }
+    Ok(valid_att)
+}
+
+/// Decode and validate a nested message against the registered protocols,
+/// returns the error kind and the first nested command error, if any, on failure
+///
+/// ctx: validation context reference
+///
+/// att_desc: Message attribute descriptor reference
+///
+/// mode: nested message representation mode
+///
+/// data: nested message buffer reference
+fn validate_nested(
+    ctx: &LcsfValidateCtx,
+    att_desc: &LcsfAttDesc,
+    mode: LcsfModeEnum,
+    data: &[u8],
+) -> Result<(u16, LcsfValidCmd, LcsfValidInfo), LcsfNestedError> {
+    if ctx.msg_depth >= LCSF_MAX_MSG_DEPTH {
+        return Err((LcsfValidateErrorEnum::TooDeepMsg, None));
+    }
+    let raw_msg = lcsf_transcoder::decode_buff(mode, data)
+        .map_err(|_| (LcsfValidateErrorEnum::WrongAttDataType, None))?;
+    if !att_desc.inner_prot_id_arr.is_empty()
+        && !att_desc.inner_prot_id_arr.contains(&raw_msg.prot_id)
+    {
+        return Err((LcsfValidateErrorEnum::UnknownProtId, None));
+    }
+    validate_msg_mode(
+        ctx.prot_desc_map,
+        ctx.role,
+        &raw_msg,
+        false,
+        ctx.msg_depth + 1,
+    )
+    .map(|(nested_cmd, prot_id, nested_info)| (prot_id, nested_cmd, nested_info))
+    .map_err(|mut err_arr| {
+        let nested_err = err_arr.remove(0);
+        (nested_err.kind, Some(Box::new(nested_err)))
+    })
+}
+
+/// Recursively validate & received attribute and its payload
+///
+/// ctx: validation context reference
+///
+/// att_id: attribute id value
+///
+/// att_desc: attribute descriptor reference
+///
+/// rx_att_arr: received (id, attribute) array reference
+fn validate_att_rec(
+    ctx: &mut LcsfValidateCtx,
+    att_id: u16,
+    att_desc: &LcsfAttDesc,
+    rx_att_arr: &[(u16, LcsfRawAtt)],
+) -> Result<LcsfValidAtt, LcsfValidateError> {
+    // Check for attribute in received array
+    let rx_att_list: Vec<&LcsfRawAtt> = rx_att_arr
+        .iter()
+        .filter(|(rx_id, _)| *rx_id == att_id)
+        .map(|(_, rx_att)| rx_att)
+        .collect();
+    let rx_att_nb = rx_att_list.len();
+    let data_type = Some(att_desc.data_type);
+
+    match att_desc.cardinality {
+        LcsfAttCardinality::Single => {
+            let Some(rx_att) = rx_att_list.first() else {
+                // Attribute missing, check optional
+                if !att_desc.is_optional {
+                    return Err(ctx.error(
+                        LcsfValidateErrorEnum::MissMandatoryAtt,
+                        Some(att_id),
+                        data_type,
+                        Some(LcsfAttShape::Absent),
+                    ));
+                } else if let Some(default_value) = &att_desc.default_value {
+                    // Fill default value
+                    let mut att_path = ctx.att_path.clone();
+                    att_path.push(att_id);
+                    ctx.info.default_att_arr.push(att_path);
+                    return Ok(LcsfValidAtt {
+                        payload: LcsfValidAttPayload::Data(default_value.clone()),
+                    });
+                } else {
+                    return Ok(LcsfValidAtt {
+                        payload: LcsfValidAttPayload::Absent,
+                    });
+                }
+            };
+            // Attribute present, validate payload
+            validate_att_payload(ctx, att_id, att_desc, rx_att)
+        }
+        LcsfAttCardinality::Repeated {
+            min_count,
+            max_count,
+        } => {
+            // Attribute missing, check optional
+            if rx_att_nb == 0 && !att_desc.is_optional {
+                return Err(ctx.error(
+                    LcsfValidateErrorEnum::MissMandatoryAtt,
+                    Some(att_id),
+                    data_type,
+                    Some(LcsfAttShape::List(0)),
+                ));
+            }
+            // Check repetition count, an absent optional attribute has no minimum
+            if (!(rx_att_nb == 0 && att_desc.is_optional)
+                && min_count.is_some_and(|min| rx_att_nb < min))
+                || max_count.is_some_and(|max| rx_att_nb > max)
+            {
+                return Err(ctx.error(
+                    LcsfValidateErrorEnum::WrongAttCount,
+                    Some(att_id),
+                    data_type,
+                    Some(LcsfAttShape::List(rx_att_nb)),
+                ));
+            }
+            // Validate each occurrence
+            let mut valid_item_arr: Vec<LcsfValidAtt> = Vec::new();
+            for rx_att in rx_att_list {
+                valid_item_arr.push(validate_att_payload(ctx, att_id, att_desc, rx_att)?);
+            }
+            Ok(LcsfValidAtt {
+                payload: LcsfValidAttPayload::List(valid_item_arr),
+            })
+        }
+    }
+}
+
+/// Validate a received attribute array following a descriptor array
+///
+/// ctx: validation context reference
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// rx_att_arr: received (id, attribute) array reference
+fn validate_att_arr(
+    ctx: &mut LcsfValidateCtx,
+    att_desc_arr: &[(u16, LcsfAttDesc)],
+    rx_att_arr: &[(u16, LcsfRawAtt)],
+) -> Result<Vec<LcsfValidAtt>, LcsfValidateError> {
+    let mut valid_att_arr: Vec<LcsfValidAtt> = Vec::new();
+
+    // Too many attributes case, extra attributes may be unknown ones in lenient mode
+    if !ctx.policy.ignore_unknown_att && is_too_many_att(att_desc_arr, rx_att_arr.len()) {
+        ctx.report(ctx.error(
+            LcsfValidateErrorEnum::TooManyAtt,
+            None,
+            None,
+            Some(LcsfAttShape::SubattArr(rx_att_arr.len())),
+        ))?;
+    }
+    // Duplicate attribute case
+    for dup_id in find_duplicate_ids(att_desc_arr, rx_att_arr) {
+        let dup_nb = rx_att_arr
+            .iter()
+            .filter(|(att_id, _)| *att_id == dup_id)
+            .count();
+        ctx.report(ctx.error(
+            LcsfValidateErrorEnum::DuplicateAttId,
+            Some(dup_id),
+            find_att_desc(att_desc_arr, dup_id).map(|att_desc| att_desc.data_type),
+            Some(LcsfAttShape::List(dup_nb)),
+        ))?;
+    }
+    // Parse through the descriptor list, faulty attributes are noted absent in collect mode
+    let mut faulty_idx_arr: Vec<usize> = Vec::new();
+    for (idx, (att_id, att_desc)) in att_desc_arr.iter().enumerate() {
+        match validate_att_rec(ctx, *att_id, att_desc, rx_att_arr) {
+            Ok(valid_att) => valid_att_arr.push(valid_att),
+            Err(err) => {
+                ctx.report(err)?;
+                faulty_idx_arr.push(idx);
+                valid_att_arr.push(LcsfValidAtt {
+                    payload: LcsfValidAttPayload::Absent,
+                });
+            }
+        }
+    }
+    // Conditionally required attribute case, faulty attributes are already reported
+    for idx in find_missing_required(att_desc_arr, &valid_att_arr) {
+        if faulty_idx_arr.contains(&idx) {
+            continue;
+        }
+        let (att_id, att_desc) = &att_desc_arr[idx];
+        ctx.report(ctx.error(
+            LcsfValidateErrorEnum::MissRequiredAtt,
+            Some(*att_id),
+            Some(att_desc.data_type),
+            Some(get_valid_shape(&valid_att_arr[idx])),
+        ))?;
+    }
+    // Unrecognized attribute case
+    for (rx_id, rx_att) in rx_att_arr {
+        if att_desc_arr.iter().any(|(att_id, _)| att_id == rx_id) {
+            continue;
+        }
+        if !ctx.policy.ignore_unknown_att {
+            ctx.report(ctx.error(
+                LcsfValidateErrorEnum::UnknownAttId,
+                Some(*rx_id),
+                None,
+                Some(get_raw_shape(rx_att)),
+            ))?;
+            continue;
+        }
+        if ctx.policy.keep_unknown_att {
+            let mut att_path = ctx.att_path.clone();
+            att_path.push(*rx_id);
+            ctx.info.unknown_att_arr.push((att_path, rx_att.clone()));
+        }
+    }
+    Ok(valid_att_arr)
+}
+
+/// Validate a received lcsf raw message, in fast or collect mode
+///
+/// prot_desc_map: (protocol id, protocol descriptor) hash map reference
+///
+/// role: local role, see [LcsfRole]
+///
+/// rx_msg: received message reference
+///
+/// collect_err: collect every error instead of stopping at the first one
+///
+/// msg_depth: number of nested command layers above the message
+fn validate_msg_mode(
+    prot_desc_map: &dyn LcsfProtDescMap,
+    role: LcsfRole,
+    rx_msg: &LcsfRawMsg,
+    collect_err: bool,
+    msg_depth: usize,
+) -> Result<(LcsfValidCmd, u16, LcsfValidInfo), Vec<LcsfValidateError>> {
+    let mut valid_cmd = LcsfValidCmd {
+        cmd_id: 0,
+        att_arr: Vec::new(),
+    };
+    let cmd_error = |kind| {
+        vec![LcsfValidateError {
+            kind,
+            cmd_id: rx_msg.cmd_id,
+            att_path: Vec::new(),
+            data_type: None,
+            shape: None,
+            nested: None,
+        }]
+    };
+    // Check protocol id valid
+    let prot_desc = match prot_desc_map.get_prot_desc(rx_msg.prot_id) {
+        None => return Err(cmd_error(LcsfValidateErrorEnum::UnknownProtId)),
+        Some(desc) => desc,
+    };
+    // Check command id valid
+    let cmd_desc = match prot_desc.get_cmd_desc(rx_msg.cmd_id) {
+        None => return Err(cmd_error(LcsfValidateErrorEnum::UnknownCmdId)),
+        Some(desc) => desc,
+    };
+    // Check command direction
+    if !role.can_receive(cmd_desc.direction) {
+        return Err(cmd_error(LcsfValidateErrorEnum::WrongDirection));
+    }
+    // Note data
+    valid_cmd.cmd_id = rx_msg.cmd_id;
+    // Validate attributes
+    let mut ctx = LcsfValidateCtx {
+        prot_desc_map,
+        role,
+        policy: &prot_desc.policy,
+        cmd_id: rx_msg.cmd_id,
+        att_path: Vec::new(),
+        info: LcsfValidInfo::default(),
+        collect_err,
+        err_arr: Vec::new(),
+        msg_depth,
+    };
+    valid_cmd.att_arr = validate_att_arr(&mut ctx, &cmd_desc.att_desc_arr, &rx_msg.att_arr)
+        .map_err(|err| vec![err])?;
+    if !ctx.err_arr.is_empty() {
+        return Err(ctx.err_arr);
+    }
+    Ok((valid_cmd, rx_msg.prot_id, ctx.info))
+}
+
+/// Validate a received lcsf raw message, stops at the first error
+///
+/// prot_desc_map: (protocol id, protocol descriptor) hash map reference
+///
+/// rx_msg: received message reference
+#[allow(dead_code)]
+pub fn validate_msg<D: Borrow<LcsfProtDesc>>(
+    prot_desc_map: &HashMap<u16, D>,
+    rx_msg: &LcsfRawMsg,
+) -> Result<(LcsfValidCmd, u16, LcsfValidInfo), LcsfValidateError> {
+    validate_msg_as(prot_desc_map, LcsfRole::Any, rx_msg)
+}
+
+/// Validate a received lcsf raw message as a local role, stops at the first error
+///
+/// prot_desc_map: (protocol id, protocol descriptor) hash map reference
+///
+/// role: local role, commands it can't receive are rejected
+///
+/// rx_msg: received message reference
+pub fn validate_msg_as<D: Borrow<LcsfProtDesc>>(
+    prot_desc_map: &HashMap<u16, D>,
+    role: LcsfRole,
+    rx_msg: &LcsfRawMsg,
+) -> Result<(LcsfValidCmd, u16, LcsfValidInfo), LcsfValidateError> {
+    validate_msg_mode(prot_desc_map, role, rx_msg, false, 0)
+        .map_err(|mut err_arr| err_arr.remove(0))
+}
+
+/// Validate a received lcsf raw message and return every error found, for diagnostic purposes
+///
+/// prot_desc_map: (protocol id, protocol descriptor) hash map reference
+///
+/// rx_msg: received message reference
+#[allow(dead_code)]
+pub fn validate_msg_all<D: Borrow<LcsfProtDesc>>(
+    prot_desc_map: &HashMap<u16, D>,
+    rx_msg: &LcsfRawMsg,
+) -> Result<(LcsfValidCmd, u16, LcsfValidInfo), Vec<LcsfValidateError>> {
+    validate_msg_all_as(prot_desc_map, LcsfRole::Any, rx_msg)
+}
+
+/// Validate a received lcsf raw message as a local role and return every error found
+///
+/// prot_desc_map: (protocol id, protocol descriptor) hash map reference
+///
+/// role: local role, commands it can't receive are rejected
+///
+/// rx_msg: received message reference
+pub fn validate_msg_all_as<D: Borrow<LcsfProtDesc>>(
+    prot_desc_map: &HashMap<u16, D>,
+    role: LcsfRole,
+    rx_msg: &LcsfRawMsg,
+) -> Result<(LcsfValidCmd, u16, LcsfValidInfo), Vec<LcsfValidateError>> {
+    validate_msg_mode(prot_desc_map, role, rx_msg, true, 0)
+}
+
+// *** Encode valid ***
+
+/// Count the number of present valid attributes
+///
+/// att_arr: attribute array reference
+fn cnt_present_att(att_arr: &[LcsfValidAtt]) -> u16 {
+    let mut cnt: u16 = 0;
+    for att in att_arr {
+        match &att.payload {
+            LcsfValidAttPayload::Data(_)
+            | LcsfValidAttPayload::SubattArr(_)
+            | LcsfValidAttPayload::Message(..) => cnt += 1,
+            LcsfValidAttPayload::List(item_arr) => {
+                // Each occurrence is sent as a separate attribute
+                cnt += cnt_present_att(item_arr);
+            }
+            LcsfValidAttPayload::Absent => {}
+        }
+    }
+    cnt
+}
+
+impl LcsfOneof {
+    /// Check the number of present sub-attributes is allowed by the exclusive choice
+    ///
+    /// subatt_arr: valid sub-attribute array reference
+    pub fn check(self, subatt_arr: &[LcsfValidAtt]) -> bool {
+        let present_nb = subatt_arr
+            .iter()
+            .filter(|subatt| cnt_present_att(std::slice::from_ref(subatt)) > 0)
+            .count();
+        match self {
+            LcsfOneof::Disabled => true,
+            LcsfOneof::ExactlyOne => present_nb == 1,
+            LcsfOneof::AtMostOne => present_nb <= 1,
+        }
+    }
+}
+
+/// Return the shape of a valid attribute
+///
+/// valid_att: valid attribute reference
+fn get_valid_shape(valid_att: &LcsfValidAtt) -> LcsfAttShape {
+    match &valid_att.payload {
+        LcsfValidAttPayload::Data(data) => LcsfAttShape::Data(data.len()),
+        LcsfValidAttPayload::SubattArr(subatt_arr) => LcsfAttShape::SubattArr(subatt_arr.len()),
+        LcsfValidAttPayload::List(item_arr) => LcsfAttShape::List(item_arr.len()),
+        LcsfValidAttPayload::Absent => LcsfAttShape::Absent,
+        LcsfValidAttPayload::Message(..) => LcsfAttShape::Message,
+    }
+}
+
+/// Create an encoding error for a valid attribute, its id path is completed by the callers
+///
+/// kind: error kind
+///
+/// data_type: attribute data type from descriptor
+///
+/// valid_att: valid attribute reference
+fn encode_error(
+    kind: LcsfValidateErrorEnum,
+    data_type: LcsfDataType,
+    valid_att: &LcsfValidAtt,
+) -> LcsfValidateError {
+    LcsfValidateError {
+        kind,
+        cmd_id: 0,
+        att_path: Vec::new(),
+        data_type: Some(data_type),
+        shape: Some(get_valid_shape(valid_att)),
+        nested: None,
+    }
+}
+
+/// Check payload size validity for given data type
+///
+/// data_type: payload data type from descriptor
+///
+/// data: payload reference
+pub fn check_data_type(data_type: LcsfDataType, data: &[u8]) -> bool {
+    match data_type {
+        LcsfDataType::Uint8 => data.len() == std::mem::size_of::<u8>(),
+        LcsfDataType::Uint16 => !data.is_empty() && data.len() <= std::mem::size_of::<u16>(),
+        LcsfDataType::Uint32 => !data.is_empty() && data.len() <= std::mem::size_of::<u32>(),
+        LcsfDataType::Uint64 | LcsfDataType::Timestamp { .. } | LcsfDataType::Duration(_) => {
+            !data.is_empty() && data.len() <= std::mem::size_of::<u64>()
+        }
+        LcsfDataType::Float32 => data.len() == std::mem::size_of::<f32>(),
+        LcsfDataType::Float64 => data.len() == std::mem::size_of::<f64>(),
+        LcsfDataType::ByteArray | LcsfDataType::Message(_) => !data.is_empty(),
+        LcsfDataType::String => !data.is_empty(),
+        LcsfDataType::Subattributes => false,
+        LcsfDataType::Array(elem_type) => {
+            !data.is_empty() && data.len().is_multiple_of(elem_type.size())
+        }
+    }
+}
+
+/// Fill a raw attribute info from a valid attribute
+///
+/// data_type: attribute data type from descriptor
+///
+/// valid_att: valid attribute reference
+fn fill_att_info(
+    data_type: LcsfDataType,
+    valid_att: &LcsfValidAtt,
+) -> Result<LcsfRawAtt, LcsfValidateError> {
+    match &valid_att.payload {
+        LcsfValidAttPayload::SubattArr(subatt_arr) if data_type == LcsfDataType::Subattributes => {
+            if subatt_arr.is_empty() {
+                return Err(encode_error(
+                    LcsfValidateErrorEnum::WrongAttNb,
+                    data_type,
+                    valid_att,
+                ));
+            }
+            Ok(LcsfRawAtt {
+                has_subatt: true,
+                payload_size: cnt_present_att(subatt_arr),
+                payload: LcsfRawAttPayload::SubattArr(Vec::new()),
+            })
+        }
+        LcsfValidAttPayload::Data(data)
+            if check_data_type(data_type, data)
+                && (data_type != LcsfDataType::String || check_string(data, false)) =>
+        {
+            Ok(LcsfRawAtt {
+                has_subatt: false,
+                payload_size: data.len() as u16,
+                payload: LcsfRawAttPayload::Data(data.clone()),
+            })
+        }
+        _ => Err(encode_error(
+            LcsfValidateErrorEnum::WrongAttDataType,
+            data_type,
+            valid_att,
+        )),
+    }
+}
+
+/// Encoding context, carried through the recursive encoding functions
+#[derive(Default)]
+struct LcsfEncodeCtx<'a> {
+    /// Registered protocol descriptors, to encode nested commands, None if there are none
+    prot_desc_map: Option<&'a dyn LcsfProtDescMap>,
+    /// Local role, to encode nested commands
+    role: LcsfRole,
+    /// Omit attributes equal to their default value
+    omit_default_att: bool,
+    /// Number of nested command layers above the command being encoded
+    msg_depth: usize,
+}
+
+/// Encode a nested command into a raw attribute, returns the error kind and the nested command
+/// error, if any, on failure
+///
+/// ctx: encoding context reference
+///
+/// att_desc: Message attribute descriptor reference
+///
+/// prot_id: nested command protocol id
+///
+/// nested_cmd: nested command reference
+fn fill_nested_att(
+    ctx: &LcsfEncodeCtx,
+    att_desc: &LcsfAttDesc,
+    prot_id: u16,
+    nested_cmd: &LcsfValidCmd,
+) -> Result<LcsfRawAtt, LcsfNestedError> {
+    let LcsfDataType::Message(mode) = att_desc.data_type else {
+        return Err((LcsfValidateErrorEnum::WrongAttDataType, None));
+    };
+    if ctx.msg_depth >= LCSF_MAX_MSG_DEPTH {
+        return Err((LcsfValidateErrorEnum::TooDeepMsg, None));
+    }
+    if !att_desc.inner_prot_id_arr.is_empty() && !att_desc.inner_prot_id_arr.contains(&prot_id) {
+        return Err((LcsfValidateErrorEnum::UnknownProtId, None));
+    }
+    // Retrieve nested command descriptor
+    let prot_desc = ctx
+        .prot_desc_map
+        .and_then(|prot_desc_map| prot_desc_map.get_prot_desc(prot_id))
+        .ok_or((LcsfValidateErrorEnum::UnknownProtId, None))?;
+    let cmd_desc = prot_desc
+        .get_cmd_desc(nested_cmd.cmd_id)
+        .ok_or((LcsfValidateErrorEnum::UnknownCmdId, None))?;
+    let raw_msg = encode_valid_mode(
+        ctx.prot_desc_map,
+        ctx.role,
+        prot_id,
+        cmd_desc,
+        nested_cmd,
+        ctx.msg_depth + 1,
+    )
+    .map_err(|nested_err| (nested_err.kind, Some(Box::new(nested_err))))?;
+    let data = lcsf_transcoder::encode_buff(mode, &raw_msg);
+    Ok(LcsfRawAtt {
+        has_subatt: false,
+        payload_size: data.len() as u16,
+        payload: LcsfRawAttPayload::Data(data),
+    })
+}
+
+/// Fill recursively a raw attribute from a valid attribute following a descriptor
+///
+/// ctx: encoding context reference
+///
+/// att_desc: attribute descriptor reference
+///
+/// valid_att: valid attribute reference
+fn fill_att_rec(
+    ctx: &LcsfEncodeCtx,
+    att_desc: &LcsfAttDesc,
+    valid_att: &LcsfValidAtt,
+) -> Result<LcsfRawAtt, LcsfValidateError> {
+    // Encode nested command
+    if let LcsfValidAttPayload::Message(prot_id, nested_cmd) = &valid_att.payload {
+        return fill_nested_att(ctx, att_desc, *prot_id, nested_cmd).map_err(|(kind, nested)| {
+            LcsfValidateError {
+                nested,
+                ..encode_error(kind, att_desc.data_type, valid_att)
+            }
+        });
+    }
+    // Fill raw att header
+    let mut raw_att = fill_att_info(att_desc.data_type, valid_att)?;
+    // Split data and sub-attribute cases
+    if let LcsfValidAttPayload::SubattArr(valid_subatt_arr) = &valid_att.payload {
+        // Check sub-attribute number
+        if valid_subatt_arr.len() != att_desc.subatt_desc_arr.len() {
+            return Err(encode_error(
+                LcsfValidateErrorEnum::WrongAttNb,
+                att_desc.data_type,
+                valid_att,
+            ));
+        }
+        // Check exclusive choice
+        if !att_desc.oneof.check(valid_subatt_arr) {
+            return Err(encode_error(
+                LcsfValidateErrorEnum::WrongOneofNb,
+                att_desc.data_type,
+                valid_att,
+            ));
+        }
+        // Check conditional requirements
+        if let Some(&idx) =
+            find_missing_required(&att_desc.subatt_desc_arr, valid_subatt_arr).first()
+        {
+            let (subatt_id, subatt_desc) = &att_desc.subatt_desc_arr[idx];
+            let mut err = encode_error(
+                LcsfValidateErrorEnum::MissRequiredAtt,
+                subatt_desc.data_type,
+                &valid_subatt_arr[idx],
+            );
+            err.att_path.push(*subatt_id);
+            return Err(err);
+        }
+        // Parse valid sub-attribute array
+        for ((subatt_id, subatt_desc), valid_subatt) in
+            att_desc.subatt_desc_arr.iter().zip(valid_subatt_arr)
+        {
+            // Store raw sub-attribute
+            if let LcsfRawAttPayload::SubattArr(raw_subat_arr) = &mut raw_att.payload {
+                raw_subat_arr.extend(fill_att_entries(
+                    ctx,
+                    *subatt_id,
+                    subatt_desc,
+                    valid_subatt,
+                )?);
+            };
+        }
+        // Omitted sub-attributes are not counted
+        if let LcsfRawAttPayload::SubattArr(raw_subat_arr) = &raw_att.payload {
+            raw_att.payload_size = raw_subat_arr.len() as u16;
+        }
+    } else if let LcsfValidAttPayload::Data(data) = &valid_att.payload
+        && !check_constraints(data, att_desc.data_type, &att_desc.constraints)
+    {
+        // Check constraints
+        return Err(encode_error(
+            LcsfValidateErrorEnum::AttOutOfRange,
+            att_desc.data_type,
+            valid_att,
+        ));
+    }
+    Ok(raw_att)
+}
+
+/// Fill the raw attributes of a valid attribute following its cardinality, one per occurrence
+///
+/// ctx: encoding context reference
+///
+/// att_desc: attribute descriptor reference
+///
+/// valid_att: valid attribute reference
+fn fill_att_occurrences(
+    ctx: &LcsfEncodeCtx,
+    att_desc: &LcsfAttDesc,
+    valid_att: &LcsfValidAtt,
+) -> Result<Vec<LcsfRawAtt>, LcsfValidateError> {
+    // Absent attribute case, only allowed if optional
+    if valid_att.payload == LcsfValidAttPayload::Absent {
+        if !att_desc.is_optional {
+            return Err(encode_error(
+                LcsfValidateErrorEnum::MissMandatoryAtt,
+                att_desc.data_type,
+                valid_att,
+            ));
+        }
+        return Ok(Vec::new());
+    }
+    match att_desc.cardinality {
+        LcsfAttCardinality::Single => {
+            // Omit attribute equal to its default value
+            if ctx.omit_default_att
+                && let LcsfValidAttPayload::Data(data) = &valid_att.payload
+                && att_desc.default_value.as_ref() == Some(data)
+            {
+                return Ok(Vec::new());
+            }
+            Ok(vec![fill_att_rec(ctx, att_desc, valid_att)?])
+        }
+        LcsfAttCardinality::Repeated {
+            min_count,
+            max_count,
+        } => {
+            let LcsfValidAttPayload::List(item_arr) = &valid_att.payload else {
+                return Err(encode_error(
+                    LcsfValidateErrorEnum::WrongAttDataType,
+                    att_desc.data_type,
+                    valid_att,
+                ));
+            };
+            let item_nb = item_arr.len();
+            // Check missing attribute
+            if item_nb == 0 && !att_desc.is_optional {
+                return Err(encode_error(
+                    LcsfValidateErrorEnum::MissMandatoryAtt,
+                    att_desc.data_type,
+                    valid_att,
+                ));
+            }
+            // Check repetition count
+            if (!(item_nb == 0 && att_desc.is_optional)
+                && min_count.is_some_and(|min| item_nb < min))
+                || max_count.is_some_and(|max| item_nb > max)
+            {
+                return Err(encode_error(
+                    LcsfValidateErrorEnum::WrongAttCount,
+                    att_desc.data_type,
+                    valid_att,
+                ));
+            }
+            // Fill each occurrence
+            item_arr
+                .iter()
+                .map(|item| fill_att_rec(ctx, att_desc, item))
+                .collect()
+        }
+    }
+}
+
+/// Fill the raw (id, attribute) entries of a valid attribute following its cardinality
+///
+/// ctx: encoding context reference
+///
+/// att_id: attribute id value
+///
+/// att_desc: attribute descriptor reference
+///
+/// valid_att: valid attribute reference
+fn fill_att_entries(
+    ctx: &LcsfEncodeCtx,
+    att_id: u16,
+    att_desc: &LcsfAttDesc,
+    valid_att: &LcsfValidAtt,
+) -> Result<Vec<(u16, LcsfRawAtt)>, LcsfValidateError> {
+    match fill_att_occurrences(ctx, att_desc, valid_att) {
+        Ok(raw_att_arr) => Ok(raw_att_arr
+            .into_iter()
+            .map(|raw_att| (att_id, raw_att))
+            .collect()),
+        Err(mut err) => {
+            // Complete error id path
+            err.att_path.insert(0, att_id);
+            Err(err)
+        }
+    }
+}
+
+/// Encode a valid command and its descriptor into a lcsf raw message
+///
+/// prot_desc_map: (protocol id, protocol descriptor) hash map reference, to encode nested
+/// commands, None if there are none
+///
+/// role: local role, see [LcsfRole]
+///
+/// prot_id: protocol id
+///
+/// cmd_desc: command descriptor reference
+///
+/// valid_cmd: valid command reference
+///
+/// msg_depth: number of nested command layers above the command
+fn encode_valid_mode(
+    prot_desc_map: Option<&dyn LcsfProtDescMap>,
+    role: LcsfRole,
+    prot_id: u16,
+    cmd_desc: &LcsfCmdDesc,
+    valid_cmd: &LcsfValidCmd,
+    msg_depth: usize,
+) -> Result<LcsfRawMsg, LcsfValidateError> {
+    let ctx = LcsfEncodeCtx {
+        prot_desc_map,
+        role,
+        omit_default_att: prot_desc_map
+            .and_then(|prot_desc_map| prot_desc_map.get_prot_desc(prot_id))
+            .is_some_and(|prot_desc| prot_desc.policy.omit_default_att),
+        msg_depth,
+    };
+    // Init raw message
+    let mut raw_msg = LcsfRawMsg {
+        prot_id,
+        cmd_id: valid_cmd.cmd_id,
+        att_nb: 0,
+        att_arr: Vec::new(),
+    };
+    // Check command direction
+    if !role.can_send(cmd_desc.direction) {
+        return Err(LcsfValidateError {
+            kind: LcsfValidateErrorEnum::WrongDirection,
+            cmd_id: valid_cmd.cmd_id,
+            att_path: Vec::new(),
+            data_type: None,
+            shape: None,
+            nested: None,
+        });
+    }
+    // Check attribute number
+    if valid_cmd.att_arr.len() != cmd_desc.att_desc_arr.len() {
+        return Err(LcsfValidateError {
+            kind: LcsfValidateErrorEnum::WrongAttNb,
+            cmd_id: valid_cmd.cmd_id,
+            att_path: Vec::new(),
+            data_type: None,
+            shape: Some(LcsfAttShape::SubattArr(valid_cmd.att_arr.len())),
+            nested: None,
+        });
+    }
+    // Check conditional requirements
+    if let Some(&idx) = find_missing_required(&cmd_desc.att_desc_arr, &valid_cmd.att_arr).first() {
+        let (att_id, att_desc) = &cmd_desc.att_desc_arr[idx];
+        let mut err = encode_error(
+            LcsfValidateErrorEnum::MissRequiredAtt,
+            att_desc.data_type,
+            &valid_cmd.att_arr[idx],
+        );
+        err.cmd_id = valid_cmd.cmd_id;
+        err.att_path.push(*att_id);
+        return Err(err);
+    }
+    // Fill attribute array
+    for ((att_id, att_desc), valid_att) in cmd_desc.att_desc_arr.iter().zip(&valid_cmd.att_arr) {
+        let raw_entry_arr =
+            fill_att_entries(&ctx, *att_id, att_desc, valid_att).map_err(|mut err| {
+                err.cmd_id = valid_cmd.cmd_id;
+                err
+            })?;
+        raw_msg.att_arr.extend(raw_entry_arr);
+    }
+    // Omitted attributes are not counted
+    raw_msg.att_nb = raw_msg.att_arr.len() as u16;
+    Ok(raw_msg)
+}
+
+/// Encode a valid command and its descriptor into a lcsf raw message, without nested commands
+///
+/// prot_id: protocol id
+///
+/// cmd_desc: command descriptor reference
+///
+/// valid_cmd: valid command reference
+#[allow(dead_code)]
+pub fn encode_valid(
+    prot_id: u16,
+    cmd_desc: &LcsfCmdDesc,
+    valid_cmd: &LcsfValidCmd,
+) -> Result<LcsfRawMsg, LcsfValidateError> {
+    encode_valid_mode(None, LcsfRole::Any, prot_id, cmd_desc, valid_cmd, 0)
+}
+
+/// Encode a valid command and its descriptor into a lcsf raw message as a local role, nested
+/// commands are encoded following their protocol descriptor, and the protocol policy applies
+///
+/// prot_desc_map: (protocol id, protocol descriptor) hash map reference
+///
+/// role: local role, commands it can't send are rejected
+///
+/// prot_id: protocol id
+///
+/// cmd_desc: command descriptor reference
+///
+/// valid_cmd: valid command reference
+pub fn encode_valid_as<D: Borrow<LcsfProtDesc>>(
+    prot_desc_map: &HashMap<u16, D>,
+    role: LcsfRole,
+    prot_id: u16,
+    cmd_desc: &LcsfCmdDesc,
+    valid_cmd: &LcsfValidCmd,
+) -> Result<LcsfRawMsg, LcsfValidateError> {
+    encode_valid_mode(Some(prot_desc_map), role, prot_id, cmd_desc, valid_cmd, 0)
+}
+
+/// Encode an integer depending on its value
+///
+/// data: integer to encode
+pub fn vle_encode(data: u64) -> Vec<u8> {
+    if data <= 0x0000_00ff {
+        data.to_le_bytes()[0..1].to_vec()
+    } else if data <= 0x0000_ffff {
+        data.to_le_bytes()[0..2].to_vec()
+    } else if data <= 0x00ff_ffff {
+        data.to_le_bytes()[0..3].to_vec()
+    } else if data <= 0xffff_ffff {
+        data.to_le_bytes()[0..4].to_vec()
+    } else if data <= 0x0000_00ff_ffff_ffff {
+        data.to_le_bytes()[0..5].to_vec()
+    } else if data <= 0x0000_ffff_ffff_ffff {
+        data.to_le_bytes()[0..6].to_vec()
+    } else if data <= 0x00ff_ffff_ffff_ffff {
+        data.to_le_bytes()[0..7].to_vec()
+    } else {
+        data.to_le_bytes().to_vec()
+    }
+}
+
+/// Decode a vector into an integer depending on its size
+///
+/// data: vector to decode
+pub fn vle_decode(data: &[u8]) -> u64 {
+    match data.len() {
+        1 => u8::from_le_bytes(data.try_into().unwrap()) as u64,
+        2 => u16::from_le_bytes(data.try_into().unwrap()) as u64,
+        3 => {
+            let mut tmp: [u8; 4] = [0; 4];
+            tmp[..3].copy_from_slice(data);
+            u32::from_le_bytes(tmp) as u64
+        }
+        4 => u32::from_le_bytes(data.try_into().unwrap()) as u64,
+        5 => {
+            let mut tmp: [u8; 8] = [0; 8];
+            tmp[..5].copy_from_slice(data);
+            u64::from_le_bytes(tmp)
+        }
+        6 => {
+            let mut tmp: [u8; 8] = [0; 8];
+            tmp[..6].copy_from_slice(data);
+            u64::from_le_bytes(tmp)
+        }
+        7 => {
+            let mut tmp: [u8; 8] = [0; 8];
+            tmp[..7].copy_from_slice(data);
+            u64::from_le_bytes(tmp)
+        }
+        _ => u64::from_le_bytes(data.try_into().unwrap()),
+    }
+}
+
+// *** Display ***
+
+/// Descriptor-aware display of a valid command, see [LcsfValidCmd::display]
+pub struct LcsfValidCmdDisplay<'a> {
+    cmd_desc: &'a LcsfCmdDesc,
+    valid_cmd: &'a LcsfValidCmd,
+}
+
+impl LcsfValidCmd {
+    /// Return a displayable command, printing attribute names and typed values
+    ///
+    /// cmd_desc: command descriptor reference
+    pub fn display<'a>(&'a self, cmd_desc: &'a LcsfCmdDesc) -> LcsfValidCmdDisplay<'a> {
+        LcsfValidCmdDisplay {
+            cmd_desc,
+            valid_cmd: self,
+        }
+    }
+}
+
+/// Write a descriptor name, or its id if unnamed
+///
+/// f: formatter reference
+///
+/// name: descriptor name
+///
+/// id: descriptor id value
+fn fmt_name(f: &mut fmt::Formatter, name: &str, id: u16) -> fmt::Result {
+    if name.is_empty() {
+        write!(f, "{id:#04x}")
+    } else {
+        write!(f, "{name}")
+    }
+}
+
+/// Write an attribute data following its data type
+///
+/// f: formatter reference
+///
+/// data_type: attribute data type from descriptor
+///
+/// data: payload reference
+fn fmt_data(f: &mut fmt::Formatter, data_type: LcsfDataType, data: &[u8]) -> fmt::Result {
+    // Write raw bytes if data doesn't match its type
+    if !check_data_type(data_type, data) {
+        return write!(f, "{data:02x?}");
+    }
+    match data_type {
+        LcsfDataType::Uint8
+        | LcsfDataType::Uint16
+        | LcsfDataType::Uint32
+        | LcsfDataType::Uint64 => write!(f, "{}", vle_decode(data)),
+        LcsfDataType::Timestamp { res, epoch } => {
+            // Write as Unix time, e.g. @1700000000.5s
+            let time = res.to_time(epoch, vle_decode(data));
+            match time.and_then(|time| time.duration_since(UNIX_EPOCH).ok()) {
+                Some(unix_time) => write!(f, "@{unix_time:?}"),
+                None => write!(f, "{data:02x?}"),
+            }
+        }
+        LcsfDataType::Duration(res) => write!(f, "{:?}", res.to_duration(vle_decode(data))),
+        LcsfDataType::Float32 => write!(f, "{}", f32::from_le_bytes(data.try_into().unwrap())),
+        LcsfDataType::Float64 => write!(f, "{}", f64::from_le_bytes(data.try_into().unwrap())),
+        LcsfDataType::String => {
+            write!(
+                f,
+                "{:?}",
+                String::from_utf8_lossy(data).trim_end_matches('\0')
+            )
+        }
+        LcsfDataType::ByteArray | LcsfDataType::Subattributes | LcsfDataType::Message(_) => {
+            write!(f, "{data:02x?}")
+        }
+        LcsfDataType::Array(elem_type) => {
+            write!(f, "[")?;
+            for (idx, elem) in data.chunks_exact(elem_type.size()).enumerate() {
+                if idx > 0 {
+                    write!(f, ", ")?;
+                }
+                match elem_type.decode_int(elem) {
+                    Some(value) => write!(f, "{value}")?,
+                    None if elem_type == LcsfElemType::Float32 => {
+                        write!(f, "{}", f32::from_le_bytes(elem.try_into().unwrap()))?
+                    }
+                    None => write!(f, "{}", elem_type.decode_f64(elem).unwrap_or(f64::NAN))?,
+                }
+            }
+            write!(f, "]")
+        }
+    }
+}
+
+/// Recursively write a valid attribute following its descriptor
+///
+/// f: formatter reference
+///
+/// att_desc: attribute descriptor reference
+///
+/// valid_att: valid attribute reference
+fn fmt_att_rec(
+    f: &mut fmt::Formatter,
+    att_desc: &LcsfAttDesc,
+    valid_att: &LcsfValidAtt,
+) -> fmt::Result {
+    match &valid_att.payload {
+        LcsfValidAttPayload::Data(data) => match &att_desc.scale {
+            // Write physical value of valid scaled attributes
+            Some(scale)
+                if is_uint_type(att_desc.data_type)
+                    && check_data_type(att_desc.data_type, data) =>
+            {
+                let value = scale.to_physical(vle_decode(data));
+                write!(f, "{value:.0$}", scale.get_precision())?;
+                if !scale.unit.is_empty() {
+                    write!(f, " {}", scale.unit)?;
+                }
+                Ok(())
+            }
+            _ => fmt_data(f, att_desc.data_type, data),
+        },
+        LcsfValidAttPayload::SubattArr(subatt_arr) => {
+            fmt_att_arr(f, &att_desc.subatt_desc_arr, subatt_arr)
+        }
+        LcsfValidAttPayload::Absent => Ok(()),
+        LcsfValidAttPayload::Message(prot_id, nested_cmd) => {
+            write!(f, "<prot {prot_id:#04x} cmd {:#04x}>", nested_cmd.cmd_id)
+        }
+        LcsfValidAttPayload::List(item_arr) => {
+            write!(f, "[")?;
+            for (idx, item) in item_arr.iter().enumerate() {
+                if idx > 0 {
+                    write!(f, ", ")?;
+                }
+                fmt_att_rec(f, att_desc, item)?;
+            }
+            write!(f, "]")
+        }
+    }
+}
+
+/// Write a valid attribute array following its descriptor array, absent attributes are skipped
+///
+/// f: formatter reference
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// att_arr: valid attribute array reference
+fn fmt_att_arr(
+    f: &mut fmt::Formatter,
+    att_desc_arr: &[(u16, LcsfAttDesc)],
+    att_arr: &[LcsfValidAtt],
+) -> fmt::Result {
+    let mut is_first = true;
+    write!(f, "{{")?;
+    for ((att_id, att_desc), valid_att) in att_desc_arr.iter().zip(att_arr) {
+        if cnt_present_att(std::slice::from_ref(valid_att)) == 0 {
+            continue;
+        }
+        write!(f, "{}", if is_first { " " } else { ", " })?;
+        is_first = false;
+        fmt_name(f, &att_desc.name, *att_id)?;
+        write!(f, ": ")?;
+        fmt_att_rec(f, att_desc, valid_att)?;
+    }
+    write!(f, "{}}}", if is_first { "" } else { " " })
+}
+
+impl fmt::Display for LcsfValidCmdDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_name(f, &self.cmd_desc.name, self.valid_cmd.cmd_id)?;
+        write!(f, " ")?;
+        fmt_att_arr(f, &self.cmd_desc.att_desc_arr, &self.valid_cmd.att_arr)
+    }
+}
+
+impl fmt::Display for LcsfAttShape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LcsfAttShape::Data(size) => write!(f, "{size} bytes of data"),
+            LcsfAttShape::SubattArr(nb) => write!(f, "{nb} attributes"),
+            LcsfAttShape::List(nb) => write!(f, "{nb} occurrences"),
+            LcsfAttShape::Absent => write!(f, "no attribute"),
+            LcsfAttShape::Message => write!(f, "a nested command"),
+        }
+    }
+}
+
+impl fmt::Display for LcsfValidateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} in command {:#04x}", self.kind, self.cmd_id)?;
+        for (idx, att_id) in self.att_path.iter().enumerate() {
+            write!(
+                f,
+                "{}{att_id:#04x}",
+                if idx == 0 { " at attribute " } else { "/" }
+            )?;
+        }
+        if let Some(data_type) = self.data_type {
+            write!(f, ", expected {data_type:?}")?;
+        }
+        if let Some(shape) = self.shape {
+            write!(f, ", got {shape}")?;
+        }
+        if let Some(nested) = &self.nested {
+            write!(f, " ({nested})")?;
+        }
+        Ok(())
+    }
+}
+
+// *** Tests ***
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lazy_static::lazy_static;
+
+    #[test]
+    fn test_validate_data_type() {
+        assert!(!validate_data_type(4, LcsfDataType::Uint16));
+        assert!(validate_data_type(4, LcsfDataType::Uint32));
+    }
+
+    #[test]
+    fn test_check_constraints() {
+        let mut constraints = LcsfAttConstraints {
+            min_value: Some(10.0),
+            max_value: Some(300.0),
+            ..Default::default()
+        };
+        // Test numeric types
+        assert!(!check_constraints(
+            &[0x09],
+            LcsfDataType::Uint8,
+            &constraints
+        ));
+        assert!(check_constraints(
+            &[0x0a],
+            LcsfDataType::Uint8,
+            &constraints
+        ));
+        assert!(check_constraints(
+            &[0x2c, 0x01],
+            LcsfDataType::Uint16,
+            &constraints
+        ));
+        assert!(!check_constraints(
+            &[0x2d, 0x01],
+            LcsfDataType::Uint16,
+            &constraints
+        ));
+        assert!(check_constraints(
+            &12.5_f32.to_le_bytes(),
+            LcsfDataType::Float32,
+            &constraints
+        ));
+        assert!(!check_constraints(
+            &9.5_f64.to_le_bytes(),
+            LcsfDataType::Float64,
+            &constraints
+        ));
+        // Value constraints don't apply to arrays
+        assert!(check_constraints(
+            &[0x00],
+            LcsfDataType::ByteArray,
+            &constraints
+        ));
+        // Test length constraints
+        constraints = LcsfAttConstraints {
+            min_len: Some(2),
+            max_len: Some(4),
+            ..Default::default()
+        };
+        assert!(!check_constraints(
+            &[0x00],
+            LcsfDataType::ByteArray,
+            &constraints
+        ));
+        assert!(check_constraints(
+            &[0x00, 0x01],
+            LcsfDataType::ByteArray,
+            &constraints
+        ));
+        assert!(!check_constraints(
+            &[0x41, 0x42, 0x43, 0x44, 0x00],
+            LcsfDataType::String,
+            &constraints
+        ));
+        constraints.fixed_len = Some(3);
+        assert!(!check_constraints(
+            &[0x00, 0x01],
+            LcsfDataType::ByteArray,
+            &constraints
+        ));
+        assert!(check_constraints(
+            &[0x41, 0x42, 0x00],
+            LcsfDataType::String,
+            &constraints
+        ));
+        // Length constraints don't apply to numeric types
+        assert!(check_constraints(
+            &[0x00],
+            LcsfDataType::Uint8,
+            &constraints
+        ));
+        // Test non finite floats fail any set bound
+        constraints = LcsfAttConstraints {
+            max_value: Some(300.0),
+            ..Default::default()
+        };
+        assert!(!check_constraints(
+            &f64::NAN.to_le_bytes(),
+            LcsfDataType::Float64,
+            &constraints
+        ));
+        assert!(!check_constraints(
+            &f32::NAN.to_le_bytes(),
+            LcsfDataType::Float32,
+            &constraints
+        ));
+        assert!(!check_constraints(
+            &f64::NEG_INFINITY.to_le_bytes(),
+            LcsfDataType::Float64,
+            &constraints
+        ));
+        assert!(!check_constraints(
+            &f32::NAN.to_le_bytes(),
+            LcsfDataType::Array(LcsfElemType::Float32),
+            &constraints
+        ));
+        assert!(check_constraints(
+            &f64::NAN.to_le_bytes(),
+            LcsfDataType::Float64,
+            &LcsfAttConstraints::default()
+        ));
+        // Test large integers are compared without loss
+        constraints = LcsfAttConstraints {
+            max_value: Some(9007199254740992.0),
+            ..Default::default()
+        };
+        assert!(check_constraints(
+            &vle_encode(9007199254740992),
+            LcsfDataType::Uint64,
+            &constraints
+        ));
+        assert!(!check_constraints(
+            &vle_encode(9007199254740993),
+            LcsfDataType::Uint64,
+            &constraints
+        ));
+        constraints = LcsfAttConstraints {
+            min_value: Some(0.5),
+            max_value: Some(2.5),
+            ..Default::default()
+        };
+        assert!(!check_constraints(
+            &[0x00],
+            LcsfDataType::Uint8,
+            &constraints
+        ));
+        assert!(check_constraints(
+            &[0x02],
+            LcsfDataType::Uint8,
+            &constraints
+        ));
+        assert!(!check_constraints(
+            &[0x03],
+            LcsfDataType::Uint8,
+            &constraints
+        ));
+        // Test NaN bounds are ignored
+        constraints = LcsfAttConstraints {
+            min_value: Some(10.0),
+            max_value: Some(f64::NAN),
+            ..Default::default()
+        };
+        assert!(check_constraints(
+            &[0x2d, 0x01],
+            LcsfDataType::Uint16,
+            &constraints
+        ));
+        assert!(!check_constraints(
+            &[0x09],
+            LcsfDataType::Uint8,
+            &constraints
+        ));
+        assert!(check_constraints(
+            &12.5_f64.to_le_bytes(),
+            LcsfDataType::Float64,
+            &constraints
+        ));
+    }
+
+    #[test]
+    fn test_constraints_validate_encode() {
+        let prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x01,
+                LcsfCmdDesc {
+                    att_desc_arr: vec![(
+                        0x02,
+                        LcsfAttDesc {
+                            is_optional: false,
+                            data_type: LcsfDataType::Uint16,
+                            constraints: LcsfAttConstraints {
+                                max_value: Some(1000.0),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                    )],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+        let mut raw_msg = LcsfRawMsg {
+            prot_id: 0xab,
+            cmd_id: 0x01,
+            att_nb: 1,
+            att_arr: vec![(
+                0x02,
+                LcsfRawAtt {
+                    has_subatt: false,
+                    payload_size: 2,
+                    payload: LcsfRawAttPayload::Data(vec![0xe9, 0x03]),
+                },
+            )],
+        };
+        let mut valid_cmd = LcsfValidCmd {
+            cmd_id: 0x01,
+            att_arr: vec![LcsfValidAtt {
+                payload: LcsfValidAttPayload::Data(vec![0xe9, 0x03]),
+            }],
+        };
+        // Test error
+        match validate_msg(&prot_desc_map, &raw_msg) {
+            Ok(_) => panic!("validate_msg should fail"),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::AttOutOfRange),
+        }
+        assert!(encode_valid(0xab, &prot_desc.cmd_desc_arr[0].1, &valid_cmd).is_err());
+        // Test valid
+        raw_msg.att_arr[0].1.payload = LcsfRawAttPayload::Data(vec![0xe8, 0x03]);
+        valid_cmd.att_arr[0].payload = LcsfValidAttPayload::Data(vec![0xe8, 0x03]);
+        match validate_msg(&prot_desc_map, &raw_msg) {
+            Err(err) => panic!("validate_msg failed with error: {err:?}, but should not fail"),
+            Ok((cmd, _, _)) => assert_eq!(cmd, valid_cmd),
+        }
+        match encode_valid(0xab, &prot_desc.cmd_desc_arr[0].1, &valid_cmd) {
+            Err(_) => panic!("encode_valid should not fail"),
+            Ok(msg) => assert_eq!(msg, raw_msg),
+        }
+        // Test descriptor check
+        let constrained_desc =
+            |data_type: LcsfDataType, constraints: LcsfAttConstraints| LcsfAttDesc {
+                data_type,
+                constraints,
+                ..Default::default()
+            };
+        let value_constraints = |min_value: f64, max_value: f64| LcsfAttConstraints {
+            min_value: Some(min_value),
+            max_value: Some(max_value),
+            ..Default::default()
+        };
+        let len_constraints =
+            |min_len: usize, max_len: usize, fixed_len: Option<usize>| LcsfAttConstraints {
+                min_len: Some(min_len),
+                max_len: Some(max_len),
+                fixed_len,
+                ..Default::default()
+            };
+        let prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x01,
+                LcsfCmdDesc {
+                    att_desc_arr: vec![
+                        (
+                            0x01,
+                            constrained_desc(LcsfDataType::Uint8, value_constraints(1.0, 1.0)),
+                        ),
+                        (
+                            0x02,
+                            constrained_desc(
+                                LcsfDataType::Array(LcsfElemType::Uint8),
+                                len_constraints(1, 4, Some(4)),
+                            ),
+                        ),
+                        (
+                            0x03,
+                            constrained_desc(LcsfDataType::Uint8, value_constraints(0.0, f64::NAN)),
+                        ),
+                        (
+                            0x04,
+                            constrained_desc(
+                                LcsfDataType::Float32,
+                                value_constraints(f64::NEG_INFINITY, 0.0),
+                            ),
+                        ),
+                        (
+                            0x05,
+                            constrained_desc(LcsfDataType::Uint16, value_constraints(2.0, 1.0)),
+                        ),
+                        (
+                            0x06,
+                            constrained_desc(LcsfDataType::ByteArray, len_constraints(4, 2, None)),
+                        ),
+                        (
+                            0x07,
+                            constrained_desc(LcsfDataType::String, len_constraints(2, 4, Some(5))),
+                        ),
+                        (
+                            0x08,
+                            constrained_desc(LcsfDataType::Uint32, len_constraints(1, 4, None)),
+                        ),
+                        (
+                            0x09,
+                            constrained_desc(LcsfDataType::ByteArray, value_constraints(0.0, 1.0)),
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let issue_arr = prot_desc.check(LcsfModeEnum::Small).unwrap_err();
+        let path_arr: Vec<Vec<u16>> = issue_arr
+            .iter()
+            .filter(|issue| issue.issue == LcsfDescIssueEnum::InvalidConstraints)
+            .map(|issue| issue.att_path.clone())
+            .collect();
+        assert_eq!(
+            path_arr,
+            vec![
+                vec![0x03],
+                vec![0x04],
+                vec![0x05],
+                vec![0x06],
+                vec![0x07],
+                vec![0x08],
+                vec![0x09]
+            ]
+        );
+        assert_eq!(issue_arr.len(), path_arr.len());
+    }
+
+    #[test]
+    fn test_repeated_att() {
+        let cmd_desc = LcsfCmdDesc {
+            att_desc_arr: vec![
+                (
+                    0x01,
+                    LcsfAttDesc {
+                        is_optional: false,
+                        data_type: LcsfDataType::Uint8,
+                        cardinality: LcsfAttCardinality::Repeated {
+                            min_count: Some(2),
+                            max_count: Some(3),
+                        },
+                        ..Default::default()
+                    },
+                ),
+                (
+                    0x02,
+                    LcsfAttDesc {
+                        is_optional: true,
+                        data_type: LcsfDataType::Uint8,
+                        ..Default::default()
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+        let prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(0x01, cmd_desc.clone())],
+            ..Default::default()
+        };
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+        let raw_att = |value: u8| LcsfRawAtt {
+            has_subatt: false,
+            payload_size: 1,
+            payload: LcsfRawAttPayload::Data(vec![value]),
+        };
+        let valid_att = |value: u8| LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(vec![value]),
+        };
+        let mut raw_msg = LcsfRawMsg {
+            prot_id: 0xab,
+            cmd_id: 0x01,
+            att_nb: 1,
+            att_arr: vec![(0x01, raw_att(0x10))],
+        };
+        // Test error
+        match validate_msg(&prot_desc_map, &raw_msg) {
+            Ok(_) => panic!("validate_msg should fail"),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::WrongAttCount),
+        }
+        raw_msg.att_arr = vec![(0x01, raw_att(0x10)); 4];
+        match validate_msg(&prot_desc_map, &raw_msg) {
+            Ok(_) => panic!("validate_msg should fail"),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::WrongAttCount),
+        }
+        raw_msg.att_arr = vec![(0x01, raw_att(0x10)); 5];
+        match validate_msg(&prot_desc_map, &raw_msg) {
+            Ok(_) => panic!("validate_msg should fail"),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::TooManyAtt),
+        }
+        raw_msg.att_arr = vec![
+            (0x01, raw_att(0x10)),
+            (0x02, raw_att(0x20)),
+            (0x01, raw_att(0x11)),
+            (0x02, raw_att(0x21)),
+        ];
+        match validate_msg(&prot_desc_map, &raw_msg) {
+            Ok(_) => panic!("validate_msg should fail"),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::DuplicateAttId),
+        }
+        // Test valid
+        raw_msg.att_arr.pop();
+        raw_msg.att_nb = 3;
+        let valid_cmd = LcsfValidCmd {
+            cmd_id: 0x01,
+            att_arr: vec![
+                LcsfValidAtt {
+                    payload: LcsfValidAttPayload::List(vec![valid_att(0x10), valid_att(0x11)]),
+                },
+                valid_att(0x20),
+            ],
+        };
+        match validate_msg(&prot_desc_map, &raw_msg) {
+            Err(err) => panic!("validate_msg failed with error: {err:?}, but should not fail"),
+            Ok((cmd, _, _)) => assert_eq!(cmd, valid_cmd),
+        }
+        // Test encoding, occurrences are grouped by attribute
+        match encode_valid(0xab, &cmd_desc, &valid_cmd) {
+            Err(_) => panic!("encode_valid should not fail"),
+            Ok(msg) => {
+                assert_eq!(msg.att_nb, 3);
+                assert_eq!(
+                    msg.att_arr,
+                    vec![
+                        (0x01, raw_att(0x10)),
+                        (0x01, raw_att(0x11)),
+                        (0x02, raw_att(0x20))
+                    ]
+                );
+            }
+        }
+        let mut bad_cmd = valid_cmd.clone();
+        bad_cmd.att_arr[0].payload = LcsfValidAttPayload::List(vec![valid_att(0x10)]);
+        assert!(encode_valid(0xab, &cmd_desc, &bad_cmd).is_err());
+        bad_cmd.att_arr[0].payload = valid_att(0x10).payload;
+        assert!(encode_valid(0xab, &cmd_desc, &bad_cmd).is_err());
+        // Test absent mandatory attribute
+        raw_msg.att_arr = vec![(0x02, raw_att(0x20))];
+        raw_msg.att_nb = 1;
+        match validate_msg(&prot_desc_map, &raw_msg) {
+            Ok(_) => panic!("validate_msg should fail"),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::MissMandatoryAtt),
+        }
+        bad_cmd.att_arr[0].payload = LcsfValidAttPayload::List(Vec::new());
+        match encode_valid(0xab, &cmd_desc, &bad_cmd) {
+            Ok(_) => panic!("encode_valid should fail"),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::MissMandatoryAtt),
+        }
+        // Test absent optional attribute, minimum count doesn't apply
+        let mut opt_cmd_desc = cmd_desc.clone();
+        opt_cmd_desc.att_desc_arr[0].1.is_optional = true;
+        let prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(0x01, opt_cmd_desc.clone())],
+            ..Default::default()
+        };
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+        raw_msg.att_arr = vec![(0x02, raw_att(0x20))];
+        let opt_cmd = LcsfValidCmd {
+            cmd_id: 0x01,
+            att_arr: vec![
+                LcsfValidAtt {
+                    payload: LcsfValidAttPayload::List(Vec::new()),
+                },
+                valid_att(0x20),
+            ],
+        };
+        match validate_msg(&prot_desc_map, &raw_msg) {
+            Err(err) => panic!("validate_msg failed with error: {err:?}, but should not fail"),
+            Ok((cmd, _, _)) => assert_eq!(cmd, opt_cmd),
+        }
+        assert!(encode_valid(0xab, &opt_cmd_desc, &opt_cmd).is_ok());
+        // Minimum count still applies to a present optional attribute
+        raw_msg.att_arr.push((0x01, raw_att(0x10)));
+        raw_msg.att_nb = 2;
+        match validate_msg(&prot_desc_map, &raw_msg) {
+            Ok(_) => panic!("validate_msg should fail"),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::WrongAttCount),
+        }
+    }
+
+    #[test]
+    fn test_desc_lookup_display() {
+        let mut prot_desc = LcsfProtDesc {
+            name: "PROT".into(),
+            cmd_desc_arr: TEST_PROT_DESC.cmd_desc_arr.clone(),
+            ..Default::default()
+        };
+        let mut valid_cmd = TEST_VALID_CMD.clone();
+        // Test unnamed display
+        assert_eq!(
+            valid_cmd.display(&prot_desc.cmd_desc_arr[0].1).to_string(),
+            "0x12 { 0x55: [00, 01, 02, 03, 04], 0x7f: { 0x30: 10, 0x31: { 0x32: \"Organoleptic\" } }, 0x40: 52651 }"
+        );
+        // Name some descriptors
+        let cmd_desc = &mut prot_desc.cmd_desc_arr[0].1;
+        cmd_desc.name = "CMD".into();
+        cmd_desc.att_desc_arr[1].1.name = "GROUP".into();
+        cmd_desc.att_desc_arr[1].1.subatt_desc_arr[1]
+            .1
+            .subatt_desc_arr[0]
+            .1
+            .name = "TEXT".into();
+        // Test lookup
+        assert!(prot_desc.get_cmd_desc(0x13).is_none());
+        assert!(prot_desc.get_cmd_by_name("").is_none());
+        assert!(prot_desc.get_cmd_by_name("OTHER").is_none());
+        let (cmd_id, cmd_desc) = prot_desc.get_cmd_by_name("CMD").unwrap();
+        assert_eq!(cmd_id, 0x12);
+        assert_eq!(prot_desc.get_cmd_desc(0x12), Some(cmd_desc));
+        assert!(cmd_desc.get_att_by_name("TEXT").is_none());
+        let (att_id, att_desc) = cmd_desc.get_att_by_name("GROUP").unwrap();
+        assert_eq!(att_id, 0x7f);
+        assert_eq!(cmd_desc.get_att_desc(0x7f), Some(att_desc));
+        assert_eq!(
+            att_desc.get_subatt_desc(0x30),
+            Some(&att_desc.subatt_desc_arr[0].1)
+        );
+        assert!(att_desc.get_subatt_by_name("GROUP").is_none());
+        // Test named display, absent attribute is skipped
+        valid_cmd.att_arr[2].payload = LcsfValidAttPayload::Absent;
+        assert_eq!(
+            valid_cmd.display(cmd_desc).to_string(),
+            "CMD { 0x55: [00, 01, 02, 03, 04], GROUP: { 0x30: 10, 0x31: { TEXT: \"Organoleptic\" } } }"
+        );
+    }
+
+    #[test]
+    fn test_check_desc() {
+        let data_desc = LcsfAttDesc {
+            data_type: LcsfDataType::Uint8,
+            ..Default::default()
+        };
+        // Test valid
+        assert_eq!(TEST_PROT_DESC.check(LcsfModeEnum::Small), Ok(()));
+        assert_eq!(TEST_PROT_DESC.check(LcsfModeEnum::Normal), Ok(()));
+        // Build a too deep attribute
+        let mut deep_desc = data_desc.clone();
+        for _ in 0..LCSF_MAX_ATT_DEPTH {
+            deep_desc = LcsfAttDesc {
+                data_type: LcsfDataType::Subattributes,
+                subatt_desc_arr: vec![(0x00, deep_desc)],
+                ..Default::default()
+            };
+        }
+        let prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![
+                (
+                    0x01,
+                    LcsfCmdDesc {
+                        att_desc_arr: vec![
+                            (0x01, data_desc.clone()),
+                            (0x01, data_desc.clone()),
+                            (0x80, data_desc.clone()),
+                            (
+                                0x02,
+                                LcsfAttDesc {
+                                    data_type: LcsfDataType::Subattributes,
+                                    subatt_desc_arr: vec![(
+                                        0x03,
+                                        LcsfAttDesc {
+                                            data_type: LcsfDataType::Subattributes,
+                                            ..Default::default()
+                                        },
+                                    )],
+                                    ..Default::default()
+                                },
+                            ),
+                            (
+                                0x04,
+                                LcsfAttDesc {
+                                    data_type: LcsfDataType::String,
+                                    subatt_desc_arr: vec![(0x00, data_desc.clone())],
+                                    ..Default::default()
+                                },
+                            ),
+                        ],
+                        ..Default::default()
+                    },
+                ),
+                (
+                    0x01,
+                    LcsfCmdDesc {
+                        att_desc_arr: Vec::new(),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    0x100,
+                    LcsfCmdDesc {
+                        att_desc_arr: vec![(0x05, deep_desc)],
+                        ..Default::default()
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+        let issue = |cmd_id: u16, att_path: Vec<u16>, issue: LcsfDescIssueEnum| LcsfDescIssue {
+            cmd_id,
+            att_path,
+            issue,
+        };
+        // Test error
+        assert_eq!(
+            prot_desc.check(LcsfModeEnum::Small),
+            Err(vec![
+                issue(0x01, vec![0x01], LcsfDescIssueEnum::DuplicateAttId),
+                issue(0x01, vec![0x80], LcsfDescIssueEnum::AttIdOutOfRange),
+                issue(0x01, vec![0x02, 0x03], LcsfDescIssueEnum::EmptySubattArr),
+                issue(0x01, vec![0x04], LcsfDescIssueEnum::UnexpectedSubattArr),
+                issue(0x01, Vec::new(), LcsfDescIssueEnum::DuplicateCmdId),
+                issue(0x100, Vec::new(), LcsfDescIssueEnum::CmdIdOutOfRange),
+                issue(
+                    0x100,
+                    [vec![0x05], vec![0x00; LCSF_MAX_ATT_DEPTH - 1]].concat(),
+                    LcsfDescIssueEnum::TooDeep
+                ),
+            ])
+        );
+        match prot_desc.check(LcsfModeEnum::Normal) {
+            Ok(_) => panic!("check should have failed"),
+            Err(issue_arr) => assert_eq!(issue_arr.len(), 5),
+        }
+    }
+
+    #[test]
+    fn test_scaled_att() {
+        let scale = LcsfAttScale {
+            factor: 0.5,
+            offset: -40.0,
+            unit: "°C".into(),
+        };
+        // Test conversions
+        assert_eq!(scale.to_physical(123), 21.5);
+        assert_eq!(scale.to_raw(21.5), Some(123));
+        assert_eq!(scale.to_raw(21.6), Some(123));
+        assert_eq!(scale.to_raw(-40.5), None);
+        assert_eq!(scale.to_raw(f64::NAN), None);
+        let unit_scale = LcsfAttScale {
+            factor: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            unit_scale.to_raw(18446744073709549568.0),
+            Some(u64::MAX - 2047)
+        );
+        assert_eq!(unit_scale.to_raw(18446744073709551616.0), None);
+        // Test descriptor check
+        let scaled_desc = |data_type: LcsfDataType, factor: f64| LcsfAttDesc {
+            data_type,
+            scale: Some(LcsfAttScale {
+                factor,
+                ..scale.clone()
+            }),
+            ..Default::default()
+        };
+        let prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x01,
+                LcsfCmdDesc {
+                    att_desc_arr: vec![
+                        (0x01, scaled_desc(LcsfDataType::Uint8, 0.5)),
+                        (0x02, scaled_desc(LcsfDataType::Float32, 0.5)),
+                        (0x03, scaled_desc(LcsfDataType::Uint16, 0.0)),
+                        (0x04, scaled_desc(LcsfDataType::Uint16, f64::INFINITY)),
+                    ],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let issue_arr = prot_desc.check(LcsfModeEnum::Small).unwrap_err();
+        let path_arr: Vec<Vec<u16>> = issue_arr
+            .iter()
+            .filter(|issue| issue.issue == LcsfDescIssueEnum::InvalidScale)
+            .map(|issue| issue.att_path.clone())
+            .collect();
+        assert_eq!(path_arr, vec![vec![0x02], vec![0x03], vec![0x04]]);
+        // Test display, with and without unit
+        let mut cmd_desc = LcsfCmdDesc {
+            name: "CMD".into(),
+            att_desc_arr: vec![(0x01, scaled_desc(LcsfDataType::Uint8, 0.5))],
+            ..Default::default()
+        };
+        let valid_cmd = LcsfValidCmd {
+            cmd_id: 0x01,
+            att_arr: vec![LcsfValidAtt {
+                payload: LcsfValidAttPayload::Data(vec![122]),
+            }],
+        };
+        assert_eq!(
+            valid_cmd.display(&cmd_desc).to_string(),
+            "CMD { 0x01: 21.0 °C }"
+        );
+        cmd_desc.att_desc_arr[0].1.scale = Some(LcsfAttScale {
+            factor: 0.001,
+            ..Default::default()
+        });
+        assert_eq!(
+            valid_cmd.display(&cmd_desc).to_string(),
+            "CMD { 0x01: 0.122 }"
+        );
+    }
+
+    #[test]
+    fn test_time_att() {
+        // Test conversions
+        let res = LcsfTimeRes::Micros;
+        assert_eq!(res.to_duration(1500), Duration::from_micros(1500));
+        assert_eq!(res.to_raw(Duration::from_nanos(1500999)), Some(1500));
+        assert_eq!(LcsfTimeRes::Nanos.to_raw(Duration::MAX), None);
+        let time = UNIX_EPOCH + Duration::from_secs(1000) + Duration::from_millis(500);
+        assert_eq!(LcsfTimeRes::Millis.to_time(1000, 500), Some(time));
+        assert_eq!(LcsfTimeRes::Millis.to_raw_time(1000, time), Some(500));
+        assert_eq!(LcsfTimeRes::Millis.to_raw_time(1001, time), None);
+        // Test data size, VLE encoded
+        let time_type = LcsfDataType::Timestamp {
+            res: LcsfTimeRes::Millis,
+            epoch: 1000,
+        };
+        let duration_type = LcsfDataType::Duration(LcsfTimeRes::Micros);
+        assert!(validate_data_type(1, time_type));
+        assert!(validate_data_type(8, duration_type));
+        assert!(!validate_data_type(9, duration_type));
+        assert!(!check_data_type(time_type, &[]));
+        // Test display
+        let cmd_desc = LcsfCmdDesc {
+            name: "CMD".into(),
+            att_desc_arr: vec![
+                (
+                    0x01,
+                    LcsfAttDesc {
+                        data_type: time_type,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    0x02,
+                    LcsfAttDesc {
+                        data_type: duration_type,
+                        ..Default::default()
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+        let valid_cmd = LcsfValidCmd {
+            cmd_id: 0x12,
+            att_arr: vec![
+                LcsfValidAtt {
+                    payload: LcsfValidAttPayload::Data(vec![0xf4, 0x01]),
+                },
+                LcsfValidAtt {
+                    payload: LcsfValidAttPayload::Data(vec![0xdc, 0x05]),
+                },
+            ],
+        };
+        assert_eq!(
+            valid_cmd.display(&cmd_desc).to_string(),
+            "CMD { 0x01: @1000.5s, 0x02: 1.5ms }"
+        );
+    }
+
+    #[test]
+    fn test_cmd_direction() {
+        // Test role rules
+        for (role, direction, can_receive, can_send) in [
+            (LcsfRole::Any, LcsfCmdDirection::ToDevice, true, true),
+            (LcsfRole::Device, LcsfCmdDirection::Both, true, true),
+            (LcsfRole::Device, LcsfCmdDirection::ToDevice, true, false),
+            (LcsfRole::Device, LcsfCmdDirection::FromDevice, false, true),
+            (LcsfRole::Host, LcsfCmdDirection::ToDevice, false, true),
+            (LcsfRole::Host, LcsfCmdDirection::FromDevice, true, false),
+        ] {
+            assert_eq!(role.can_receive(direction), can_receive);
+            assert_eq!(role.can_send(direction), can_send);
+        }
+        // Test validation and encoding
+        let prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x01,
+                LcsfCmdDesc {
+                    direction: LcsfCmdDirection::FromDevice,
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+        let cmd_desc = &prot_desc.cmd_desc_arr[0].1;
+        let valid_cmd = LcsfValidCmd {
+            cmd_id: 0x01,
+            att_arr: Vec::new(),
+        };
+        let raw_msg =
+            encode_valid_as(&prot_desc_map, LcsfRole::Device, 0xab, cmd_desc, &valid_cmd).unwrap();
+        assert!(validate_msg_as(&prot_desc_map, LcsfRole::Host, &raw_msg).is_ok());
+        assert!(validate_msg(&prot_desc_map, &raw_msg).is_ok());
+        let err = validate_msg_as(&prot_desc_map, LcsfRole::Device, &raw_msg).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongDirection);
+        let err = encode_valid_as(&prot_desc_map, LcsfRole::Host, 0xab, cmd_desc, &valid_cmd)
+            .unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongDirection);
+    }
+
+    #[test]
+    fn test_default_att() {
+        let default_desc = |is_optional: bool, default_value: &[u8]| LcsfAttDesc {
+            is_optional,
+            data_type: LcsfDataType::Uint16,
+            constraints: LcsfAttConstraints {
+                max_value: Some(1000.0),
+                ..Default::default()
+            },
+            default_value: Some(default_value.to_vec()),
+            ..Default::default()
+        };
+        let mut prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x01,
+                LcsfCmdDesc {
+                    att_desc_arr: vec![
+                        (0x01, default_desc(true, &[0x05])),
+                        (0x02, default_desc(false, &[0x05])),
+                        (0x03, default_desc(true, &[0x01, 0x02, 0x03])),
+                        (0x04, default_desc(true, &[0xe9, 0x03])),
+                    ],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        // Test descriptor check
+        let issue_arr = prot_desc.check(LcsfModeEnum::Small).unwrap_err();
+        let path_arr: Vec<Vec<u16>> = issue_arr
+            .iter()
+            .filter(|issue| issue.issue == LcsfDescIssueEnum::InvalidDefault)
+            .map(|issue| issue.att_path.clone())
+            .collect();
+        assert_eq!(path_arr, vec![vec![0x02], vec![0x03], vec![0x04]]);
+        // Test default filled on reception
+        prot_desc.cmd_desc_arr[0].1.att_desc_arr.truncate(1);
+        prot_desc.cmd_desc_arr[0]
+            .1
+            .att_desc_arr
+            .push((0x02, default_desc(true, &[0x06])));
+        let cmd_desc = &prot_desc.cmd_desc_arr[0].1;
+        let rx_msg = LcsfRawMsg {
+            prot_id: 0xab,
+            cmd_id: 0x01,
+            att_nb: 1,
+            att_arr: vec![(
+                0x02,
+                LcsfRawAtt {
+                    has_subatt: false,
+                    payload_size: 1,
+                    payload: LcsfRawAttPayload::Data(vec![0x07]),
+                },
+            )],
+        };
+        let (valid_cmd, _, valid_info) = {
+            let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+            validate_msg(&prot_desc_map, &rx_msg).unwrap()
+        };
+        assert_eq!(
+            valid_cmd.att_arr[0].payload,
+            LcsfValidAttPayload::Data(vec![0x05])
+        );
+        assert_eq!(valid_info.default_att_arr, vec![vec![0x01]]);
+        // Test the received command equals the same command built by hand
+        assert_eq!(
+            valid_cmd,
+            LcsfValidCmd {
+                cmd_id: 0x01,
+                att_arr: vec![
+                    LcsfValidAtt {
+                        payload: LcsfValidAttPayload::Data(vec![0x05]),
+                    },
+                    LcsfValidAtt {
+                        payload: LcsfValidAttPayload::Data(vec![0x07]),
+                    },
+                ],
+            }
+        );
+        // Test encoding keeps default values unless the policy omits them
+        let raw_msg = encode_valid(0xab, cmd_desc, &valid_cmd).unwrap();
+        assert_eq!(raw_msg.att_nb, 2);
+        prot_desc.policy.omit_default_att = true;
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+        let cmd_desc = &prot_desc.cmd_desc_arr[0].1;
+        let raw_msg =
+            encode_valid_as(&prot_desc_map, LcsfRole::Any, 0xab, cmd_desc, &valid_cmd).unwrap();
+        assert_eq!(raw_msg, rx_msg);
+    }
+
+    #[test]
+    fn test_att_group() {
+        let att_desc = |data_type: LcsfDataType| LcsfAttDesc {
+            data_type,
+            ..Default::default()
+        };
+        let prot_desc = LcsfProtDesc {
+            group_arr: vec![
+                LcsfAttGroup {
+                    name: "INNER".into(),
+                    att_desc_arr: vec![(0x00, att_desc(LcsfDataType::Uint8))],
+                },
+                LcsfAttGroup {
+                    name: "OUTER".into(),
+                    att_desc_arr: vec![
+                        (0x00, att_desc(LcsfDataType::Uint16)),
+                        (
+                            0x01,
+                            LcsfAttDesc {
+                                data_type: LcsfDataType::Subattributes,
+                                group: "INNER".into(),
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                },
+            ],
+            cmd_desc_arr: vec![
+                (
+                    0x01,
+                    LcsfCmdDesc {
+                        group: "OUTER".into(),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    0x02,
+                    LcsfCmdDesc {
+                        group: "OUTER".into(),
+                        ..Default::default()
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+        // Test expansion
+        let mut prot_desc = prot_desc.expand_groups();
+        assert_eq!(prot_desc.check(LcsfModeEnum::Small), Ok(()));
+        let cmd_desc = prot_desc.get_cmd_desc(0x02).unwrap();
+        assert_eq!(cmd_desc.att_desc_arr.len(), 2);
+        assert_eq!(
+            cmd_desc.att_desc_arr[1].1.subatt_desc_arr,
+            vec![(0x00, att_desc(LcsfDataType::Uint8))]
+        );
+        // Test unknown group and mismatch
+        prot_desc.cmd_desc_arr[0].1.group = "MISSING".into();
+        prot_desc.cmd_desc_arr[1].1.att_desc_arr[1]
+            .1
+            .subatt_desc_arr
+            .push((0x01, att_desc(LcsfDataType::Uint8)));
+        let issue_arr = prot_desc.check(LcsfModeEnum::Small).unwrap_err();
+        assert_eq!(
+            issue_arr,
+            vec![
+                LcsfDescIssue {
+                    cmd_id: 0x01,
+                    att_path: Vec::new(),
+                    issue: LcsfDescIssueEnum::UnknownGroup,
+                },
+                LcsfDescIssue {
+                    cmd_id: 0x02,
+                    att_path: Vec::new(),
+                    issue: LcsfDescIssueEnum::GroupMismatch,
+                },
+                LcsfDescIssue {
+                    cmd_id: 0x02,
+                    att_path: vec![0x01],
+                    issue: LcsfDescIssueEnum::GroupMismatch,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_oneof_att() {
+        let member_desc = |data_type: LcsfDataType| LcsfAttDesc {
+            is_optional: true,
+            data_type,
+            ..Default::default()
+        };
+        let mut prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x01,
+                LcsfCmdDesc {
+                    att_desc_arr: vec![(
+                        0x01,
+                        LcsfAttDesc {
+                            data_type: LcsfDataType::Subattributes,
+                            oneof: LcsfOneof::ExactlyOne,
+                            subatt_desc_arr: vec![
+                                (0x00, member_desc(LcsfDataType::Uint8)),
+                                (
+                                    0x01,
+                                    LcsfAttDesc {
+                                        is_optional: false,
+                                        ..member_desc(LcsfDataType::Uint16)
+                                    },
+                                ),
+                            ],
+                            ..Default::default()
+                        },
+                    )],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        // Test descriptor check
+        let issue_arr = prot_desc.check(LcsfModeEnum::Small).unwrap_err();
+        assert_eq!(
+            issue_arr,
+            vec![LcsfDescIssue {
+                cmd_id: 0x01,
+                att_path: vec![0x01],
+                issue: LcsfDescIssueEnum::InvalidOneof,
+            }]
+        );
+        prot_desc.cmd_desc_arr[0].1.att_desc_arr[0]
+            .1
+            .subatt_desc_arr[1]
+            .1
+            .is_optional = true;
+        assert_eq!(prot_desc.check(LcsfModeEnum::Small), Ok(()));
+        // Test size bounds
+        assert_eq!(
+            prot_desc.size_bounds(LcsfModeEnum::Small),
+            LcsfSizeBounds {
+                min_size: 8,
+                max_size: Some(9),
+            }
+        );
+        // Test reception
+        let raw_subatt = |subatt_id: u16| {
+            (
+                subatt_id,
+                LcsfRawAtt {
+                    has_subatt: false,
+                    payload_size: 1,
+                    payload: LcsfRawAttPayload::Data(vec![0x01]),
+                },
+            )
+        };
+        let rx_msg = |subatt_arr: Vec<(u16, LcsfRawAtt)>| LcsfRawMsg {
+            prot_id: 0xab,
+            cmd_id: 0x01,
+            att_nb: 1,
+            att_arr: vec![(
+                0x01,
+                LcsfRawAtt {
+                    has_subatt: true,
+                    payload_size: subatt_arr.len() as u16,
+                    payload: LcsfRawAttPayload::SubattArr(subatt_arr),
+                },
+            )],
+        };
+        let validate = |prot_desc: &LcsfProtDesc, subatt_arr: Vec<(u16, LcsfRawAtt)>| {
+            let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, prot_desc)]);
+            validate_msg(&prot_desc_map, &rx_msg(subatt_arr)).map(|(valid_cmd, _, _)| valid_cmd)
+        };
+        let valid_cmd = validate(&prot_desc, vec![raw_subatt(0x01)]).unwrap();
+        assert_eq!(
+            valid_cmd.att_arr[0].payload,
+            LcsfValidAttPayload::SubattArr(vec![
+                LcsfValidAtt {
+                    payload: LcsfValidAttPayload::Absent,
+                },
+                LcsfValidAtt {
+                    payload: LcsfValidAttPayload::Data(vec![0x01]),
+                },
+            ])
+        );
+        let err = validate(&prot_desc, vec![raw_subatt(0x00), raw_subatt(0x01)]).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongOneofNb);
+        assert_eq!(err.att_path, vec![0x01]);
+        let err = validate(&prot_desc, Vec::new()).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongOneofNb);
+        prot_desc.cmd_desc_arr[0].1.att_desc_arr[0].1.oneof = LcsfOneof::AtMostOne;
+        assert!(validate(&prot_desc, Vec::new()).is_ok());
+        let err = validate(&prot_desc, vec![raw_subatt(0x00), raw_subatt(0x01)]).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongOneofNb);
+        // Test encoding
+        let cmd_desc = &prot_desc.cmd_desc_arr[0].1;
+        let raw_msg = encode_valid(0xab, cmd_desc, &valid_cmd).unwrap();
+        assert_eq!(raw_msg, rx_msg(vec![raw_subatt(0x01)]));
+        let mut bad_cmd = valid_cmd;
+        bad_cmd.att_arr[0].payload = LcsfValidAttPayload::SubattArr(vec![
+            LcsfValidAtt {
+                payload: LcsfValidAttPayload::Data(vec![0x01]),
+            },
+            LcsfValidAtt {
+                payload: LcsfValidAttPayload::Data(vec![0x01]),
+            },
+        ]);
+        let err = encode_valid(0xab, cmd_desc, &bad_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongOneofNb);
+        assert_eq!(err.att_path, vec![0x01]);
+    }
+
+    #[test]
+    fn test_cond_att() {
+        let cond_desc = |required_if: LcsfAttCond| LcsfAttDesc {
+            is_optional: true,
+            data_type: LcsfDataType::Uint32,
+            required_if: Some(required_if),
+            ..Default::default()
+        };
+        let mut prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x01,
+                LcsfCmdDesc {
+                    att_desc_arr: vec![
+                        (
+                            0x01,
+                            LcsfAttDesc {
+                                data_type: LcsfDataType::Uint16,
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            0x02,
+                            LcsfAttDesc {
+                                is_optional: true,
+                                data_type: LcsfDataType::ByteArray,
+                                ..Default::default()
+                            },
+                        ),
+                        (0x03, cond_desc(LcsfAttCond::Equals(0x01, 0xffff))),
+                        (0x04, cond_desc(LcsfAttCond::Present(0x02))),
+                        (0x05, cond_desc(LcsfAttCond::Equals(0x02, 0x01))),
+                        (0x06, cond_desc(LcsfAttCond::Present(0x07))),
+                        (
+                            0x07,
+                            LcsfAttDesc {
+                                is_optional: false,
+                                ..cond_desc(LcsfAttCond::Present(0x01))
+                            },
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        // Test descriptor check
+        let issue_arr = prot_desc.check(LcsfModeEnum::Small).unwrap_err();
+        let path_arr: Vec<Vec<u16>> = issue_arr
+            .iter()
+            .filter(|issue| issue.issue == LcsfDescIssueEnum::InvalidCondition)
+            .map(|issue| issue.att_path.clone())
+            .collect();
+        assert_eq!(path_arr, vec![vec![0x05], vec![0x07]]);
+        prot_desc.cmd_desc_arr[0].1.att_desc_arr.truncate(4);
+        assert_eq!(prot_desc.check(LcsfModeEnum::Small), Ok(()));
+        // Test reception
+        let raw_att = |att_id: u16, data: Vec<u8>| {
+            (
+                att_id,
+                LcsfRawAtt {
+                    has_subatt: false,
+                    payload_size: data.len() as u16,
+                    payload: LcsfRawAttPayload::Data(data),
+                },
+            )
+        };
+        let rx_msg = |att_arr: Vec<(u16, LcsfRawAtt)>| LcsfRawMsg {
+            prot_id: 0xab,
+            cmd_id: 0x01,
+            att_nb: att_arr.len() as u16,
+            att_arr,
+        };
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+        assert!(
+            validate_msg(
+                &prot_desc_map,
+                &rx_msg(vec![raw_att(0x01, vec![0xfe, 0xff])])
+            )
+            .is_ok()
+        );
+        let err = validate_msg(
+            &prot_desc_map,
+            &rx_msg(vec![raw_att(0x01, vec![0xff, 0xff])]),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::MissRequiredAtt);
+        assert_eq!(err.att_path, vec![0x03]);
+        assert_eq!(err.shape, Some(LcsfAttShape::Absent));
+        let (valid_cmd, _, _) = validate_msg(
+            &prot_desc_map,
+            &rx_msg(vec![
+                raw_att(0x01, vec![0xff, 0xff]),
+                raw_att(0x03, vec![0x01]),
+                raw_att(0x02, vec![0x01]),
+                raw_att(0x04, vec![0x02]),
+            ]),
+        )
+        .unwrap();
+        let err_arr = validate_msg_all(
+            &prot_desc_map,
+            &rx_msg(vec![
+                raw_att(0x01, vec![0xff, 0xff]),
+                raw_att(0x02, vec![0x01]),
+            ]),
+        )
+        .unwrap_err();
+        let path_arr: Vec<Vec<u16>> = err_arr.iter().map(|err| err.att_path.clone()).collect();
+        assert_eq!(path_arr, vec![vec![0x03], vec![0x04]]);
+        // Test encoding
+        let cmd_desc = &prot_desc.cmd_desc_arr[0].1;
+        assert!(encode_valid(0xab, cmd_desc, &valid_cmd).is_ok());
+        let mut bad_cmd = valid_cmd.clone();
+        bad_cmd.att_arr[3].payload = LcsfValidAttPayload::Absent;
+        let err = encode_valid(0xab, cmd_desc, &bad_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::MissRequiredAtt);
+        assert_eq!(err.cmd_id, 0x01);
+        assert_eq!(err.att_path, vec![0x04]);
+        // Test an empty or oversized sibling doesn't meet the condition
+        for sibling_data in [Vec::new(), vec![0xff; 9]] {
+            let mut bad_cmd = valid_cmd.clone();
+            bad_cmd.att_arr[0].payload = LcsfValidAttPayload::Data(sibling_data.clone());
+            bad_cmd.att_arr[2].payload = LcsfValidAttPayload::Absent;
+            let err = encode_valid(0xab, cmd_desc, &bad_cmd).unwrap_err();
+            assert_eq!(err.kind, LcsfValidateErrorEnum::WrongAttDataType);
+            assert_eq!(err.att_path, vec![0x01]);
+            let err_arr =
+                validate_msg_all(&prot_desc_map, &rx_msg(vec![raw_att(0x01, sibling_data)]))
+                    .unwrap_err();
+            assert_eq!(err_arr.len(), 1);
+            assert_eq!(err_arr[0].kind, LcsfValidateErrorEnum::WrongAttDataType);
+            assert_eq!(err_arr[0].att_path, vec![0x01]);
+        }
+    }
+
+    #[test]
+    fn test_size_bounds() {
+        let mut prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x01,
+                LcsfCmdDesc {
+                    att_desc_arr: vec![
+                        (
+                            0x01,
+                            LcsfAttDesc {
+                                data_type: LcsfDataType::Uint16,
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            0x02,
+                            LcsfAttDesc {
+                                is_optional: true,
+                                data_type: LcsfDataType::String,
+                                constraints: LcsfAttConstraints {
+                                    max_len: Some(10),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            0x03,
+                            LcsfAttDesc {
+                                data_type: LcsfDataType::Subattributes,
+                                subatt_desc_arr: vec![(
+                                    0x00,
+                                    LcsfAttDesc {
+                                        data_type: LcsfDataType::Float32,
+                                        ..Default::default()
+                                    },
+                                )],
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            0x04,
+                            LcsfAttDesc {
+                                is_optional: true,
+                                data_type: LcsfDataType::Array(LcsfElemType::Uint16),
+                                constraints: LcsfAttConstraints {
+                                    fixed_len: Some(3),
+                                    ..Default::default()
+                                },
+                                cardinality: LcsfAttCardinality::Repeated {
+                                    min_count: Some(2),
+                                    max_count: Some(4),
+                                },
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        // Test bounded command
+        let cmd_desc = &prot_desc.cmd_desc_arr[0].1;
+        assert_eq!(
+            cmd_desc.size_bounds(LcsfModeEnum::Small),
+            LcsfSizeBounds {
+                min_size: 14,
+                max_size: Some(59),
+            }
+        );
+        assert_eq!(
+            cmd_desc.size_bounds(LcsfModeEnum::Normal),
+            LcsfSizeBounds {
+                min_size: 23,
+                max_size: Some(78),
+            }
+        );
+        // Test smallest message matches its encoding
+        let raw_msg = LcsfRawMsg {
+            prot_id: 0xab,
+            cmd_id: 0x01,
+            att_nb: 2,
+            att_arr: vec![
+                (
+                    0x01,
+                    LcsfRawAtt {
+                        has_subatt: false,
+                        payload_size: 1,
+                        payload: LcsfRawAttPayload::Data(vec![0x05]),
+                    },
+                ),
+                (
+                    0x03,
+                    LcsfRawAtt {
+                        has_subatt: true,
+                        payload_size: 1,
+                        payload: LcsfRawAttPayload::SubattArr(vec![(
+                            0x00,
+                            LcsfRawAtt {
+                                has_subatt: false,
+                                payload_size: 4,
+                                payload: LcsfRawAttPayload::Data(1.5_f32.to_le_bytes().to_vec()),
+                            },
+                        )]),
+                    },
+                ),
+            ],
+        };
+        assert_eq!(
+            lcsf_transcoder::encode_buff(LcsfModeEnum::Small, &raw_msg).len(),
+            14
+        );
+        // Test protocol bounds
+        assert_eq!(
+            prot_desc.size_bounds(LcsfModeEnum::Small),
+            LcsfSizeBounds {
+                min_size: 14,
+                max_size: Some(59),
+            }
+        );
+        prot_desc.cmd_desc_arr.push((
+            0x02,
+            LcsfCmdDesc {
+                att_desc_arr: vec![(
+                    0x01,
+                    LcsfAttDesc {
+                        data_type: LcsfDataType::ByteArray,
+                        ..Default::default()
+                    },
+                )],
+                ..Default::default()
+            },
+        ));
+        let cmd_bounds = prot_desc.cmd_desc_arr[1].1.size_bounds(LcsfModeEnum::Small);
+        assert!(!cmd_bounds.is_bounded());
+        assert_eq!(cmd_bounds.min_size, 6);
+        assert_eq!(
+            prot_desc.size_bounds(LcsfModeEnum::Small),
+            LcsfSizeBounds {
+                min_size: 6,
+                max_size: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_nested_att() {
+        // Gateway protocol tunneling device commands
+        let device_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x02,
+                LcsfCmdDesc {
+                    name: "DEV_CMD".into(),
+                    att_desc_arr: vec![(
+                        0x01,
+                        LcsfAttDesc {
+                            data_type: LcsfDataType::Uint8,
+                            ..Default::default()
+                        },
+                    )],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let gateway_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x01,
+                LcsfCmdDesc {
+                    name: "TUNNEL".into(),
+                    att_desc_arr: vec![(
+                        0x01,
+                        LcsfAttDesc {
+                            data_type: LcsfDataType::Message(LcsfModeEnum::Small),
+                            inner_prot_id_arr: vec![0x56],
+                            ..Default::default()
+                        },
+                    )],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> =
+            HashMap::from([(0x55, &gateway_desc), (0x56, &device_desc)]);
+        let cmd_desc = gateway_desc.get_cmd_desc(0x01).unwrap();
+        let nested_cmd = LcsfValidCmd {
+            cmd_id: 0x02,
+            att_arr: vec![LcsfValidAtt {
+                payload: LcsfValidAttPayload::Data(vec![0x2a]),
+            }],
+        };
+        let tunnel_cmd = |prot_id: u16, nested_cmd: &LcsfValidCmd| LcsfValidCmd {
+            cmd_id: 0x01,
+            att_arr: vec![LcsfValidAtt {
+                payload: LcsfValidAttPayload::Message(prot_id, Box::new(nested_cmd.clone())),
+            }],
+        };
+        let valid_cmd = tunnel_cmd(0x56, &nested_cmd);
+        // Test round trip
+        let raw_msg =
+            encode_valid_as(&prot_desc_map, LcsfRole::Any, 0x55, cmd_desc, &valid_cmd).unwrap();
+        let LcsfRawAttPayload::Data(data) = &raw_msg.att_arr[0].1.payload else {
+            panic!("nested command should be encoded as data");
+        };
+        assert_eq!(data, &vec![0x56, 0x02, 0x01, 0x01, 0x01, 0x2a]);
+        assert_eq!(
+            validate_msg(&prot_desc_map, &raw_msg),
+            Ok((valid_cmd.clone(), 0x55, LcsfValidInfo::default()))
+        );
+        assert_eq!(
+            valid_cmd.display(cmd_desc).to_string(),
+            "TUNNEL { 0x01: <prot 0x56 cmd 0x02> }"
+        );
+        // Test encode without descriptors
+        let err = encode_valid(0x55, cmd_desc, &valid_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::UnknownProtId);
+        // Test undeclared inner protocol
+        let bad_cmd = tunnel_cmd(0x55, &nested_cmd);
+        let err =
+            encode_valid_as(&prot_desc_map, LcsfRole::Any, 0x55, cmd_desc, &bad_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::UnknownProtId);
+        let mut bad_msg = raw_msg.clone();
+        bad_msg.att_arr[0].1.payload = LcsfRawAttPayload::Data(vec![0x55, 0x01, 0x00]);
+        let err = validate_msg(&prot_desc_map, &bad_msg).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::UnknownProtId);
+        assert_eq!(err.att_path, vec![0x01]);
+        // Test invalid inner command, reported at the outer attribute
+        bad_msg.att_arr[0].1.payload = LcsfRawAttPayload::Data(vec![0x56, 0x02, 0x00]);
+        let err = validate_msg(&prot_desc_map, &bad_msg).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::MissMandatoryAtt);
+        assert_eq!(err.att_path, vec![0x01]);
+        let nested_err = err.nested.as_deref().unwrap();
+        assert_eq!(nested_err.cmd_id, 0x02);
+        assert_eq!(nested_err.att_path, vec![0x01]);
+        assert!(nested_err.nested.is_none());
+        // Test invalid inner command on encode
+        let mut bad_nested_cmd = nested_cmd.clone();
+        bad_nested_cmd.att_arr[0].payload = LcsfValidAttPayload::Absent;
+        let bad_cmd = tunnel_cmd(0x56, &bad_nested_cmd);
+        let err =
+            encode_valid_as(&prot_desc_map, LcsfRole::Any, 0x55, cmd_desc, &bad_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::MissMandatoryAtt);
+        assert_eq!(err.att_path, vec![0x01]);
+        let nested_err = err.nested.as_deref().unwrap();
+        assert_eq!(nested_err.cmd_id, 0x02);
+        assert_eq!(nested_err.att_path, vec![0x01]);
+        // Test undecodable inner message
+        bad_msg.att_arr[0].1.payload = LcsfRawAttPayload::Data(vec![0x56]);
+        let err = validate_msg(&prot_desc_map, &bad_msg).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongAttDataType);
+        // Test nested info is noted under the Message attribute path
+        let mut default_desc = device_desc.clone();
+        default_desc.cmd_desc_arr[0].1.att_desc_arr.push((
+            0x02,
+            LcsfAttDesc {
+                is_optional: true,
+                data_type: LcsfDataType::Uint8,
+                default_value: Some(vec![0x03]),
+                ..Default::default()
+            },
+        ));
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> =
+            HashMap::from([(0x55, &gateway_desc), (0x56, &default_desc)]);
+        let (_, _, valid_info) = validate_msg(&prot_desc_map, &raw_msg).unwrap();
+        assert_eq!(valid_info.default_att_arr, vec![vec![0x01, 0x02]]);
+    }
+
+    #[test]
+    fn test_nested_depth() {
+        // Protocol whose command can nest itself
+        let prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x01,
+                LcsfCmdDesc {
+                    name: "LOOP".into(),
+                    att_desc_arr: vec![(
+                        0x01,
+                        LcsfAttDesc {
+                            is_optional: true,
+                            data_type: LcsfDataType::Message(LcsfModeEnum::Small),
+                            ..Default::default()
+                        },
+                    )],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0x57, &prot_desc)]);
+        let cmd_desc = prot_desc.get_cmd_desc(0x01).unwrap();
+        // Build a command with the given number of nested layers
+        let nest_cmd = |depth: usize| {
+            let mut valid_cmd = LcsfValidCmd {
+                cmd_id: 0x01,
+                att_arr: vec![LcsfValidAtt {
+                    payload: LcsfValidAttPayload::Absent,
+                }],
+            };
+            for _ in 0..depth {
+                valid_cmd = LcsfValidCmd {
+                    cmd_id: 0x01,
+                    att_arr: vec![LcsfValidAtt {
+                        payload: LcsfValidAttPayload::Message(0x57, Box::new(valid_cmd)),
+                    }],
+                };
+            }
+            valid_cmd
+        };
+        // Build the matching raw message without the validator
+        let nest_msg = |depth: usize| {
+            let mut raw_msg = LcsfRawMsg {
+                prot_id: 0x57,
+                cmd_id: 0x01,
+                att_nb: 0,
+                att_arr: Vec::new(),
+            };
+            for _ in 0..depth {
+                let data = lcsf_transcoder::encode_buff(LcsfModeEnum::Small, &raw_msg);
+                raw_msg = LcsfRawMsg {
+                    prot_id: 0x57,
+                    cmd_id: 0x01,
+                    att_nb: 1,
+                    att_arr: vec![(
+                        0x01,
+                        LcsfRawAtt {
+                            has_subatt: false,
+                            payload_size: data.len() as u16,
+                            payload: LcsfRawAttPayload::Data(data),
+                        },
+                    )],
+                };
             }
-            // Note data
-            raw_att.has_subatt = true;
-            raw_att.payload_size = cnt_non_empty_att(subatt_arr);
-            raw_att.payload = LcsfRawAttPayload::SubattArr(Vec::new());
+            raw_msg
         };
-    } else {
-        // Check other data types
-        if let LcsfValidAttPayload::Data(data) = &valid_att.payload {
-            if !check_data_type(data_type, data) {
-                return None;
+        // Test maximum depth
+        let valid_cmd = nest_cmd(LCSF_MAX_MSG_DEPTH);
+        let raw_msg = nest_msg(LCSF_MAX_MSG_DEPTH);
+        assert_eq!(
+            encode_valid_as(&prot_desc_map, LcsfRole::Any, 0x57, cmd_desc, &valid_cmd),
+            Ok(raw_msg.clone())
+        );
+        assert_eq!(
+            validate_msg(&prot_desc_map, &raw_msg),
+            Ok((valid_cmd, 0x57, LcsfValidInfo::default()))
+        );
+        // Test depth overflow, reported through every nested layer
+        let check_err = |err: &LcsfValidateError| {
+            let mut err = err;
+            for _ in 0..LCSF_MAX_MSG_DEPTH {
+                assert_eq!(err.kind, LcsfValidateErrorEnum::TooDeepMsg);
+                assert_eq!(err.att_path, vec![0x01]);
+                err = err.nested.as_deref().unwrap();
             }
-            // Note data
-            raw_att.payload_size = data.len() as u16;
-            raw_att.payload = LcsfRawAttPayload::Data(data.clone());
+            assert_eq!(err.kind, LcsfValidateErrorEnum::TooDeepMsg);
+            assert!(err.nested.is_none());
         };
+        let valid_cmd = nest_cmd(LCSF_MAX_MSG_DEPTH + 1);
+        check_err(
+            &encode_valid_as(&prot_desc_map, LcsfRole::Any, 0x57, cmd_desc, &valid_cmd)
+                .unwrap_err(),
+        );
+        check_err(&validate_msg(&prot_desc_map, &nest_msg(LCSF_MAX_MSG_DEPTH + 1)).unwrap_err());
     }
-    Some(raw_att)
-}
 
-/// Fill recursively a raw attribute from a valid attribute following a descriptor
-///
-/// att_desc: attribute descriptor reference
-///
-/// valid_att: valid attribute reference
-fn fill_att_rec(att_desc: &LcsfAttDesc, valid_att: &LcsfValidAtt) -> Option<LcsfRawAtt> {
-    // Init raw_att
-    let mut raw_att = LcsfRawAtt {
-        has_subatt: false,
-        payload_size: 0,
-        payload: LcsfRawAttPayload::Data(Vec::new()),
-    };
-    // Split data and sub-attribute cases
-    if att_desc.data_type == LcsfDataType::Subattributes {
-        if let LcsfValidAttPayload::SubattArr(valid_subatt_arr) = &valid_att.payload {
-            // Check missing attribute
-            if valid_subatt_arr.is_empty() {
-                // Check if mandatory
-                if !att_desc.is_optional {
-                    return None;
-                } else {
-                    return Some(raw_att);
-                }
-            }
-            // Check sub-attribute number
-            if valid_subatt_arr.len() != att_desc.subatt_desc_arr.len() {
-                return None;
-            }
-            // Fill raw att header
-            raw_att = fill_att_info(att_desc.data_type, valid_att)?;
-            // Parse valid sub-attribute array
-            for (idx, valid_subatt) in valid_subatt_arr.iter().enumerate() {
-                // Get sub-attribute description
-                let (subatt_id, subatt_desc) = att_desc.subatt_desc_arr.get(idx)?;
-                // Store raw sub-attribute
-                if let LcsfRawAttPayload::SubattArr(raw_subat_arr) = &mut raw_att.payload {
-                    raw_subat_arr.push((*subatt_id, fill_att_rec(subatt_desc, valid_subatt)?));
-                };
-            }
+    #[test]
+    fn test_lenient_policy() {
+        let mut prot_desc = LcsfProtDesc {
+            cmd_desc_arr: TEST_PROT_DESC.cmd_desc_arr.clone(),
+            ..Default::default()
         };
-    } else if let LcsfValidAttPayload::Data(data) = &valid_att.payload {
-        // Check missing attribute
-        if data.is_empty() {
-            // Check if mandatory
-            if !att_desc.is_optional {
-                return None;
-            } else {
-                return Some(raw_att);
+        let unknown_att = LcsfRawAtt {
+            has_subatt: false,
+            payload_size: 1,
+            payload: LcsfRawAttPayload::Data(vec![0x2a]),
+        };
+        // Add an unknown attribute at command level and in a sub-attribute array
+        let mut rx_msg = TEST_RAW_MSG.clone();
+        rx_msg.att_arr.push((0x60, unknown_att.clone()));
+        rx_msg.att_arr.push((0x61, unknown_att.clone()));
+        if let LcsfRawAttPayload::SubattArr(subatt_arr) = &mut rx_msg.att_arr[1].1.payload {
+            subatt_arr.push((0x33, unknown_att.clone()));
+        };
+        // Test strict policy
+        {
+            let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+            match validate_msg(&prot_desc_map, &rx_msg) {
+                Ok(_) => panic!("validate_msg should have failed"),
+                Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::TooManyAtt),
             }
         }
-        // Fill raw att
-        raw_att = fill_att_info(att_desc.data_type, valid_att)?;
-    };
-    Some(raw_att)
-}
-
-/// Encode a valid command and its descriptor into a lcsf raw message
-///
-/// prot_id: protocol id
-///
-/// cmd_desc: command descriptor reference
-///
-/// valid_cmd: valid command reference
-pub fn encode_valid(
-    prot_id: u16,
-    cmd_desc: &LcsfCmdDesc,
-    valid_cmd: &LcsfValidCmd,
-) -> Option<LcsfRawMsg> {
-    // Init raw message
-    let mut raw_msg = LcsfRawMsg {
-        prot_id,
-        cmd_id: valid_cmd.cmd_id,
-        att_nb: cnt_non_empty_att(&valid_cmd.att_arr),
-        att_arr: Vec::new(),
-    };
-    // Check attribute number
-    if valid_cmd.att_arr.len() != cmd_desc.att_desc_arr.len() {
-        return None;
-    }
-    // Fill attribute array
-    for (idx, valid_att) in valid_cmd.att_arr.iter().enumerate() {
-        let (att_id, att_desc) = cmd_desc.att_desc_arr.get(idx)?;
-        raw_msg
-            .att_arr
-            .push((*att_id, fill_att_rec(att_desc, valid_att)?));
-    }
-    Some(raw_msg)
-}
-
-/// Encode an integer depending on its value
-///
-/// data: integer to encode
-pub fn vle_encode(data: u64) -> Vec<u8> {
-    if data <= 0x0000_00ff {
-        data.to_le_bytes()[0..1].to_vec()
-    } else if data <= 0x0000_ffff {
-        data.to_le_bytes()[0..2].to_vec()
-    } else if data <= 0x00ff_ffff {
-        data.to_le_bytes()[0..3].to_vec()
-    } else if data <= 0xffff_ffff {
-        data.to_le_bytes()[0..4].to_vec()
-    } else if data <= 0x0000_00ff_ffff_ffff {
-        data.to_le_bytes()[0..5].to_vec()
-    } else if data <= 0x0000_ffff_ffff_ffff {
-        data.to_le_bytes()[0..6].to_vec()
-    } else if data <= 0x00ff_ffff_ffff_ffff {
-        data.to_le_bytes()[0..7].to_vec()
-    } else {
-        data.to_le_bytes().to_vec()
-    }
-}
-
-/// Decode a vector into an integer depending on its size
-///
-/// data: vector to decode
-pub fn vle_decode(data: &[u8]) -> u64 {
-    match data.len() {
-        1 => u8::from_le_bytes(data.try_into().unwrap()) as u64,
-        2 => u16::from_le_bytes(data.try_into().unwrap()) as u64,
-        3 => {
-            let mut tmp: [u8; 4] = [0; 4];
-            tmp[..3].copy_from_slice(data);
-            u32::from_le_bytes(tmp) as u64
+        // Test lenient policy, unknown attributes dropped
+        prot_desc.policy.ignore_unknown_att = true;
+        {
+            let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+            match validate_msg(&prot_desc_map, &rx_msg) {
+                Err(err) => panic!("validate_msg failed with error: {err:?}, but should not fail"),
+                Ok((valid_cmd, _, _)) => assert_eq!(valid_cmd, *TEST_VALID_CMD),
+            }
         }
-        4 => u32::from_le_bytes(data.try_into().unwrap()) as u64,
-        5 => {
-            let mut tmp: [u8; 8] = [0; 8];
-            tmp[..5].copy_from_slice(data);
-            u64::from_le_bytes(tmp)
+        // Test lenient policy, unknown attributes kept
+        prot_desc.policy.keep_unknown_att = true;
+        {
+            let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+            match validate_msg(&prot_desc_map, &rx_msg) {
+                Err(err) => panic!("validate_msg failed with error: {err:?}, but should not fail"),
+                Ok((valid_cmd, _, valid_info)) => {
+                    assert_eq!(valid_cmd, *TEST_VALID_CMD);
+                    assert_eq!(
+                        valid_info.unknown_att_arr,
+                        vec![
+                            (vec![0x7f, 0x33], unknown_att.clone()),
+                            (vec![0x60], unknown_att.clone()),
+                            (vec![0x61], unknown_att.clone()),
+                        ]
+                    );
+                }
+            }
         }
-        6 => {
-            let mut tmp: [u8; 8] = [0; 8];
-            tmp[..6].copy_from_slice(data);
-            u64::from_le_bytes(tmp)
+        // Known attributes are still validated
+        rx_msg.att_arr.remove(0);
+        {
+            let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+            match validate_msg(&prot_desc_map, &rx_msg) {
+                Ok(_) => panic!("validate_msg should have failed"),
+                Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::MissMandatoryAtt),
+            }
         }
-        7 => {
-            let mut tmp: [u8; 8] = [0; 8];
-            tmp[..7].copy_from_slice(data);
-            u64::from_le_bytes(tmp)
+        // Repeated unknown ids are still duplicates, at command level and in sub-attributes
+        let mut dup_msg = TEST_RAW_MSG.clone();
+        dup_msg.att_arr.push((0x60, unknown_att.clone()));
+        dup_msg.att_arr.push((0x60, unknown_att.clone()));
+        {
+            let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+            match validate_msg(&prot_desc_map, &dup_msg) {
+                Ok(_) => panic!("validate_msg should have failed"),
+                Err(err) => {
+                    assert_eq!(err.kind, LcsfValidateErrorEnum::DuplicateAttId);
+                    assert_eq!(err.att_path, vec![0x60]);
+                    assert_eq!(err.shape, Some(LcsfAttShape::List(2)));
+                }
+            }
+        }
+        dup_msg = TEST_RAW_MSG.clone();
+        if let LcsfRawAttPayload::SubattArr(subatt_arr) = &mut dup_msg.att_arr[1].1.payload {
+            subatt_arr.push((0x33, unknown_att.clone()));
+            subatt_arr.push((0x33, unknown_att.clone()));
+        };
+        {
+            let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+            match validate_msg(&prot_desc_map, &dup_msg) {
+                Ok(_) => panic!("validate_msg should have failed"),
+                Err(err) => {
+                    assert_eq!(err.kind, LcsfValidateErrorEnum::DuplicateAttId);
+                    assert_eq!(err.att_path, vec![0x7f, 0x33]);
+                }
+            }
         }
-        _ => u64::from_le_bytes(data.try_into().unwrap()),
-    }
-}
-
-// *** Tests ***
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use lazy_static::lazy_static;
-
-    #[test]
-    fn test_validate_data_type() {
-        assert!(!validate_data_type(4, LcsfDataType::Uint16));
-        assert!(validate_data_type(4, LcsfDataType::Uint32));
     }
 
     #[test]
@@ -510,39 +4492,67 @@ mod tests {
             },
         )];
         let att_desc_arr = &TEST_PROT_DESC.cmd_desc_arr[0].1.att_desc_arr;
+        let policy = LcsfValidatePolicy::default();
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::new();
+        let mut ctx = LcsfValidateCtx {
+            prot_desc_map: &prot_desc_map,
+            role: LcsfRole::Any,
+            policy: &policy,
+            cmd_id: 0x12,
+            att_path: Vec::new(),
+            info: LcsfValidInfo::default(),
+            collect_err: false,
+            err_arr: Vec::new(),
+            msg_depth: 0,
+        };
 
         // Test error
-        match validate_att_rec(0x55, &att_desc_arr[0].1, &bad_att1) {
+        match validate_att_rec(&mut ctx, 0x55, &att_desc_arr[0].1, &bad_att1) {
             Ok(_) => panic!("validate_att_rec should have failed"),
-            Err(err) => assert_eq!(err, LcsfValidateErrorEnum::MissMandatoryAtt),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::MissMandatoryAtt),
         }
-        match validate_att_rec(0x40, &att_desc_arr[2].1, &bad_att1) {
+        match validate_att_rec(&mut ctx, 0x40, &att_desc_arr[2].1, &bad_att1) {
             Ok(_) => panic!("validate_att_rec should have failed"),
-            Err(err) => assert_eq!(err, LcsfValidateErrorEnum::WrongAttDataType),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::WrongAttDataType),
         }
-        match validate_att_rec(0x31, &att_desc_arr[1].1.subatt_desc_arr[1].1, &bad_att2) {
+        match validate_att_rec(
+            &mut ctx,
+            0x31,
+            &att_desc_arr[1].1.subatt_desc_arr[1].1,
+            &bad_att2,
+        ) {
             Ok(_) => panic!("validate_att_rec should have failed"),
-            Err(err) => assert_eq!(err, LcsfValidateErrorEnum::WrongAttDataType),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::WrongAttDataType),
         }
         bad_att2[0].1.has_subatt = true;
-        match validate_att_rec(0x31, &att_desc_arr[1].1.subatt_desc_arr[1].1, &bad_att2) {
+        match validate_att_rec(
+            &mut ctx,
+            0x31,
+            &att_desc_arr[1].1.subatt_desc_arr[1].1,
+            &bad_att2,
+        ) {
             Ok(_) => panic!("validate_att_rec should have failed"),
-            Err(err) => assert_eq!(err, LcsfValidateErrorEnum::TooManyAtt),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::TooManyAtt),
         }
         if let LcsfRawAttPayload::SubattArr(subatt_arr) = &mut bad_att2[0].1.payload {
             subatt_arr.remove(1);
         };
-        match validate_att_rec(0x31, &att_desc_arr[1].1.subatt_desc_arr[1].1, &bad_att2) {
+        match validate_att_rec(
+            &mut ctx,
+            0x31,
+            &att_desc_arr[1].1.subatt_desc_arr[1].1,
+            &bad_att2,
+        ) {
             Ok(_) => panic!("validate_att_rec should have failed"),
-            Err(err) => assert_eq!(err, LcsfValidateErrorEnum::UnknownAttId),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::UnknownAttId),
         }
         // Test valid
         for (idx, (att_id, att_desc)) in att_desc_arr.iter().enumerate() {
-            match validate_att_rec(*att_id, att_desc, &TEST_RAW_MSG.att_arr) {
+            match validate_att_rec(&mut ctx, *att_id, att_desc, &TEST_RAW_MSG.att_arr) {
                 Err(err) => {
                     panic!("decode_att_rec failed with error: {err:?}, but should not fail")
                 }
-                Ok((_, valid_att)) => assert_eq!(valid_att, TEST_VALID_CMD.att_arr[idx]),
+                Ok(valid_att) => assert_eq!(valid_att, TEST_VALID_CMD.att_arr[idx]),
             };
         }
     }
@@ -594,28 +4604,370 @@ mod tests {
         // Test error
         match validate_msg(&prot_desc_map, &bad_msg) {
             Ok(_) => panic!("validate_msg should fail"),
-            Err(err) => assert_eq!(err, LcsfValidateErrorEnum::UnknownProtId),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::UnknownProtId),
         }
         bad_msg.prot_id = 0xab;
         match validate_msg(&prot_desc_map, &bad_msg) {
             Ok(_) => panic!("validate_msg should fail"),
-            Err(err) => assert_eq!(err, LcsfValidateErrorEnum::UnknownCmdId),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::UnknownCmdId),
         }
         bad_msg.cmd_id = 0x12;
         match validate_msg(&prot_desc_map, &bad_msg) {
             Ok(_) => panic!("validate_msg should fail"),
-            Err(err) => assert_eq!(err, LcsfValidateErrorEnum::TooManyAtt),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::TooManyAtt),
+        }
+        let mut dup_msg = TEST_RAW_MSG.clone();
+        dup_msg.att_arr[2] = dup_msg.att_arr[0].clone();
+        match validate_msg(&prot_desc_map, &dup_msg) {
+            Ok(_) => panic!("validate_msg should fail"),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::DuplicateAttId),
+        }
+        dup_msg = TEST_RAW_MSG.clone();
+        if let LcsfRawAttPayload::SubattArr(subatt_arr) = &mut dup_msg.att_arr[1].1.payload {
+            subatt_arr[1] = subatt_arr[0].clone();
+        };
+        match validate_msg(&prot_desc_map, &dup_msg) {
+            Ok(_) => panic!("validate_msg should fail"),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::DuplicateAttId),
         }
         // Test valid
         match validate_msg(&prot_desc_map, &TEST_RAW_MSG) {
             Err(err) => panic!("decode_att_rec failed with error: {err:?}, but should not fail"),
-            Ok((valid_cmd, id)) => {
+            Ok((valid_cmd, id, _)) => {
                 assert_eq!(valid_cmd, *TEST_VALID_CMD);
                 assert_eq!(id, 0xab);
             }
         }
     }
 
+    #[test]
+    fn test_error_path() {
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &*TEST_PROT_DESC)]);
+        let cmd_desc = &TEST_PROT_DESC.cmd_desc_arr[0].1;
+        // Test command error
+        let mut raw_msg = TEST_RAW_MSG.clone();
+        raw_msg.cmd_id = 0x13;
+        let err = validate_msg(&prot_desc_map, &raw_msg).unwrap_err();
+        assert_eq!(err.to_string(), "UnknownCmdId in command 0x13");
+        // Test nested wrong data type, in both directions
+        let mut raw_msg = TEST_RAW_MSG.clone();
+        if let LcsfRawAttPayload::SubattArr(subatt_arr) = &mut raw_msg.att_arr[1].1.payload
+            && let LcsfRawAttPayload::SubattArr(subsubatt_arr) = &mut subatt_arr[1].1.payload
+        {
+            subsubatt_arr[0].1.payload_size = 0;
+            subsubatt_arr[0].1.payload = LcsfRawAttPayload::Data(Vec::new());
+        }
+        let err = validate_msg(&prot_desc_map, &raw_msg).unwrap_err();
+        assert_eq!(
+            err,
+            LcsfValidateError {
+                kind: LcsfValidateErrorEnum::WrongAttDataType,
+                cmd_id: 0x12,
+                att_path: vec![0x7f, 0x31, 0x32],
+                data_type: Some(LcsfDataType::String),
+                shape: Some(LcsfAttShape::Data(0)),
+                nested: None,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "WrongAttDataType in command 0x12 at attribute 0x7f/0x31/0x32, expected String, got 0 bytes of data"
+        );
+        let mut valid_cmd = TEST_VALID_CMD.clone();
+        if let LcsfValidAttPayload::SubattArr(subatt_arr) = &mut valid_cmd.att_arr[1].payload
+            && let LcsfValidAttPayload::SubattArr(subsubatt_arr) = &mut subatt_arr[1].payload
+        {
+            subsubatt_arr[0].payload = LcsfValidAttPayload::Data(Vec::new());
+        }
+        assert_eq!(encode_valid(0xab, cmd_desc, &valid_cmd), Err(err));
+        // Test missing attribute, in both directions
+        let mut raw_msg = TEST_RAW_MSG.clone();
+        if let LcsfRawAttPayload::SubattArr(subatt_arr) = &mut raw_msg.att_arr[1].1.payload {
+            subatt_arr.remove(0);
+        }
+        let err = validate_msg(&prot_desc_map, &raw_msg).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "MissMandatoryAtt in command 0x12 at attribute 0x7f/0x30, expected Uint8, got no attribute"
+        );
+        let mut valid_cmd = TEST_VALID_CMD.clone();
+        if let LcsfValidAttPayload::SubattArr(subatt_arr) = &mut valid_cmd.att_arr[1].payload {
+            subatt_arr[0].payload = LcsfValidAttPayload::Absent;
+        }
+        assert_eq!(encode_valid(0xab, cmd_desc, &valid_cmd), Err(err));
+        // Test too many attributes
+        let mut raw_msg = TEST_RAW_MSG.clone();
+        if let LcsfRawAttPayload::SubattArr(subatt_arr) = &mut raw_msg.att_arr[1].1.payload {
+            subatt_arr.push(subatt_arr[0].clone());
+        }
+        let err = validate_msg(&prot_desc_map, &raw_msg).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "TooManyAtt in command 0x12 at attribute 0x7f, got 3 attributes"
+        );
+        // Test wrong attribute number
+        let mut valid_cmd = TEST_VALID_CMD.clone();
+        valid_cmd.att_arr.pop();
+        let err = encode_valid(0xab, cmd_desc, &valid_cmd).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "WrongAttNb in command 0x12, got 2 attributes"
+        );
+    }
+
+    #[test]
+    fn test_validate_msg_all() {
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &*TEST_PROT_DESC)]);
+        // Break several attributes at different levels
+        let mut raw_msg = TEST_RAW_MSG.clone();
+        raw_msg.att_arr[0].1.has_subatt = true;
+        if let LcsfRawAttPayload::SubattArr(subatt_arr) = &mut raw_msg.att_arr[1].1.payload {
+            subatt_arr.remove(0);
+            subatt_arr.push((0x33, subatt_arr[0].1.clone()));
+        }
+        raw_msg.att_arr.push(raw_msg.att_arr[2].clone());
+        // Test fast mode stops at the first error
+        let err = validate_msg(&prot_desc_map, &raw_msg).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::TooManyAtt);
+        // Test collect mode
+        let err_arr = validate_msg_all(&prot_desc_map, &raw_msg).unwrap_err();
+        let err_list: Vec<(LcsfValidateErrorEnum, Vec<u16>)> = err_arr
+            .into_iter()
+            .map(|err| (err.kind, err.att_path))
+            .collect();
+        assert_eq!(
+            err_list,
+            vec![
+                (LcsfValidateErrorEnum::TooManyAtt, vec![]),
+                (LcsfValidateErrorEnum::DuplicateAttId, vec![0x40]),
+                (LcsfValidateErrorEnum::WrongAttDataType, vec![0x55]),
+                (LcsfValidateErrorEnum::MissMandatoryAtt, vec![0x7f, 0x30]),
+                (LcsfValidateErrorEnum::UnknownAttId, vec![0x7f, 0x33]),
+            ]
+        );
+        // Test command errors and valid message
+        raw_msg.cmd_id = 0x13;
+        let err_arr = validate_msg_all(&prot_desc_map, &raw_msg).unwrap_err();
+        assert_eq!(err_arr.len(), 1);
+        assert_eq!(err_arr[0].kind, LcsfValidateErrorEnum::UnknownCmdId);
+        assert_eq!(
+            validate_msg_all(&prot_desc_map, &TEST_RAW_MSG),
+            Ok((TEST_VALID_CMD.clone(), 0xab, LcsfValidInfo::default()))
+        );
+        // Test a faulty conditionally required attribute is only reported once
+        let cond_prot_desc = LcsfProtDesc {
+            cmd_desc_arr: vec![(
+                0x01,
+                LcsfCmdDesc {
+                    att_desc_arr: vec![
+                        (
+                            0x01,
+                            LcsfAttDesc {
+                                data_type: LcsfDataType::Uint8,
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            0x02,
+                            LcsfAttDesc {
+                                is_optional: true,
+                                data_type: LcsfDataType::Uint16,
+                                constraints: LcsfAttConstraints {
+                                    max_value: Some(10.0),
+                                    ..Default::default()
+                                },
+                                required_if: Some(LcsfAttCond::Present(0x01)),
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &cond_prot_desc)]);
+        for (data, kind) in [
+            (
+                vec![0x01, 0x02, 0x03],
+                LcsfValidateErrorEnum::WrongAttDataType,
+            ),
+            (vec![0x20], LcsfValidateErrorEnum::AttOutOfRange),
+        ] {
+            let raw_msg = LcsfRawMsg {
+                prot_id: 0xab,
+                cmd_id: 0x01,
+                att_nb: 2,
+                att_arr: vec![
+                    (
+                        0x01,
+                        LcsfRawAtt {
+                            has_subatt: false,
+                            payload_size: 1,
+                            payload: LcsfRawAttPayload::Data(vec![0x01]),
+                        },
+                    ),
+                    (
+                        0x02,
+                        LcsfRawAtt {
+                            has_subatt: false,
+                            payload_size: data.len() as u16,
+                            payload: LcsfRawAttPayload::Data(data),
+                        },
+                    ),
+                ],
+            };
+            let err_arr = validate_msg_all(&prot_desc_map, &raw_msg).unwrap_err();
+            let err_list: Vec<(LcsfValidateErrorEnum, Vec<u16>)> = err_arr
+                .into_iter()
+                .map(|err| (err.kind, err.att_path))
+                .collect();
+            assert_eq!(err_list, vec![(kind, vec![0x02])]);
+        }
+    }
+
+    #[test]
+    fn test_string_att() {
+        let mut prot_desc = LcsfProtDesc {
+            cmd_desc_arr: TEST_PROT_DESC.cmd_desc_arr.clone(),
+            ..Default::default()
+        };
+        let cmd_desc = &prot_desc.cmd_desc_arr[0].1;
+        // Test check function
+        assert!(check_string(b"Text\0", true));
+        assert!(check_string(b"\0", true));
+        assert!(!check_string(b"", false));
+        assert!(!check_string(b"Text", false));
+        assert!(!check_string(b"Te\0xt\0", false));
+        assert!(check_string(b"\xffText\0", false));
+        assert!(!check_string(b"\xffText\0", true));
+        // Build messages with a given string payload
+        let string_msg = |data: &[u8]| {
+            let mut raw_msg = TEST_RAW_MSG.clone();
+            if let LcsfRawAttPayload::SubattArr(subatt_arr) = &mut raw_msg.att_arr[1].1.payload
+                && let LcsfRawAttPayload::SubattArr(subsubatt_arr) = &mut subatt_arr[1].1.payload
+            {
+                subsubatt_arr[0].1.payload_size = data.len() as u16;
+                subsubatt_arr[0].1.payload = LcsfRawAttPayload::Data(data.to_vec());
+            }
+            raw_msg
+        };
+        let string_cmd = |data: &[u8]| {
+            let mut valid_cmd = TEST_VALID_CMD.clone();
+            if let LcsfValidAttPayload::SubattArr(subatt_arr) = &mut valid_cmd.att_arr[1].payload
+                && let LcsfValidAttPayload::SubattArr(subsubatt_arr) = &mut subatt_arr[1].payload
+            {
+                subsubatt_arr[0].payload = LcsfValidAttPayload::Data(data.to_vec());
+            }
+            valid_cmd
+        };
+        // Test terminator rules, in both directions
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+        for data in [&b"Text"[..], b"Te\0xt\0"] {
+            let err = validate_msg(&prot_desc_map, &string_msg(data)).unwrap_err();
+            assert_eq!(err.kind, LcsfValidateErrorEnum::WrongAttDataType);
+            assert_eq!(err.att_path, vec![0x7f, 0x31, 0x32]);
+            assert_eq!(encode_valid(0xab, cmd_desc, &string_cmd(data)), Err(err));
+        }
+        // Test UTF-8 is not checked by default
+        let (valid_cmd, _, _) = validate_msg(&prot_desc_map, &string_msg(b"\xff\0")).unwrap();
+        assert_eq!(valid_cmd, string_cmd(b"\xff\0"));
+        // Test strict UTF-8
+        prot_desc.policy.check_utf8 = true;
+        let prot_desc_map: HashMap<u16, &LcsfProtDesc> = HashMap::from([(0xab, &prot_desc)]);
+        let err = validate_msg(&prot_desc_map, &string_msg(b"\xff\0")).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongAttDataType);
+        assert!(validate_msg(&prot_desc_map, &string_msg("Texte é\0".as_bytes())).is_ok());
+    }
+
+    #[test]
+    fn test_array_att() {
+        let i16_type = LcsfDataType::Array(LcsfElemType::Int16);
+        let f32_type = LcsfDataType::Array(LcsfElemType::Float32);
+        // Test element decoding
+        assert_eq!(LcsfElemType::Int16.decode_int(&[0xfe, 0xff]), Some(-2));
+        assert_eq!(LcsfElemType::Uint16.decode_int(&[0xfe, 0xff]), Some(0xfffe));
+        assert_eq!(
+            LcsfElemType::Float32.decode_int(&1.5_f32.to_le_bytes()),
+            None
+        );
+        assert_eq!(
+            LcsfElemType::Float32.decode_f64(&1.5_f32.to_le_bytes()),
+            Some(1.5)
+        );
+        // Test wrong element sizes are rejected
+        assert_eq!(LcsfElemType::Int16.decode_int(&[]), None);
+        assert_eq!(LcsfElemType::Uint64.decode_int(&[0x00; 9]), None);
+        assert_eq!(LcsfElemType::Uint16.decode_int(&[0x01]), None);
+        assert_eq!(LcsfElemType::Float64.decode_f64(&[0x00; 4]), None);
+        assert_eq!(LcsfElemType::Int8.decode_f64(&[]), None);
+        // Test size is a multiple of the element size
+        assert!(validate_data_type(6, i16_type));
+        assert!(!validate_data_type(5, i16_type));
+        assert!(!validate_data_type(0, i16_type));
+        assert!(check_data_type(f32_type, &[0x00; 8]));
+        assert!(!check_data_type(f32_type, &[0x00; 6]));
+        // Test length constraints count elements, value constraints apply to each element
+        let constraints = LcsfAttConstraints {
+            min_value: Some(-2.0),
+            max_value: Some(300.0),
+            max_len: Some(2),
+            ..Default::default()
+        };
+        assert!(check_constraints(
+            &[0xfe, 0xff, 0x2c, 0x01],
+            i16_type,
+            &constraints
+        ));
+        assert!(!check_constraints(
+            &[0xfd, 0xff, 0x2c, 0x01],
+            i16_type,
+            &constraints
+        ));
+        assert!(!check_constraints(&[0x00; 6], i16_type, &constraints));
+        let data: Vec<u8> = [1.5_f32, 300.5]
+            .iter()
+            .flat_map(|elem| elem.to_le_bytes())
+            .collect();
+        assert!(!check_constraints(&data, f32_type, &constraints));
+        // Test display
+        let cmd_desc = LcsfCmdDesc {
+            name: "CMD".into(),
+            att_desc_arr: vec![
+                (
+                    0x01,
+                    LcsfAttDesc {
+                        data_type: i16_type,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    0x02,
+                    LcsfAttDesc {
+                        data_type: f32_type,
+                        ..Default::default()
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+        let valid_cmd = LcsfValidCmd {
+            cmd_id: 0x12,
+            att_arr: vec![
+                LcsfValidAtt {
+                    payload: LcsfValidAttPayload::Data(vec![0xfe, 0xff, 0x2c, 0x01]),
+                },
+                LcsfValidAtt {
+                    payload: LcsfValidAttPayload::Data(data),
+                },
+            ],
+        };
+        assert_eq!(
+            valid_cmd.display(&cmd_desc).to_string(),
+            "CMD { 0x01: [-2, 300], 0x02: [1.5, 300.5] }"
+        );
+    }
+
     #[test]
     fn test_fill_att_info() {
         // Test data
@@ -666,54 +5018,54 @@ mod tests {
         };
         // Test error
         match fill_att_info(LcsfDataType::Subattributes, &valid_att_err) {
-            Some(_) => panic!("fill_att_info should fail"),
-            None => {}
+            Ok(_) => panic!("fill_att_info should fail"),
+            Err(_) => {}
         }
         valid_att_err.payload = LcsfValidAttPayload::Data(Vec::new());
         match fill_att_info(LcsfDataType::Uint8, &valid_att_err) {
-            Some(_) => panic!("fill_att_info should fail"),
-            None => {}
+            Ok(_) => panic!("fill_att_info should fail"),
+            Err(_) => {}
         }
         match fill_att_info(LcsfDataType::Uint16, &valid_att_err) {
-            Some(_) => panic!("fill_att_info should fail"),
-            None => {}
+            Ok(_) => panic!("fill_att_info should fail"),
+            Err(_) => {}
         }
         match fill_att_info(LcsfDataType::Uint32, &valid_att_err) {
-            Some(_) => panic!("fill_att_info should fail"),
-            None => {}
+            Ok(_) => panic!("fill_att_info should fail"),
+            Err(_) => {}
         }
         match fill_att_info(LcsfDataType::ByteArray, &valid_att_err) {
-            Some(_) => panic!("fill_att_info should fail"),
-            None => {}
+            Ok(_) => panic!("fill_att_info should fail"),
+            Err(_) => {}
         }
         match fill_att_info(LcsfDataType::String, &valid_att_err) {
-            Some(_) => panic!("fill_att_info should fail"),
-            None => {}
+            Ok(_) => panic!("fill_att_info should fail"),
+            Err(_) => {}
         }
         // Test valid
         match fill_att_info(LcsfDataType::Uint8, &valid_att_u8) {
-            None => panic!("fill_att_info should not fail"),
-            Some(raw_att) => assert_eq!(raw_att, raw_att_u8),
+            Err(_) => panic!("fill_att_info should not fail"),
+            Ok(raw_att) => assert_eq!(raw_att, raw_att_u8),
         }
         match fill_att_info(LcsfDataType::Uint16, &valid_att_u16) {
-            None => panic!("fill_att_info should not fail"),
-            Some(raw_att) => assert_eq!(raw_att, raw_att_u16),
+            Err(_) => panic!("fill_att_info should not fail"),
+            Ok(raw_att) => assert_eq!(raw_att, raw_att_u16),
         }
         match fill_att_info(LcsfDataType::Uint32, &valid_att_u32) {
-            None => panic!("fill_att_info should not fail"),
-            Some(raw_att) => assert_eq!(raw_att, raw_att_u32),
+            Err(_) => panic!("fill_att_info should not fail"),
+            Ok(raw_att) => assert_eq!(raw_att, raw_att_u32),
         }
         match fill_att_info(LcsfDataType::ByteArray, &valid_att_arr) {
-            None => panic!("fill_att_info should not fail"),
-            Some(raw_att) => assert_eq!(raw_att, raw_att_arr),
+            Err(_) => panic!("fill_att_info should not fail"),
+            Ok(raw_att) => assert_eq!(raw_att, raw_att_arr),
         }
         match fill_att_info(LcsfDataType::String, &valid_att_arr) {
-            None => panic!("fill_att_info should not fail"),
-            Some(raw_att) => assert_eq!(raw_att, raw_att_arr),
+            Err(_) => panic!("fill_att_info should not fail"),
+            Ok(raw_att) => assert_eq!(raw_att, raw_att_arr),
         }
         match fill_att_info(LcsfDataType::Subattributes, &valid_att_sub) {
-            None => panic!("fill_att_info should not fail"),
-            Some(raw_att) => assert_eq!(raw_att, raw_att_sub),
+            Err(_) => panic!("fill_att_info should not fail"),
+            Ok(raw_att) => assert_eq!(raw_att, raw_att_sub),
         }
     }
 
@@ -729,13 +5081,16 @@ mod tests {
                     is_optional: false,
                     data_type: LcsfDataType::Uint32,
                     subatt_desc_arr: Vec::new(),
+                    ..Default::default()
                 },
             )],
+            ..Default::default()
         };
         let mut test_data_att_desc = LcsfAttDesc {
             is_optional: false,
             data_type: LcsfDataType::Uint32,
             subatt_desc_arr: Vec::new(),
+            ..Default::default()
         };
         let empty_valid_att = LcsfValidAtt {
             payload: LcsfValidAttPayload::SubattArr(Vec::new()),
@@ -743,42 +5098,71 @@ mod tests {
         let valid_data_att = LcsfValidAtt {
             payload: LcsfValidAttPayload::Data(Vec::new()),
         };
-        let empty_raw_att = LcsfRawAtt {
-            has_subatt: false,
-            payload_size: 0,
-            payload: LcsfRawAttPayload::Data(Vec::new()),
+        let absent_valid_att = LcsfValidAtt {
+            payload: LcsfValidAttPayload::Absent,
         };
         // Test error
-        match fill_att_rec(&test_att_desc, &empty_valid_att) {
-            Some(_) => panic!("fill_att_rec should fail"),
-            None => {}
+        match fill_att_rec(&LcsfEncodeCtx::default(), &test_att_desc, &empty_valid_att) {
+            Ok(_) => panic!("fill_att_rec should fail"),
+            Err(_) => {}
         }
         test_att_desc.subatt_desc_arr = Vec::new();
-        match fill_att_rec(&test_att_desc, &empty_valid_att) {
-            Some(_) => panic!("fill_att_rec should fail"),
-            None => {}
+        match fill_att_rec(&LcsfEncodeCtx::default(), &test_att_desc, &empty_valid_att) {
+            Ok(_) => panic!("fill_att_rec should fail"),
+            Err(_) => {}
         }
-        match fill_att_rec(&test_data_att_desc, &valid_data_att) {
-            Some(_) => panic!("fill_att_rec should fail"),
-            None => {}
+        match fill_att_rec(
+            &LcsfEncodeCtx::default(),
+            &test_data_att_desc,
+            &valid_data_att,
+        ) {
+            Ok(_) => panic!("fill_att_rec should fail"),
+            Err(_) => {}
         }
-        // Test valid
-        test_data_att_desc.is_optional = true;
-        match fill_att_rec(&test_data_att_desc, &valid_data_att) {
-            Some(raw_att) => assert_eq!(raw_att, empty_raw_att),
-            None => panic!("fill_att_rec should not fail"),
+        assert!(
+            fill_att_rec(
+                &LcsfEncodeCtx::default(),
+                &test_data_att_desc,
+                &absent_valid_att
+            )
+            .is_err()
+        );
+        match fill_att_entries(
+            &LcsfEncodeCtx::default(),
+            0x0a,
+            &test_data_att_desc,
+            &absent_valid_att,
+        ) {
+            Ok(_) => panic!("fill_att_entries should fail"),
+            Err(err) => assert_eq!(err.kind, LcsfValidateErrorEnum::MissMandatoryAtt),
         }
-        test_att_desc.subatt_desc_arr = Vec::new();
+        // Empty payloads are not absent attributes
+        test_data_att_desc.is_optional = true;
+        assert!(
+            fill_att_rec(
+                &LcsfEncodeCtx::default(),
+                &test_data_att_desc,
+                &valid_data_att
+            )
+            .is_err()
+        );
         test_att_desc.is_optional = true;
-        match fill_att_rec(&test_att_desc, &empty_valid_att) {
-            Some(raw_att) => assert_eq!(raw_att, empty_raw_att),
-            None => panic!("fill_att_rec should not fail"),
-        }
+        assert!(fill_att_rec(&LcsfEncodeCtx::default(), &test_att_desc, &empty_valid_att).is_err());
+        // Test valid
+        assert_eq!(
+            fill_att_entries(
+                &LcsfEncodeCtx::default(),
+                0x0a,
+                &test_data_att_desc,
+                &absent_valid_att
+            ),
+            Ok(Vec::new())
+        );
         for (idx, valid_att) in TEST_VALID_CMD.att_arr.iter().enumerate() {
             let att_desc = &TEST_PROT_DESC.cmd_desc_arr[0].1.att_desc_arr[idx].1;
-            match fill_att_rec(att_desc, valid_att) {
-                None => panic!("fill_att_rec should not fail"),
-                Some(raw_att) => assert_eq!(raw_att, TEST_RAW_MSG.att_arr[idx].1),
+            match fill_att_rec(&LcsfEncodeCtx::default(), att_desc, valid_att) {
+                Err(_) => panic!("fill_att_rec should not fail"),
+                Ok(raw_att) => assert_eq!(raw_att, TEST_RAW_MSG.att_arr[idx].1),
             }
         }
     }
@@ -792,13 +5176,13 @@ mod tests {
         };
         // Test error
         match encode_valid(0xab, &TEST_PROT_DESC.cmd_desc_arr[0].1, &bad_cmd) {
-            Some(_) => panic!("fill_att_rec should fail"),
-            None => {}
+            Ok(_) => panic!("fill_att_rec should fail"),
+            Err(_) => {}
         }
         // Test valid
         match encode_valid(0xab, &TEST_PROT_DESC.cmd_desc_arr[0].1, &TEST_VALID_CMD) {
-            None => panic!("encode_valid should not fail"),
-            Some(raw_msg) => assert_eq!(raw_msg, *TEST_RAW_MSG),
+            Err(_) => panic!("encode_valid should not fail"),
+            Ok(raw_msg) => assert_eq!(raw_msg, *TEST_RAW_MSG),
         }
     }
 
@@ -952,6 +5336,7 @@ mod tests {
                                 is_optional: false,
                                 data_type: LcsfDataType::ByteArray,
                                 subatt_desc_arr: Vec::new(),
+                                ..Default::default()
                             }
                         ),
                         (
@@ -966,6 +5351,7 @@ mod tests {
                                             is_optional: false,
                                             data_type: LcsfDataType::Uint8,
                                             subatt_desc_arr: Vec::new(),
+                                            ..Default::default()
                                         }
                                     ),
                                     (
@@ -979,11 +5365,14 @@ mod tests {
                                                     is_optional: true,
                                                     data_type: LcsfDataType::String,
                                                     subatt_desc_arr: Vec::new(),
+                                                    ..Default::default()
                                                 }
                                             ),],
+                                            ..Default::default()
                                         }
                                     ),
                                 ],
+                                ..Default::default()
                             }
                         ),
                         (
@@ -992,11 +5381,14 @@ mod tests {
                                 is_optional: true,
                                 data_type: LcsfDataType::Uint16,
                                 subatt_desc_arr: Vec::new(),
+                                ..Default::default()
                             }
                         ),
                     ],
+                    ..Default::default()
                 }
             ),],
+            ..Default::default()
         };
         static ref TEST_VALID_CMD: LcsfValidCmd = LcsfValidCmd {
             cmd_id: 0x12,
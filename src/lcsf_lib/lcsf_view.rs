@@ -0,0 +1,1436 @@
+//! Descriptor-bound read-only view of a LcsfValidCmd, addressing attributes by id or by path,
+//! and typed accessors and constructors of LcsfValidAtt
+//!
+//! author: Jean-Roland Gosse
+//!
+//! This file is part of LCSF Stack Rust.
+//! Spec details at <https://jean-roland.github.io/LCSF_Doc/>
+//! You should have received a copy of the GNU Lesser General Public License
+//! along with this program. If not, see <https://www.gnu.org/licenses/>
+
+use std::borrow::Cow;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use crate::lcsf_lib::lcsf_validator;
+use lcsf_validator::LcsfAttDesc;
+use lcsf_validator::LcsfAttScale;
+use lcsf_validator::LcsfCmdDesc;
+use lcsf_validator::LcsfDataType;
+use lcsf_validator::LcsfElemType;
+use lcsf_validator::LcsfValidAtt;
+use lcsf_validator::LcsfValidAttPayload;
+use lcsf_validator::LcsfValidCmd;
+
+/// Typed attribute value enum
+#[derive(Debug, PartialEq, Clone)]
+pub enum LcsfValue<'a> {
+    /// Uint8, Uint16, Uint32 or Uint64 value
+    Uint(u64),
+    /// Signed Array element value
+    Int(i64),
+    /// Scaled integer physical value, with its unit
+    Scaled(f64, &'a str),
+    /// Float32 or Float64 value
+    Float(f64),
+    /// ByteArray value
+    ByteArray(&'a [u8]),
+    /// String value, without its terminating NUL and with invalid UTF-8 replaced
+    String(Cow<'a, str>),
+    /// Array elements value
+    Array(Vec<LcsfValue<'a>>),
+    /// Timestamp value
+    Timestamp(SystemTime),
+    /// Duration value
+    Duration(Duration),
+    /// Nested command value, with its protocol id
+    Message(u16, &'a LcsfValidCmd),
+}
+
+/// Typed value access error enum
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum LcsfValueErrorEnum {
+    /// Attribute has no data payload: absent, sub-attributes or repeated
+    NoData,
+    /// Requested type doesn't match the attribute data type
+    WrongDataType,
+    /// Payload size doesn't match the attribute data type
+    WrongDataSize,
+    /// Value doesn't fit in the requested or described type
+    OutOfRange,
+    /// String isn't NUL terminated UTF-8, or contains an inner NUL
+    InvalidString,
+}
+
+/// Packed array element trait, implemented for the primitive types of [LcsfElemType]
+pub trait LcsfArrayElem: Copy {
+    /// Matching array element type
+    const ELEM_TYPE: LcsfElemType;
+
+    /// Decode an element from its little endian bytes
+    ///
+    /// elem: element reference, of the element size
+    fn from_le(elem: &[u8]) -> Self;
+
+    /// Append the element little endian bytes to a buffer
+    ///
+    /// data: buffer to append to
+    fn extend_le(self, data: &mut Vec<u8>);
+}
+
+macro_rules! impl_array_elem {
+    ($($elem:ty => $elem_type:ident),*) => {
+        $(
+            impl LcsfArrayElem for $elem {
+                const ELEM_TYPE: LcsfElemType = LcsfElemType::$elem_type;
+
+                fn from_le(elem: &[u8]) -> Self {
+                    <$elem>::from_le_bytes(elem.try_into().unwrap())
+                }
+
+                fn extend_le(self, data: &mut Vec<u8>) {
+                    data.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_array_elem!(
+    u8 => Uint8, u16 => Uint16, u32 => Uint32, u64 => Uint64,
+    i8 => Int8, i16 => Int16, i32 => Int32, i64 => Int64,
+    f32 => Float32, f64 => Float64
+);
+
+/// Return the typed value of an array element, None for a wrong element size
+///
+/// elem_type: array element type
+///
+/// elem: element reference, of the element size
+fn get_elem_value<'a>(elem_type: LcsfElemType, elem: &[u8]) -> Option<LcsfValue<'a>> {
+    match elem_type {
+        LcsfElemType::Uint8
+        | LcsfElemType::Uint16
+        | LcsfElemType::Uint32
+        | LcsfElemType::Uint64 => Some(LcsfValue::Uint(elem_type.decode_int(elem)? as u64)),
+        LcsfElemType::Int8 | LcsfElemType::Int16 | LcsfElemType::Int32 | LcsfElemType::Int64 => {
+            Some(LcsfValue::Int(elem_type.decode_int(elem)? as i64))
+        }
+        LcsfElemType::Float32 | LcsfElemType::Float64 => {
+            elem_type.decode_f64(elem).map(LcsfValue::Float)
+        }
+    }
+}
+
+/// Return the largest raw value of an integer, Timestamp or Duration data type, None for other data types
+///
+/// data_type: attribute data type from descriptor
+fn get_uint_max(data_type: LcsfDataType) -> Option<u64> {
+    match data_type {
+        LcsfDataType::Uint8 => Some(u8::MAX as u64),
+        LcsfDataType::Uint16 => Some(u16::MAX as u64),
+        LcsfDataType::Uint32 => Some(u32::MAX as u64),
+        LcsfDataType::Uint64 | LcsfDataType::Timestamp { .. } | LcsfDataType::Duration(_) => {
+            Some(u64::MAX)
+        }
+        _ => None,
+    }
+}
+
+#[allow(dead_code)]
+impl LcsfValidAtt {
+    /// Return the data payload, checked against its data type
+    ///
+    /// data_type: attribute data type from descriptor
+    fn get_data(&self, data_type: LcsfDataType) -> Result<&[u8], LcsfValueErrorEnum> {
+        let LcsfValidAttPayload::Data(data) = &self.payload else {
+            return Err(LcsfValueErrorEnum::NoData);
+        };
+        if !lcsf_validator::check_data_type(data_type, data) {
+            return Err(LcsfValueErrorEnum::WrongDataSize);
+        }
+        Ok(data)
+    }
+
+    /// Return the value of an integer attribute
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_uint(&self, data_type: LcsfDataType) -> Result<u64, LcsfValueErrorEnum> {
+        if get_uint_max(data_type).is_none() {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        }
+        Ok(lcsf_validator::vle_decode(self.get_data(data_type)?))
+    }
+
+    /// Return the value of an integer attribute as u8
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_u8(&self, data_type: LcsfDataType) -> Result<u8, LcsfValueErrorEnum> {
+        u8::try_from(self.as_uint(data_type)?).map_err(|_| LcsfValueErrorEnum::OutOfRange)
+    }
+
+    /// Return the value of an integer attribute as u16
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_u16(&self, data_type: LcsfDataType) -> Result<u16, LcsfValueErrorEnum> {
+        u16::try_from(self.as_uint(data_type)?).map_err(|_| LcsfValueErrorEnum::OutOfRange)
+    }
+
+    /// Return the value of an integer attribute as u32
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_u32(&self, data_type: LcsfDataType) -> Result<u32, LcsfValueErrorEnum> {
+        u32::try_from(self.as_uint(data_type)?).map_err(|_| LcsfValueErrorEnum::OutOfRange)
+    }
+
+    /// Return the value of an integer attribute as u64
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_u64(&self, data_type: LcsfDataType) -> Result<u64, LcsfValueErrorEnum> {
+        self.as_uint(data_type)
+    }
+
+    /// Return the value of an integer attribute as i32
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_i32(&self, data_type: LcsfDataType) -> Result<i32, LcsfValueErrorEnum> {
+        i32::try_from(self.as_uint(data_type)?).map_err(|_| LcsfValueErrorEnum::OutOfRange)
+    }
+
+    /// Return the value of an integer attribute as i64
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_i64(&self, data_type: LcsfDataType) -> Result<i64, LcsfValueErrorEnum> {
+        i64::try_from(self.as_uint(data_type)?).map_err(|_| LcsfValueErrorEnum::OutOfRange)
+    }
+
+    /// Return the physical value of a scaled integer attribute
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// scale: attribute scale from descriptor
+    pub fn as_scaled(
+        &self,
+        data_type: LcsfDataType,
+        scale: &LcsfAttScale,
+    ) -> Result<f64, LcsfValueErrorEnum> {
+        Ok(scale.to_physical(self.as_uint(data_type)?))
+    }
+
+    /// Return the point in time of a Timestamp attribute
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_timestamp(&self, data_type: LcsfDataType) -> Result<SystemTime, LcsfValueErrorEnum> {
+        let LcsfDataType::Timestamp { res, epoch } = data_type else {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        };
+        res.to_time(epoch, self.as_uint(data_type)?)
+            .ok_or(LcsfValueErrorEnum::OutOfRange)
+    }
+
+    /// Return the time span of a Duration attribute
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_duration(&self, data_type: LcsfDataType) -> Result<Duration, LcsfValueErrorEnum> {
+        let LcsfDataType::Duration(res) = data_type else {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        };
+        Ok(res.to_duration(self.as_uint(data_type)?))
+    }
+
+    /// Return the protocol id and validated nested command of a Message attribute
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_message(
+        &self,
+        data_type: LcsfDataType,
+    ) -> Result<(u16, &LcsfValidCmd), LcsfValueErrorEnum> {
+        if !matches!(data_type, LcsfDataType::Message(_)) {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        }
+        match &self.payload {
+            LcsfValidAttPayload::Message(prot_id, nested_cmd) => Ok((*prot_id, nested_cmd)),
+            _ => Err(LcsfValueErrorEnum::NoData),
+        }
+    }
+
+    /// Return the value of a Float32 attribute
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_f32(&self, data_type: LcsfDataType) -> Result<f32, LcsfValueErrorEnum> {
+        if data_type != LcsfDataType::Float32 {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        }
+        let data = self.get_data(data_type)?;
+        Ok(f32::from_le_bytes(data.try_into().unwrap()))
+    }
+
+    /// Return the value of a Float32 or Float64 attribute
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_f64(&self, data_type: LcsfDataType) -> Result<f64, LcsfValueErrorEnum> {
+        match data_type {
+            LcsfDataType::Float32 => Ok(self.as_f32(data_type)? as f64),
+            LcsfDataType::Float64 => {
+                let data = self.get_data(data_type)?;
+                Ok(f64::from_le_bytes(data.try_into().unwrap()))
+            }
+            _ => Err(LcsfValueErrorEnum::WrongDataType),
+        }
+    }
+
+    /// Return the text of a String attribute, without its terminating NUL
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_str(&self, data_type: LcsfDataType) -> Result<&str, LcsfValueErrorEnum> {
+        if data_type != LcsfDataType::String {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        }
+        let data = self.get_data(data_type)?;
+        let text = data
+            .strip_suffix(&[0])
+            .ok_or(LcsfValueErrorEnum::InvalidString)?;
+        if text.contains(&0) {
+            return Err(LcsfValueErrorEnum::InvalidString);
+        }
+        std::str::from_utf8(text).map_err(|_| LcsfValueErrorEnum::InvalidString)
+    }
+
+    /// Return the payload of a ByteArray or String attribute, or the packed elements of an Array attribute
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_bytes(&self, data_type: LcsfDataType) -> Result<&[u8], LcsfValueErrorEnum> {
+        match data_type {
+            LcsfDataType::ByteArray | LcsfDataType::String | LcsfDataType::Array(_) => {
+                self.get_data(data_type)
+            }
+            _ => Err(LcsfValueErrorEnum::WrongDataType),
+        }
+    }
+
+    /// Return the elements of an Array attribute, the element type must match the descriptor
+    ///
+    /// data_type: attribute data type from descriptor
+    pub fn as_array<T: LcsfArrayElem>(
+        &self,
+        data_type: LcsfDataType,
+    ) -> Result<Vec<T>, LcsfValueErrorEnum> {
+        if data_type != LcsfDataType::Array(T::ELEM_TYPE) {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        }
+        let data = self.get_data(data_type)?;
+        Ok(data
+            .chunks_exact(T::ELEM_TYPE.size())
+            .map(T::from_le)
+            .collect())
+    }
+
+    /// Create an integer attribute, VLE encoded
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// value: attribute value
+    pub fn from_uint(data_type: LcsfDataType, value: u64) -> Result<Self, LcsfValueErrorEnum> {
+        let max = get_uint_max(data_type).ok_or(LcsfValueErrorEnum::WrongDataType)?;
+        if value > max {
+            return Err(LcsfValueErrorEnum::OutOfRange);
+        }
+        Ok(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(lcsf_validator::vle_encode(value)),
+        })
+    }
+
+    /// Create an integer attribute from a u8
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// value: attribute value
+    pub fn from_u8(data_type: LcsfDataType, value: u8) -> Result<Self, LcsfValueErrorEnum> {
+        Self::from_uint(data_type, value as u64)
+    }
+
+    /// Create an integer attribute from a u16
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// value: attribute value
+    pub fn from_u16(data_type: LcsfDataType, value: u16) -> Result<Self, LcsfValueErrorEnum> {
+        Self::from_uint(data_type, value as u64)
+    }
+
+    /// Create an integer attribute from a u32
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// value: attribute value
+    pub fn from_u32(data_type: LcsfDataType, value: u32) -> Result<Self, LcsfValueErrorEnum> {
+        Self::from_uint(data_type, value as u64)
+    }
+
+    /// Create an integer attribute from a u64
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// value: attribute value
+    pub fn from_u64(data_type: LcsfDataType, value: u64) -> Result<Self, LcsfValueErrorEnum> {
+        Self::from_uint(data_type, value)
+    }
+
+    /// Create an integer attribute from an i32, negative values are out of range
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// value: attribute value
+    pub fn from_i32(data_type: LcsfDataType, value: i32) -> Result<Self, LcsfValueErrorEnum> {
+        Self::from_i64(data_type, value as i64)
+    }
+
+    /// Create an integer attribute from an i64, negative values are out of range
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// value: attribute value
+    pub fn from_i64(data_type: LcsfDataType, value: i64) -> Result<Self, LcsfValueErrorEnum> {
+        let value = u64::try_from(value).map_err(|_| LcsfValueErrorEnum::OutOfRange)?;
+        Self::from_uint(data_type, value)
+    }
+
+    /// Create a scaled integer attribute from its physical value, rounded to the closest raw value
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// scale: attribute scale from descriptor
+    ///
+    /// value: attribute physical value
+    pub fn from_scaled(
+        data_type: LcsfDataType,
+        scale: &LcsfAttScale,
+        value: f64,
+    ) -> Result<Self, LcsfValueErrorEnum> {
+        let raw = scale.to_raw(value).ok_or(LcsfValueErrorEnum::OutOfRange)?;
+        Self::from_uint(data_type, raw)
+    }
+
+    /// Create a Timestamp attribute, truncated to its resolution
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// time: attribute point in time, not before epoch
+    pub fn from_timestamp(
+        data_type: LcsfDataType,
+        time: SystemTime,
+    ) -> Result<Self, LcsfValueErrorEnum> {
+        let LcsfDataType::Timestamp { res, epoch } = data_type else {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        };
+        let raw = res
+            .to_raw_time(epoch, time)
+            .ok_or(LcsfValueErrorEnum::OutOfRange)?;
+        Self::from_uint(data_type, raw)
+    }
+
+    /// Create a Duration attribute, truncated to its resolution
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// duration: attribute time span
+    pub fn from_duration(
+        data_type: LcsfDataType,
+        duration: Duration,
+    ) -> Result<Self, LcsfValueErrorEnum> {
+        let LcsfDataType::Duration(res) = data_type else {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        };
+        let raw = res.to_raw(duration).ok_or(LcsfValueErrorEnum::OutOfRange)?;
+        Self::from_uint(data_type, raw)
+    }
+
+    /// Create a Message attribute from a nested command, encoded when sent
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// prot_id: nested command protocol id
+    ///
+    /// nested_cmd: nested command
+    pub fn from_message(
+        data_type: LcsfDataType,
+        prot_id: u16,
+        nested_cmd: LcsfValidCmd,
+    ) -> Result<Self, LcsfValueErrorEnum> {
+        if !matches!(data_type, LcsfDataType::Message(_)) {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        }
+        Ok(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Message(prot_id, Box::new(nested_cmd)),
+        })
+    }
+
+    /// Create a Float32 or Float64 attribute from an f32
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// value: attribute value
+    pub fn from_f32(data_type: LcsfDataType, value: f32) -> Result<Self, LcsfValueErrorEnum> {
+        match data_type {
+            LcsfDataType::Float32 => Ok(LcsfValidAtt {
+                payload: LcsfValidAttPayload::Data(value.to_le_bytes().to_vec()),
+            }),
+            LcsfDataType::Float64 => Self::from_f64(data_type, value as f64),
+            _ => Err(LcsfValueErrorEnum::WrongDataType),
+        }
+    }
+
+    /// Create a Float32 or Float64 attribute from an f64, it must be exactly representable as Float32
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// value: attribute value
+    pub fn from_f64(data_type: LcsfDataType, value: f64) -> Result<Self, LcsfValueErrorEnum> {
+        match data_type {
+            LcsfDataType::Float32 => {
+                if !value.is_nan() && value as f32 as f64 != value {
+                    return Err(LcsfValueErrorEnum::OutOfRange);
+                }
+                Self::from_f32(data_type, value as f32)
+            }
+            LcsfDataType::Float64 => Ok(LcsfValidAtt {
+                payload: LcsfValidAttPayload::Data(value.to_le_bytes().to_vec()),
+            }),
+            _ => Err(LcsfValueErrorEnum::WrongDataType),
+        }
+    }
+
+    /// Create a String attribute, NUL terminated
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// text: attribute text, without inner NUL
+    pub fn from_str(data_type: LcsfDataType, text: &str) -> Result<Self, LcsfValueErrorEnum> {
+        if data_type != LcsfDataType::String {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        }
+        if text.contains('\0') {
+            return Err(LcsfValueErrorEnum::InvalidString);
+        }
+        let mut data = text.as_bytes().to_vec();
+        data.push(0);
+        Ok(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(data),
+        })
+    }
+
+    /// Create a ByteArray attribute
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// data: attribute data, not empty
+    pub fn from_bytes(data_type: LcsfDataType, data: &[u8]) -> Result<Self, LcsfValueErrorEnum> {
+        if data_type != LcsfDataType::ByteArray
+            && data_type != LcsfDataType::Array(LcsfElemType::Uint8)
+        {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        }
+        if data.is_empty() {
+            return Err(LcsfValueErrorEnum::WrongDataSize);
+        }
+        Ok(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(data.to_vec()),
+        })
+    }
+
+    /// Create an Array attribute, the element type must match the descriptor
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// elem_arr: attribute elements, not empty
+    pub fn from_array<T: LcsfArrayElem>(
+        data_type: LcsfDataType,
+        elem_arr: &[T],
+    ) -> Result<Self, LcsfValueErrorEnum> {
+        let mut data = Vec::with_capacity(elem_arr.len() * T::ELEM_TYPE.size());
+        elem_arr.iter().for_each(|elem| elem.extend_le(&mut data));
+        Self::from_packed(data_type, T::ELEM_TYPE, data)
+    }
+
+    /// Create an Array attribute from already packed elements
+    ///
+    /// data_type: attribute data type from descriptor
+    ///
+    /// elem_type: packed elements type, must match the descriptor
+    ///
+    /// data: packed little endian elements, not empty
+    pub fn from_packed(
+        data_type: LcsfDataType,
+        elem_type: LcsfElemType,
+        data: Vec<u8>,
+    ) -> Result<Self, LcsfValueErrorEnum> {
+        if data_type != LcsfDataType::Array(elem_type) {
+            return Err(LcsfValueErrorEnum::WrongDataType);
+        }
+        if !lcsf_validator::check_data_type(data_type, &data) {
+            return Err(LcsfValueErrorEnum::WrongDataSize);
+        }
+        Ok(LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(data),
+        })
+    }
+}
+
+/// Lcsf command view structure
+#[derive(Debug, Clone, Copy)]
+pub struct LcsfCmdView<'a> {
+    cmd_desc: &'a LcsfCmdDesc,
+    valid_cmd: &'a LcsfValidCmd,
+}
+
+/// Lcsf attribute view structure, only created for present attributes
+#[derive(Debug, Clone, Copy)]
+pub struct LcsfAttView<'a> {
+    att_id: u16,
+    att_desc: &'a LcsfAttDesc,
+    valid_att: &'a LcsfValidAtt,
+}
+
+/// Check if a valid attribute is present
+///
+/// valid_att: valid attribute reference
+fn is_att_present(valid_att: &LcsfValidAtt) -> bool {
+    match &valid_att.payload {
+        LcsfValidAttPayload::Data(_)
+        | LcsfValidAttPayload::SubattArr(_)
+        | LcsfValidAttPayload::Message(..) => true,
+        LcsfValidAttPayload::List(item_arr) => !item_arr.is_empty(),
+        LcsfValidAttPayload::Absent => false,
+    }
+}
+
+/// Return the view of a present attribute from its id
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// att_arr: valid attribute array reference, following att_desc_arr
+///
+/// att_id: attribute id value
+fn get_att_view<'a>(
+    att_desc_arr: &'a [(u16, LcsfAttDesc)],
+    att_arr: &'a [LcsfValidAtt],
+    att_id: u16,
+) -> Option<LcsfAttView<'a>> {
+    iter_att_view(att_desc_arr, att_arr).find(|att_view| att_view.att_id == att_id)
+}
+
+/// Iterate over the views of the present attributes
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// att_arr: valid attribute array reference, following att_desc_arr
+fn iter_att_view<'a>(
+    att_desc_arr: &'a [(u16, LcsfAttDesc)],
+    att_arr: &'a [LcsfValidAtt],
+) -> impl Iterator<Item = LcsfAttView<'a>> {
+    att_desc_arr
+        .iter()
+        .zip(att_arr)
+        .filter(|(_, valid_att)| is_att_present(valid_att))
+        .map(|((att_id, att_desc), valid_att)| LcsfAttView {
+            att_id: *att_id,
+            att_desc,
+            valid_att,
+        })
+}
+
+/// Resolve a path segment into an attribute id and an optional item index
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// segment: attribute name or id (decimal or 0x hexadecimal), with an optional [index] suffix
+fn parse_segment(
+    att_desc_arr: &[(u16, LcsfAttDesc)],
+    segment: &str,
+) -> Option<(u16, Option<usize>)> {
+    // Retrieve item index
+    let (key, idx) = match segment
+        .strip_suffix(']')
+        .and_then(|seg| seg.split_once('['))
+    {
+        Some((key, idx_str)) => (key, Some(idx_str.parse::<usize>().ok()?)),
+        None => (segment, None),
+    };
+    // Names take precedence over ids
+    if let Some((att_id, _)) = att_desc_arr
+        .iter()
+        .find(|(_, att_desc)| att_desc.name == key)
+    {
+        return Some((*att_id, idx));
+    }
+    let att_id = match key.strip_prefix("0x") {
+        Some(hex) => u16::from_str_radix(hex, 16).ok()?,
+        None => key.parse::<u16>().ok()?,
+    };
+    Some((att_id, idx))
+}
+
+/// Recursively resolve a path from an attribute array
+///
+/// att_desc_arr: (id, attribute descriptor) array reference
+///
+/// att_arr: valid attribute array reference, following att_desc_arr
+///
+/// path: '/' separated path, see [parse_segment]
+fn get_path_rec<'a>(
+    att_desc_arr: &'a [(u16, LcsfAttDesc)],
+    att_arr: &'a [LcsfValidAtt],
+    path: &str,
+) -> Option<LcsfAttView<'a>> {
+    let (segment, rest) = match path.split_once('/') {
+        Some((segment, rest)) => (segment, Some(rest)),
+        None => (path, None),
+    };
+    let (att_id, idx) = parse_segment(att_desc_arr, segment)?;
+    let mut att_view = get_att_view(att_desc_arr, att_arr, att_id)?;
+    if let Some(idx) = idx {
+        att_view = *att_view.items().get(idx)?;
+    }
+    match rest {
+        None => Some(att_view),
+        Some(rest) => att_view.get_path(rest),
+    }
+}
+
+impl LcsfValidCmd {
+    /// Return a view of the command following its descriptor
+    ///
+    /// cmd_desc: command descriptor reference
+    pub fn view<'a>(&'a self, cmd_desc: &'a LcsfCmdDesc) -> LcsfCmdView<'a> {
+        LcsfCmdView {
+            cmd_desc,
+            valid_cmd: self,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<'a> LcsfCmdView<'a> {
+    /// Return the command id
+    pub fn id(&self) -> u16 {
+        self.valid_cmd.cmd_id
+    }
+
+    /// Return the command descriptor
+    pub fn desc(&self) -> &'a LcsfCmdDesc {
+        self.cmd_desc
+    }
+
+    /// Check if an attribute is present
+    ///
+    /// att_id: attribute id value
+    pub fn is_present(&self, att_id: u16) -> bool {
+        self.get(att_id).is_some()
+    }
+
+    /// Return a present attribute from its id
+    ///
+    /// att_id: attribute id value
+    pub fn get(&self, att_id: u16) -> Option<LcsfAttView<'a>> {
+        get_att_view(&self.cmd_desc.att_desc_arr, &self.valid_cmd.att_arr, att_id)
+    }
+
+    /// Return a present attribute from its path, e.g. "ca2/ca3/sa1", "0x7f/10" or "ca13[1]/sa1"
+    ///
+    /// path: '/' separated names or ids, with an optional [index] suffix for repeated attributes
+    pub fn get_path(&self, path: &str) -> Option<LcsfAttView<'a>> {
+        get_path_rec(&self.cmd_desc.att_desc_arr, &self.valid_cmd.att_arr, path)
+    }
+
+    /// Iterate over the present attributes
+    pub fn iter(&self) -> impl Iterator<Item = LcsfAttView<'a>> {
+        iter_att_view(&self.cmd_desc.att_desc_arr, &self.valid_cmd.att_arr)
+    }
+}
+
+#[allow(dead_code)]
+impl<'a> LcsfAttView<'a> {
+    /// Return the attribute id
+    pub fn id(&self) -> u16 {
+        self.att_id
+    }
+
+    /// Return the attribute name, empty if unnamed
+    pub fn name(&self) -> &'a str {
+        &self.att_desc.name
+    }
+
+    /// Return the attribute descriptor
+    pub fn desc(&self) -> &'a LcsfAttDesc {
+        self.att_desc
+    }
+
+    /// Return the attribute physical unit, None if not scaled
+    pub fn unit(&self) -> Option<&'a str> {
+        self.att_desc
+            .scale
+            .as_ref()
+            .map(|scale| scale.unit.as_str())
+    }
+
+    /// Return the attribute typed value, None for sub-attributes, repeated
+    /// attributes or data not matching its type
+    pub fn value(&self) -> Option<LcsfValue<'a>> {
+        let data_type = self.att_desc.data_type;
+        match data_type {
+            LcsfDataType::Uint8
+            | LcsfDataType::Uint16
+            | LcsfDataType::Uint32
+            | LcsfDataType::Uint64 => match &self.att_desc.scale {
+                Some(scale) => self
+                    .valid_att
+                    .as_scaled(data_type, scale)
+                    .ok()
+                    .map(|value| LcsfValue::Scaled(value, &scale.unit)),
+                None => self.valid_att.as_uint(data_type).ok().map(LcsfValue::Uint),
+            },
+            LcsfDataType::Float32 | LcsfDataType::Float64 => {
+                self.valid_att.as_f64(data_type).ok().map(LcsfValue::Float)
+            }
+            LcsfDataType::ByteArray => self.as_bytes().ok().map(LcsfValue::ByteArray),
+            LcsfDataType::String => {
+                let data = self.as_bytes().ok()?;
+                let text = data.strip_suffix(&[0]).unwrap_or(data);
+                Some(LcsfValue::String(String::from_utf8_lossy(text)))
+            }
+            LcsfDataType::Array(elem_type) => Some(LcsfValue::Array(
+                self.as_bytes()
+                    .ok()?
+                    .chunks_exact(elem_type.size())
+                    .map(|elem| get_elem_value(elem_type, elem))
+                    .collect::<Option<Vec<LcsfValue>>>()?,
+            )),
+            LcsfDataType::Timestamp { .. } => self.as_timestamp().ok().map(LcsfValue::Timestamp),
+            LcsfDataType::Duration(_) => self.as_duration().ok().map(LcsfValue::Duration),
+            LcsfDataType::Message(_) => self
+                .as_message()
+                .ok()
+                .map(|(prot_id, nested_cmd)| LcsfValue::Message(prot_id, nested_cmd)),
+            LcsfDataType::Subattributes => None,
+        }
+    }
+
+    /// Return the value as u8, see [LcsfValidAtt::as_u8]
+    pub fn as_u8(&self) -> Result<u8, LcsfValueErrorEnum> {
+        self.valid_att.as_u8(self.att_desc.data_type)
+    }
+
+    /// Return the value as u16, see [LcsfValidAtt::as_u16]
+    pub fn as_u16(&self) -> Result<u16, LcsfValueErrorEnum> {
+        self.valid_att.as_u16(self.att_desc.data_type)
+    }
+
+    /// Return the value as u32, see [LcsfValidAtt::as_u32]
+    pub fn as_u32(&self) -> Result<u32, LcsfValueErrorEnum> {
+        self.valid_att.as_u32(self.att_desc.data_type)
+    }
+
+    /// Return the value as u64, see [LcsfValidAtt::as_u64]
+    pub fn as_u64(&self) -> Result<u64, LcsfValueErrorEnum> {
+        self.valid_att.as_u64(self.att_desc.data_type)
+    }
+
+    /// Return the value as i32, see [LcsfValidAtt::as_i32]
+    pub fn as_i32(&self) -> Result<i32, LcsfValueErrorEnum> {
+        self.valid_att.as_i32(self.att_desc.data_type)
+    }
+
+    /// Return the value as i64, see [LcsfValidAtt::as_i64]
+    pub fn as_i64(&self) -> Result<i64, LcsfValueErrorEnum> {
+        self.valid_att.as_i64(self.att_desc.data_type)
+    }
+
+    /// Return the physical value, see [LcsfValidAtt::as_scaled]
+    pub fn as_scaled(&self) -> Result<f64, LcsfValueErrorEnum> {
+        let scale = self
+            .att_desc
+            .scale
+            .as_ref()
+            .ok_or(LcsfValueErrorEnum::WrongDataType)?;
+        self.valid_att.as_scaled(self.att_desc.data_type, scale)
+    }
+
+    /// Return the value as a point in time, see [LcsfValidAtt::as_timestamp]
+    pub fn as_timestamp(&self) -> Result<SystemTime, LcsfValueErrorEnum> {
+        self.valid_att.as_timestamp(self.att_desc.data_type)
+    }
+
+    /// Return the value as a time span, see [LcsfValidAtt::as_duration]
+    pub fn as_duration(&self) -> Result<Duration, LcsfValueErrorEnum> {
+        self.valid_att.as_duration(self.att_desc.data_type)
+    }
+
+    /// Return the value as f32, see [LcsfValidAtt::as_f32]
+    pub fn as_f32(&self) -> Result<f32, LcsfValueErrorEnum> {
+        self.valid_att.as_f32(self.att_desc.data_type)
+    }
+
+    /// Return the value as f64, see [LcsfValidAtt::as_f64]
+    pub fn as_f64(&self) -> Result<f64, LcsfValueErrorEnum> {
+        self.valid_att.as_f64(self.att_desc.data_type)
+    }
+
+    /// Return the value as text, see [LcsfValidAtt::as_str]
+    pub fn as_str(&self) -> Result<&'a str, LcsfValueErrorEnum> {
+        self.valid_att.as_str(self.att_desc.data_type)
+    }
+
+    /// Return the value as bytes, see [LcsfValidAtt::as_bytes]
+    pub fn as_bytes(&self) -> Result<&'a [u8], LcsfValueErrorEnum> {
+        self.valid_att.as_bytes(self.att_desc.data_type)
+    }
+
+    /// Return the value as array elements, see [LcsfValidAtt::as_array]
+    pub fn as_array<T: LcsfArrayElem>(&self) -> Result<Vec<T>, LcsfValueErrorEnum> {
+        self.valid_att.as_array(self.att_desc.data_type)
+    }
+
+    /// Return the value as nested command, see [LcsfValidAtt::as_message]
+    pub fn as_message(&self) -> Result<(u16, &'a LcsfValidCmd), LcsfValueErrorEnum> {
+        self.valid_att.as_message(self.att_desc.data_type)
+    }
+
+    /// Return the occurrences of a repeated attribute, or the attribute itself if not repeated
+    pub fn items(&self) -> Vec<LcsfAttView<'a>> {
+        match &self.valid_att.payload {
+            LcsfValidAttPayload::List(item_arr) => item_arr
+                .iter()
+                .map(|valid_att| LcsfAttView { valid_att, ..*self })
+                .collect(),
+            _ => vec![*self],
+        }
+    }
+
+    /// Check if a sub-attribute is present
+    ///
+    /// att_id: sub-attribute id value
+    pub fn is_present(&self, att_id: u16) -> bool {
+        self.get(att_id).is_some()
+    }
+
+    /// Return a present sub-attribute from its id
+    ///
+    /// att_id: sub-attribute id value
+    pub fn get(&self, att_id: u16) -> Option<LcsfAttView<'a>> {
+        match &self.valid_att.payload {
+            LcsfValidAttPayload::SubattArr(subatt_arr) => {
+                get_att_view(&self.att_desc.subatt_desc_arr, subatt_arr, att_id)
+            }
+            _ => None,
+        }
+    }
+
+    /// Return a present sub-attribute from its path, see [LcsfCmdView::get_path]
+    ///
+    /// path: '/' separated names or ids, with an optional [index] suffix for repeated attributes
+    pub fn get_path(&self, path: &str) -> Option<LcsfAttView<'a>> {
+        match &self.valid_att.payload {
+            LcsfValidAttPayload::SubattArr(subatt_arr) => {
+                get_path_rec(&self.att_desc.subatt_desc_arr, subatt_arr, path)
+            }
+            _ => None,
+        }
+    }
+
+    /// Iterate over the present sub-attributes, empty if the attribute has none
+    pub fn iter(&self) -> impl Iterator<Item = LcsfAttView<'a>> {
+        let subatt_arr: &'a [LcsfValidAtt] = match &self.valid_att.payload {
+            LcsfValidAttPayload::SubattArr(subatt_arr) => subatt_arr,
+            _ => &[],
+        };
+        iter_att_view(&self.att_desc.subatt_desc_arr, subatt_arr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcsf_lib::lcsf_transcoder::LcsfModeEnum;
+    use crate::lcsf_lib::lcsf_validator::LcsfAttCardinality;
+    use crate::lcsf_lib::lcsf_validator::LcsfTimeRes;
+    use std::time::UNIX_EPOCH;
+
+    fn data_desc(name: &str, data_type: LcsfDataType, is_optional: bool) -> LcsfAttDesc {
+        LcsfAttDesc {
+            name: name.into(),
+            is_optional,
+            data_type,
+            ..Default::default()
+        }
+    }
+
+    fn data_att(data: &[u8]) -> LcsfValidAtt {
+        LcsfValidAtt {
+            payload: LcsfValidAttPayload::Data(data.to_vec()),
+        }
+    }
+
+    fn test_cmd_desc() -> LcsfCmdDesc {
+        LcsfCmdDesc {
+            name: "CMD".into(),
+            att_desc_arr: vec![
+                (0x01, data_desc("sa1", LcsfDataType::Uint16, false)),
+                (0x02, data_desc("sa2", LcsfDataType::String, true)),
+                (0x03, data_desc("sa3", LcsfDataType::Float32, true)),
+                (
+                    0x04,
+                    LcsfAttDesc {
+                        name: "ca1".into(),
+                        data_type: LcsfDataType::Subattributes,
+                        subatt_desc_arr: vec![
+                            (0x0a, data_desc("sa4", LcsfDataType::ByteArray, false)),
+                            (
+                                0x0b,
+                                LcsfAttDesc {
+                                    name: "ca2".into(),
+                                    data_type: LcsfDataType::Subattributes,
+                                    subatt_desc_arr: vec![(
+                                        0x00,
+                                        data_desc("sa5", LcsfDataType::Uint8, false),
+                                    )],
+                                    cardinality: LcsfAttCardinality::Repeated {
+                                        min_count: None,
+                                        max_count: None,
+                                    },
+                                    ..Default::default()
+                                },
+                            ),
+                        ],
+                        ..Default::default()
+                    },
+                ),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn test_valid_cmd() -> LcsfValidCmd {
+        LcsfValidCmd {
+            cmd_id: 0x12,
+            att_arr: vec![
+                data_att(&[0x34, 0x12]),
+                data_att(b"Hello\0"),
+                LcsfValidAtt {
+                    payload: LcsfValidAttPayload::Absent,
+                },
+                LcsfValidAtt {
+                    payload: LcsfValidAttPayload::SubattArr(vec![
+                        data_att(&[0xde, 0xad]),
+                        LcsfValidAtt {
+                            payload: LcsfValidAttPayload::List(vec![
+                                LcsfValidAtt {
+                                    payload: LcsfValidAttPayload::SubattArr(vec![data_att(&[1])]),
+                                },
+                                LcsfValidAtt {
+                                    payload: LcsfValidAttPayload::SubattArr(vec![data_att(&[2])]),
+                                },
+                            ]),
+                        },
+                    ]),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_cmd_view() {
+        let cmd_desc = test_cmd_desc();
+        let valid_cmd = test_valid_cmd();
+        let view = valid_cmd.view(&cmd_desc);
+        assert_eq!(view.id(), 0x12);
+        assert_eq!(view.desc().name, "CMD");
+        // Test presence
+        assert!(view.is_present(0x01));
+        assert!(view.is_present(0x02));
+        assert!(!view.is_present(0x03));
+        assert!(!view.is_present(0x05));
+        // Test values
+        assert_eq!(
+            view.get(0x01).unwrap().value(),
+            Some(LcsfValue::Uint(0x1234))
+        );
+        assert_eq!(
+            view.get(0x02).unwrap().value(),
+            Some(LcsfValue::String("Hello".into()))
+        );
+        assert_eq!(view.get(0x04).unwrap().value(), None);
+        // Test iteration
+        let id_arr: Vec<u16> = view.iter().map(|att_view| att_view.id()).collect();
+        assert_eq!(id_arr, vec![0x01, 0x02, 0x04]);
+        let name_arr: Vec<&str> = view
+            .get(0x04)
+            .unwrap()
+            .iter()
+            .map(|att_view| att_view.name())
+            .collect();
+        assert_eq!(name_arr, vec!["sa4", "ca2"]);
+        assert_eq!(view.get(0x01).unwrap().iter().count(), 0);
+    }
+
+    #[test]
+    fn test_path_view() {
+        let cmd_desc = test_cmd_desc();
+        let valid_cmd = test_valid_cmd();
+        let view = valid_cmd.view(&cmd_desc);
+        // Test valid paths
+        assert_eq!(
+            view.get_path("ca1/sa4").unwrap().value(),
+            Some(LcsfValue::ByteArray(&[0xde, 0xad]))
+        );
+        assert_eq!(
+            view.get_path("0x04/10").unwrap().value(),
+            Some(LcsfValue::ByteArray(&[0xde, 0xad]))
+        );
+        assert_eq!(
+            view.get_path("ca1/ca2[1]/sa5").unwrap().value(),
+            Some(LcsfValue::Uint(2))
+        );
+        let ca2_view = view.get_path("ca1/ca2").unwrap();
+        assert_eq!(ca2_view.items().len(), 2);
+        assert_eq!(
+            ca2_view.items()[0].get(0x00).unwrap().value(),
+            Some(LcsfValue::Uint(1))
+        );
+        assert_eq!(view.get_path("sa1").unwrap().items().len(), 1);
+        // Test invalid paths
+        assert!(view.get_path("sa3").is_none());
+        assert!(view.get_path("sa1/sa4").is_none());
+        assert!(view.get_path("ca1/ca2[2]/sa5").is_none());
+        assert!(view.get_path("ca1/ca2[x]").is_none());
+        assert!(view.get_path("ca1/unknown").is_none());
+        assert!(view.get_path("").is_none());
+    }
+
+    #[test]
+    fn test_typed_accessors() {
+        // Test integers
+        let att = LcsfValidAtt::from_u16(LcsfDataType::Uint16, 0x1234).unwrap();
+        assert_eq!(att, data_att(&[0x34, 0x12]));
+        assert_eq!(att.as_u16(LcsfDataType::Uint16), Ok(0x1234));
+        assert_eq!(att.as_u32(LcsfDataType::Uint16), Ok(0x1234));
+        assert_eq!(att.as_i32(LcsfDataType::Uint16), Ok(0x1234));
+        assert_eq!(
+            att.as_u8(LcsfDataType::Uint16),
+            Err(LcsfValueErrorEnum::OutOfRange)
+        );
+        assert_eq!(
+            att.as_u16(LcsfDataType::Uint8),
+            Err(LcsfValueErrorEnum::WrongDataSize)
+        );
+        assert_eq!(
+            att.as_f32(LcsfDataType::Uint16),
+            Err(LcsfValueErrorEnum::WrongDataType)
+        );
+        assert_eq!(
+            LcsfValidAtt::from_u8(LcsfDataType::Uint32, 5).unwrap(),
+            data_att(&[5])
+        );
+        assert_eq!(
+            LcsfValidAtt::from_u32(LcsfDataType::Uint16, 0x10000),
+            Err(LcsfValueErrorEnum::OutOfRange)
+        );
+        assert_eq!(
+            LcsfValidAtt::from_i32(LcsfDataType::Uint32, -1),
+            Err(LcsfValueErrorEnum::OutOfRange)
+        );
+        assert_eq!(
+            LcsfValidAtt::from_u8(LcsfDataType::String, 5),
+            Err(LcsfValueErrorEnum::WrongDataType)
+        );
+        let att = LcsfValidAtt::from_u64(LcsfDataType::Uint64, u64::MAX).unwrap();
+        assert_eq!(att.as_u64(LcsfDataType::Uint64), Ok(u64::MAX));
+        assert_eq!(
+            att.as_i64(LcsfDataType::Uint64),
+            Err(LcsfValueErrorEnum::OutOfRange)
+        );
+        // Test floats
+        let att = LcsfValidAtt::from_f32(LcsfDataType::Float32, 1.5).unwrap();
+        assert_eq!(att.as_f32(LcsfDataType::Float32), Ok(1.5));
+        assert_eq!(att.as_f64(LcsfDataType::Float32), Ok(1.5));
+        assert_eq!(
+            att.as_f64(LcsfDataType::Float64),
+            Err(LcsfValueErrorEnum::WrongDataSize)
+        );
+        let att = LcsfValidAtt::from_f32(LcsfDataType::Float64, 1.5).unwrap();
+        assert_eq!(att.as_f64(LcsfDataType::Float64), Ok(1.5));
+        assert_eq!(
+            LcsfValidAtt::from_f64(LcsfDataType::Float32, 0.1),
+            Err(LcsfValueErrorEnum::OutOfRange)
+        );
+        // Test strings and bytes
+        let att = LcsfValidAtt::from_str(LcsfDataType::String, "Hello").unwrap();
+        assert_eq!(att, data_att(b"Hello\0"));
+        assert_eq!(att.as_str(LcsfDataType::String), Ok("Hello"));
+        assert_eq!(att.as_bytes(LcsfDataType::String), Ok(&b"Hello\0"[..]));
+        assert_eq!(
+            LcsfValidAtt::from_str(LcsfDataType::String, "He\0llo"),
+            Err(LcsfValueErrorEnum::InvalidString)
+        );
+        assert_eq!(
+            data_att(b"Hello").as_str(LcsfDataType::String),
+            Err(LcsfValueErrorEnum::InvalidString)
+        );
+        assert_eq!(
+            data_att(&[0xff, 0x00]).as_str(LcsfDataType::String),
+            Err(LcsfValueErrorEnum::InvalidString)
+        );
+        let att = LcsfValidAtt::from_bytes(LcsfDataType::ByteArray, &[1, 2]).unwrap();
+        assert_eq!(att.as_bytes(LcsfDataType::ByteArray), Ok(&[1u8, 2][..]));
+        assert_eq!(
+            LcsfValidAtt::from_bytes(LcsfDataType::ByteArray, &[]),
+            Err(LcsfValueErrorEnum::WrongDataSize)
+        );
+        // Test missing data
+        let absent_att = LcsfValidAtt {
+            payload: LcsfValidAttPayload::Absent,
+        };
+        assert_eq!(
+            absent_att.as_u8(LcsfDataType::Uint8),
+            Err(LcsfValueErrorEnum::NoData)
+        );
+        assert_eq!(
+            data_att(&[]).as_u8(LcsfDataType::Uint8),
+            Err(LcsfValueErrorEnum::WrongDataSize)
+        );
+        let subatt = LcsfValidAtt {
+            payload: LcsfValidAttPayload::SubattArr(Vec::new()),
+        };
+        assert_eq!(
+            subatt.as_bytes(LcsfDataType::ByteArray),
+            Err(LcsfValueErrorEnum::NoData)
+        );
+        // Test view accessors
+        let cmd_desc = test_cmd_desc();
+        let valid_cmd = test_valid_cmd();
+        let view = valid_cmd.view(&cmd_desc);
+        assert_eq!(view.get_path("sa1").unwrap().as_u16(), Ok(0x1234));
+        assert_eq!(view.get_path("sa2").unwrap().as_str(), Ok("Hello"));
+        assert_eq!(
+            view.get_path("ca1/sa4").unwrap().as_bytes(),
+            Ok(&[0xde, 0xad][..])
+        );
+        assert_eq!(
+            view.get_path("ca1/sa4").unwrap().as_u8(),
+            Err(LcsfValueErrorEnum::WrongDataType)
+        );
+    }
+
+    #[test]
+    fn test_array_accessors() {
+        let u16_type = LcsfDataType::Array(LcsfElemType::Uint16);
+        let f32_type = LcsfDataType::Array(LcsfElemType::Float32);
+        // Test typed arrays
+        let att = LcsfValidAtt::from_array(u16_type, &[0x1234u16, 5]).unwrap();
+        assert_eq!(att, data_att(&[0x34, 0x12, 0x05, 0x00]));
+        assert_eq!(att.as_array::<u16>(u16_type), Ok(vec![0x1234, 5]));
+        assert_eq!(att.as_bytes(u16_type), Ok(&[0x34, 0x12, 0x05, 0x00][..]));
+        assert_eq!(
+            att.as_array::<i16>(u16_type),
+            Err(LcsfValueErrorEnum::WrongDataType)
+        );
+        assert_eq!(
+            data_att(&[0x34, 0x12, 0x05]).as_array::<u16>(u16_type),
+            Err(LcsfValueErrorEnum::WrongDataSize)
+        );
+        let att = LcsfValidAtt::from_array(f32_type, &[1.5f32, -2.0]).unwrap();
+        assert_eq!(att.as_array::<f32>(f32_type), Ok(vec![1.5, -2.0]));
+        // Test construction errors
+        assert_eq!(
+            LcsfValidAtt::from_array(u16_type, &[1u32]),
+            Err(LcsfValueErrorEnum::WrongDataType)
+        );
+        assert_eq!(
+            LcsfValidAtt::from_array::<u16>(u16_type, &[]),
+            Err(LcsfValueErrorEnum::WrongDataSize)
+        );
+        assert_eq!(
+            LcsfValidAtt::from_bytes(LcsfDataType::Array(LcsfElemType::Uint8), &[1, 2]),
+            Ok(data_att(&[1, 2]))
+        );
+        // Test view value
+        let cmd_desc = LcsfCmdDesc {
+            att_desc_arr: vec![
+                (
+                    0x01,
+                    data_desc("sa1", LcsfDataType::Array(LcsfElemType::Int8), false),
+                ),
+                (0x02, data_desc("sa2", f32_type, false)),
+            ],
+            ..Default::default()
+        };
+        let valid_cmd = LcsfValidCmd {
+            cmd_id: 0x01,
+            att_arr: vec![data_att(&[0xff, 0x02]), att],
+        };
+        let view = valid_cmd.view(&cmd_desc);
+        assert_eq!(
+            view.get_path("sa1").unwrap().value(),
+            Some(LcsfValue::Array(vec![
+                LcsfValue::Int(-1),
+                LcsfValue::Int(2)
+            ]))
+        );
+        assert_eq!(
+            view.get_path("sa2").unwrap().value(),
+            Some(LcsfValue::Array(vec![
+                LcsfValue::Float(1.5),
+                LcsfValue::Float(-2.0)
+            ]))
+        );
+        assert_eq!(
+            view.get_path("sa1").unwrap().as_array::<i8>(),
+            Ok(vec![-1, 2])
+        );
+    }
+
+    #[test]
+    fn test_scaled_accessors() {
+        let scale = LcsfAttScale {
+            factor: 0.5,
+            offset: -40.0,
+            unit: "°C".into(),
+        };
+        // Test scaled integers
+        let att = LcsfValidAtt::from_scaled(LcsfDataType::Uint8, &scale, 21.6).unwrap();
+        assert_eq!(att, data_att(&[123]));
+        assert_eq!(att.as_scaled(LcsfDataType::Uint8, &scale), Ok(21.5));
+        assert_eq!(att.as_u8(LcsfDataType::Uint8), Ok(123));
+        assert_eq!(
+            LcsfValidAtt::from_scaled(LcsfDataType::Uint8, &scale, 100.0),
+            Err(LcsfValueErrorEnum::OutOfRange)
+        );
+        assert_eq!(
+            LcsfValidAtt::from_scaled(LcsfDataType::Uint8, &scale, -41.0),
+            Err(LcsfValueErrorEnum::OutOfRange)
+        );
+        assert_eq!(
+            att.as_scaled(LcsfDataType::Float32, &scale),
+            Err(LcsfValueErrorEnum::WrongDataType)
+        );
+        // Test view value and unit
+        let cmd_desc = LcsfCmdDesc {
+            att_desc_arr: vec![
+                (
+                    0x01,
+                    LcsfAttDesc {
+                        name: "temp".into(),
+                        data_type: LcsfDataType::Uint8,
+                        scale: Some(scale),
+                        ..Default::default()
+                    },
+                ),
+                (0x02, data_desc("raw", LcsfDataType::Uint8, false)),
+            ],
+            ..Default::default()
+        };
+        let valid_cmd = LcsfValidCmd {
+            cmd_id: 0x01,
+            att_arr: vec![att, data_att(&[123])],
+        };
+        let view = valid_cmd.view(&cmd_desc);
+        let temp_view = view.get_path("temp").unwrap();
+        assert_eq!(temp_view.value(), Some(LcsfValue::Scaled(21.5, "°C")));
+        assert_eq!(temp_view.unit(), Some("°C"));
+        assert_eq!(temp_view.as_scaled(), Ok(21.5));
+        let raw_view = view.get_path("raw").unwrap();
+        assert_eq!(raw_view.value(), Some(LcsfValue::Uint(123)));
+        assert_eq!(raw_view.unit(), None);
+        assert_eq!(raw_view.as_scaled(), Err(LcsfValueErrorEnum::WrongDataType));
+    }
+
+    #[test]
+    fn test_time_accessors() {
+        let time_type = LcsfDataType::Timestamp {
+            res: LcsfTimeRes::Millis,
+            epoch: 1000,
+        };
+        let duration_type = LcsfDataType::Duration(LcsfTimeRes::Micros);
+        // Test timestamps
+        let time = UNIX_EPOCH + Duration::from_millis(1_000_500);
+        let att = LcsfValidAtt::from_timestamp(time_type, time).unwrap();
+        assert_eq!(att, data_att(&[0xf4, 0x01]));
+        assert_eq!(att.as_timestamp(time_type), Ok(time));
+        assert_eq!(att.as_u16(time_type), Ok(500));
+        assert_eq!(
+            att.as_duration(time_type),
+            Err(LcsfValueErrorEnum::WrongDataType)
+        );
+        assert_eq!(
+            LcsfValidAtt::from_timestamp(time_type, UNIX_EPOCH),
+            Err(LcsfValueErrorEnum::OutOfRange)
+        );
+        // Test durations
+        let att =
+            LcsfValidAtt::from_duration(duration_type, Duration::from_nanos(1_500_900)).unwrap();
+        assert_eq!(att, data_att(&[0xdc, 0x05]));
+        assert_eq!(
+            att.as_duration(duration_type),
+            Ok(Duration::from_micros(1500))
+        );
+        assert_eq!(
+            LcsfValidAtt::from_duration(LcsfDataType::Uint32, Duration::ZERO),
+            Err(LcsfValueErrorEnum::WrongDataType)
+        );
+        // Test view value
+        let cmd_desc = LcsfCmdDesc {
+            att_desc_arr: vec![
+                (0x01, data_desc("time", time_type, false)),
+                (0x02, data_desc("duration", duration_type, false)),
+            ],
+            ..Default::default()
+        };
+        let valid_cmd = LcsfValidCmd {
+            cmd_id: 0x01,
+            att_arr: vec![data_att(&[0xf4, 0x01]), att],
+        };
+        let view = valid_cmd.view(&cmd_desc);
+        assert_eq!(
+            view.get_path("time").unwrap().value(),
+            Some(LcsfValue::Timestamp(time))
+        );
+        assert_eq!(
+            view.get_path("duration").unwrap().value(),
+            Some(LcsfValue::Duration(Duration::from_micros(1500)))
+        );
+        assert_eq!(view.get_path("time").unwrap().as_timestamp(), Ok(time));
+    }
+
+    #[test]
+    fn test_message_accessors() {
+        let msg_type = LcsfDataType::Message(LcsfModeEnum::Small);
+        let nested_cmd = LcsfValidCmd {
+            cmd_id: 0x02,
+            att_arr: vec![data_att(&[0x2a])],
+        };
+        let att = LcsfValidAtt::from_message(msg_type, 0x56, nested_cmd.clone()).unwrap();
+        assert_eq!(att.as_message(msg_type), Ok((0x56, &nested_cmd)));
+        assert_eq!(
+            att.as_bytes(msg_type),
+            Err(LcsfValueErrorEnum::WrongDataType)
+        );
+        assert_eq!(
+            LcsfValidAtt::from_message(LcsfDataType::ByteArray, 0x56, nested_cmd.clone()),
+            Err(LcsfValueErrorEnum::WrongDataType)
+        );
+        // Test view value
+        let cmd_desc = LcsfCmdDesc {
+            att_desc_arr: vec![(0x01, data_desc("tunnel", msg_type, false))],
+            ..Default::default()
+        };
+        let valid_cmd = LcsfValidCmd {
+            cmd_id: 0x01,
+            att_arr: vec![att],
+        };
+        let att_view = valid_cmd.view(&cmd_desc).get_path("tunnel").unwrap();
+        assert_eq!(
+            att_view.value(),
+            Some(LcsfValue::Message(0x56, &nested_cmd))
+        );
+        assert_eq!(att_view.as_message(), Ok((0x56, &nested_cmd)));
+    }
+}
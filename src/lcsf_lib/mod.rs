@@ -7,7 +7,9 @@
 //! You should have received a copy of the GNU Lesser General Public License
 //! along with this program. If not, see <https://www.gnu.org/licenses/>
 
+pub mod lcsf_builder;
 pub mod lcsf_core;
 pub mod lcsf_error;
 pub mod lcsf_transcoder;
 pub mod lcsf_validator;
+pub mod lcsf_view;
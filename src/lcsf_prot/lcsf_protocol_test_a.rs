@@ -108,37 +108,27 @@ fn cc2_get_data(att_arr: &[LcsfValidAtt]) -> CmdPayload {
         cc2_payload.sa5 = CString::from_vec_with_nul(data.clone()).unwrap();
     }
     // Retrieve data of attribute sa6
-    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload
-        && !data.is_empty()
-    {
+    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload {
         cc2_payload.is_sa6_here = true;
         cc2_payload.sa6 = u8::from_le_bytes(data.as_slice().try_into().unwrap());
     }
     // Retrieve data of attribute sa7
-    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload
-        && !data.is_empty()
-    {
+    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload {
         cc2_payload.is_sa7_here = true;
         cc2_payload.sa7 = lcsf_validator::vle_decode(data) as u16;
     }
     // Retrieve data of attribute sa8
-    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload
-        && !data.is_empty()
-    {
+    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload {
         cc2_payload.is_sa8_here = true;
         cc2_payload.sa8 = lcsf_validator::vle_decode(data) as u32;
     }
     // Retrieve data of attribute sa9
-    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload
-        && !data.is_empty()
-    {
+    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload {
         cc2_payload.is_sa9_here = true;
         cc2_payload.sa9 = data.clone();
     }
     // Retrieve data of attribute sa10
-    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload
-        && !data.is_empty()
-    {
+    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload {
         cc2_payload.is_sa10_here = true;
         cc2_payload.sa10 = CString::from_vec_with_nul(data.clone()).unwrap();
     }
@@ -201,37 +191,27 @@ fn cc3_get_data(att_arr: &[LcsfValidAtt]) -> CmdPayload {
         cc3_payload.sa5 = CString::from_vec_with_nul(data.clone()).unwrap();
     }
     // Retrieve data of attribute sa6
-    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload
-        && !data.is_empty()
-    {
+    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload {
         cc3_payload.is_sa6_here = true;
         cc3_payload.sa6 = u8::from_le_bytes(data.as_slice().try_into().unwrap());
     }
     // Retrieve data of attribute sa7
-    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload
-        && !data.is_empty()
-    {
+    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload {
         cc3_payload.is_sa7_here = true;
         cc3_payload.sa7 = lcsf_validator::vle_decode(data) as u16;
     }
     // Retrieve data of attribute sa8
-    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload
-        && !data.is_empty()
-    {
+    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload {
         cc3_payload.is_sa8_here = true;
         cc3_payload.sa8 = lcsf_validator::vle_decode(data) as u32;
     }
     // Retrieve data of attribute sa9
-    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload
-        && !data.is_empty()
-    {
+    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload {
         cc3_payload.is_sa9_here = true;
         cc3_payload.sa9 = data.clone();
     }
     // Retrieve data of attribute sa10
-    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload
-        && !data.is_empty()
-    {
+    if let LcsfValidAttPayload::Data(data) = &att_iter.next().unwrap().payload {
         cc3_payload.is_sa10_here = true;
         cc3_payload.sa10 = CString::from_vec_with_nul(data.clone()).unwrap();
     }
@@ -289,24 +269,18 @@ fn cc5_get_data(att_arr: &[LcsfValidAtt]) -> CmdPayload {
             cc5_payload.ca5_payload.sa2 = lcsf_validator::vle_decode(data) as u16;
         }
         // Retrieve data of sub-attribute sa3
-        if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload
-            && !data.is_empty()
-        {
+        if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload {
             cc5_payload.ca5_payload.is_sa3_here = true;
             cc5_payload.ca5_payload.sa3 = lcsf_validator::vle_decode(data) as u32;
         }
     }
     // Retrieve data of attribute ca6
-    if let LcsfValidAttPayload::SubattArr(subatt_arr) = &att_iter.next().unwrap().payload
-        && !subatt_arr.is_empty()
-    {
+    if let LcsfValidAttPayload::SubattArr(subatt_arr) = &att_iter.next().unwrap().payload {
         cc5_payload.is_ca6_here = true;
         // Get iterator
         let subatt_iter = &mut subatt_arr.iter();
         // Retrieve data of sub-attribute sa1
-        if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload
-            && !data.is_empty()
-        {
+        if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload {
             cc5_payload.ca6_payload.is_sa1_here = true;
             cc5_payload.ca6_payload.sa1 = u8::from_le_bytes(data.as_slice().try_into().unwrap());
         }
@@ -315,9 +289,7 @@ fn cc5_get_data(att_arr: &[LcsfValidAtt]) -> CmdPayload {
             // Get iterator
             let subatt_iter = &mut subatt_arr.iter();
             // Retrieve data of sub-attribute sa1
-            if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload
-                && !data.is_empty()
-            {
+            if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload {
                 cc5_payload.ca6_payload.ca7_payload.is_sa1_here = true;
                 cc5_payload.ca6_payload.ca7_payload.sa1 =
                     u8::from_le_bytes(data.as_slice().try_into().unwrap());
@@ -376,24 +348,18 @@ fn cc6_get_data(att_arr: &[LcsfValidAtt]) -> CmdPayload {
             cc6_payload.ca9_payload.sa2 = lcsf_validator::vle_decode(data) as u16;
         }
         // Retrieve data of sub-attribute sa3
-        if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload
-            && !data.is_empty()
-        {
+        if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload {
             cc6_payload.ca9_payload.is_sa3_here = true;
             cc6_payload.ca9_payload.sa3 = lcsf_validator::vle_decode(data) as u32;
         }
     }
     // Retrieve data of attribute ca10
-    if let LcsfValidAttPayload::SubattArr(subatt_arr) = &att_iter.next().unwrap().payload
-        && !subatt_arr.is_empty()
-    {
+    if let LcsfValidAttPayload::SubattArr(subatt_arr) = &att_iter.next().unwrap().payload {
         cc6_payload.is_ca10_here = true;
         // Get iterator
         let subatt_iter = &mut subatt_arr.iter();
         // Retrieve data of sub-attribute sa1
-        if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload
-            && !data.is_empty()
-        {
+        if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload {
             cc6_payload.ca10_payload.is_sa1_here = true;
             cc6_payload.ca10_payload.sa1 = u8::from_le_bytes(data.as_slice().try_into().unwrap());
         }
@@ -402,9 +368,7 @@ fn cc6_get_data(att_arr: &[LcsfValidAtt]) -> CmdPayload {
             // Get iterator
             let subatt_iter = &mut subatt_arr.iter();
             // Retrieve data of sub-attribute sa1
-            if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload
-                && !data.is_empty()
-            {
+            if let LcsfValidAttPayload::Data(data) = &subatt_iter.next().unwrap().payload {
                 cc6_payload.ca10_payload.ca11_payload.is_sa1_here = true;
                 cc6_payload.ca10_payload.ca11_payload.sa1 =
                     u8::from_le_bytes(data.as_slice().try_into().unwrap());
@@ -476,7 +440,7 @@ fn cc1_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::Data(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
         // Fill data of attribute sa7
@@ -486,7 +450,7 @@ fn cc1_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::Data(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
         // Fill data of attribute sa8
@@ -496,7 +460,7 @@ fn cc1_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::Data(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
         // Fill data of attribute sa9
@@ -506,7 +470,7 @@ fn cc1_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::Data(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
         // Fill data of attribute sa10
@@ -516,7 +480,7 @@ fn cc1_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::Data(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
         // Fill data of attribute sa11
@@ -565,7 +529,7 @@ fn cc3_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::Data(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
         // Fill data of attribute sa7
@@ -575,7 +539,7 @@ fn cc3_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::Data(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
         // Fill data of attribute sa8
@@ -585,7 +549,7 @@ fn cc3_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::Data(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
         // Fill data of attribute sa9
@@ -595,7 +559,7 @@ fn cc3_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::Data(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
         // Fill data of attribute sa10
@@ -605,7 +569,7 @@ fn cc3_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::Data(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
         // Fill data of attribute sa11
@@ -653,7 +617,7 @@ fn cc4_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
                             payload.ca1_payload.sa3 as u64,
                         ))
                     } else {
-                        LcsfValidAttPayload::Data(Vec::new())
+                        LcsfValidAttPayload::Absent
                     },
                 },
             ]),
@@ -669,7 +633,7 @@ fn cc4_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
                                 payload.ca2_payload.sa1 as u64,
                             ))
                         } else {
-                            LcsfValidAttPayload::Data(Vec::new())
+                            LcsfValidAttPayload::Absent
                         },
                     },
                     // Fill data of sub-attribute ca3
@@ -682,7 +646,7 @@ fn cc4_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
                                         payload.ca2_payload.ca3_payload.sa1 as u64,
                                     ))
                                 } else {
-                                    LcsfValidAttPayload::Data(Vec::new())
+                                    LcsfValidAttPayload::Absent
                                 },
                             },
                             // Fill data of sub-attribute ca4
@@ -702,7 +666,7 @@ fn cc4_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::SubattArr(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
     }
@@ -738,7 +702,7 @@ fn cc6_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
                             payload.ca9_payload.sa3 as u64,
                         ))
                     } else {
-                        LcsfValidAttPayload::Data(Vec::new())
+                        LcsfValidAttPayload::Absent
                     },
                 },
             ]),
@@ -754,7 +718,7 @@ fn cc6_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
                                 payload.ca10_payload.sa1 as u64,
                             ))
                         } else {
-                            LcsfValidAttPayload::Data(Vec::new())
+                            LcsfValidAttPayload::Absent
                         },
                     },
                     // Fill data of sub-attribute ca11
@@ -767,7 +731,7 @@ fn cc6_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
                                         payload.ca10_payload.ca11_payload.sa1 as u64,
                                     ))
                                 } else {
-                                    LcsfValidAttPayload::Data(Vec::new())
+                                    LcsfValidAttPayload::Absent
                                 },
                             },
                             // Fill data of sub-attribute ca12
@@ -792,7 +756,7 @@ fn cc6_fill_att(cmd_payload: &CmdPayload) -> Vec<LcsfValidAtt> {
             });
         } else {
             att_arr.push(LcsfValidAtt {
-                payload: LcsfValidAttPayload::SubattArr(Vec::new()),
+                payload: LcsfValidAttPayload::Absent,
             });
         }
     }
@@ -950,106 +914,106 @@ const CC6_ATT_ID_CA10: u16 = 0xb;
 lazy_static! {
     /// Test protocol descriptor
     pub static ref PROT_DESC:LcsfProtDesc = LcsfProtDesc {cmd_desc_arr: vec![
-        (CMD_ID_SC1, LcsfCmdDesc {att_desc_arr: Vec::new()}),
-        (CMD_ID_SC2, LcsfCmdDesc {att_desc_arr: Vec::new()}),
-        (CMD_ID_SC3, LcsfCmdDesc {att_desc_arr: Vec::new()}),
+        (CMD_ID_SC1, LcsfCmdDesc {att_desc_arr: Vec::new(), ..Default::default()}),
+        (CMD_ID_SC2, LcsfCmdDesc {att_desc_arr: Vec::new(), ..Default::default()}),
+        (CMD_ID_SC3, LcsfCmdDesc {att_desc_arr: Vec::new(), ..Default::default()}),
         (CMD_ID_CC1, LcsfCmdDesc {att_desc_arr: vec![
-            (CC1_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA3, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA5, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA6, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA7, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA8, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA9, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA10, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA11, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint64, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA12, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float32, subatt_desc_arr: Vec::new()}),
-            (CC1_ATT_ID_SA13, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float64, subatt_desc_arr: Vec::new()}),
-        ]}),
+            (CC1_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA3, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA5, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA6, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA7, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA8, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA9, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA10, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA11, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint64, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA12, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC1_ATT_ID_SA13, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float64, subatt_desc_arr: Vec::new(), ..Default::default()}),
+        ], ..Default::default()}),
         (CMD_ID_CC2, LcsfCmdDesc {att_desc_arr: vec![
-            (CC2_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA3, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA5, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA6, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA7, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA8, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA9, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA10, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA11, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint64, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA12, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float32, subatt_desc_arr: Vec::new()}),
-            (CC2_ATT_ID_SA13, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float64, subatt_desc_arr: Vec::new()}),
-        ]}),
+            (CC2_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA3, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA5, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA6, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA7, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA8, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA9, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA10, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA11, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint64, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA12, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC2_ATT_ID_SA13, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float64, subatt_desc_arr: Vec::new(), ..Default::default()}),
+        ], ..Default::default()}),
         (CMD_ID_CC3, LcsfCmdDesc {att_desc_arr: vec![
-            (CC3_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA3, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA5, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA6, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA7, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA8, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA9, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA10, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA11, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint64, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA12, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float32, subatt_desc_arr: Vec::new()}),
-            (CC3_ATT_ID_SA13, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float64, subatt_desc_arr: Vec::new()}),
-        ]}),
+            (CC3_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA3, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA5, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA6, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA7, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA8, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA9, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA10, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::String, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA11, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint64, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA12, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            (CC3_ATT_ID_SA13, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Float64, subatt_desc_arr: Vec::new(), ..Default::default()}),
+        ], ..Default::default()}),
         (CMD_ID_CC4, LcsfCmdDesc {att_desc_arr: vec![
-            (CC4_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
+            (CC4_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
             (CC4_ATT_ID_CA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                (CA1_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
-                (CA1_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new()}),
-                (CA1_ATT_ID_SA3, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new()}),
-            ]}),
+                (CA1_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
+                (CA1_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new(), ..Default::default()}),
+                (CA1_ATT_ID_SA3, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            ], ..Default::default()}),
             (CC4_ATT_ID_CA2, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                (CA2_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
+                (CA2_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
                 (CA2_ATT_ID_CA3, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                    (CA3_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
+                    (CA3_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
                     (CA3_ATT_ID_CA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                        (CA4_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new()}),
-                    ]}),
-                ]}),
-            ]}),
-        ]}),
+                        (CA4_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new(), ..Default::default()}),
+                    ], ..Default::default()}),
+                ], ..Default::default()}),
+            ], ..Default::default()}),
+        ], ..Default::default()}),
         (CMD_ID_CC5, LcsfCmdDesc {att_desc_arr: vec![
-            (CC5_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new()}),
+            (CC5_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new(), ..Default::default()}),
             (CC5_ATT_ID_CA5, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                (CA5_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
-                (CA5_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new()}),
-                (CA5_ATT_ID_SA3, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new()}),
-            ]}),
+                (CA5_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
+                (CA5_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new(), ..Default::default()}),
+                (CA5_ATT_ID_SA3, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            ], ..Default::default()}),
             (CC5_ATT_ID_CA6, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                (CA6_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
+                (CA6_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
                 (CA6_ATT_ID_CA7, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                    (CA7_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
+                    (CA7_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
                     (CA7_ATT_ID_CA8, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                        (CA8_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new()}),
-                    ]}),
-                ]}),
-            ]}),
-        ]}),
+                        (CA8_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new(), ..Default::default()}),
+                    ], ..Default::default()}),
+                ], ..Default::default()}),
+            ], ..Default::default()}),
+        ], ..Default::default()}),
         (CMD_ID_CC6, LcsfCmdDesc {att_desc_arr: vec![
-            (CC6_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new()}),
+            (CC6_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new(), ..Default::default()}),
             (CC6_ATT_ID_CA9, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                (CA9_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
-                (CA9_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new()}),
-                (CA9_ATT_ID_SA3, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new()}),
-            ]}),
+                (CA9_ATT_ID_SA1, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
+                (CA9_ATT_ID_SA2, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Uint16, subatt_desc_arr: Vec::new(), ..Default::default()}),
+                (CA9_ATT_ID_SA3, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint32, subatt_desc_arr: Vec::new(), ..Default::default()}),
+            ], ..Default::default()}),
             (CC6_ATT_ID_CA10, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                (CA10_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
+                (CA10_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
                 (CA10_ATT_ID_CA11, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                    (CA11_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new()}),
+                    (CA11_ATT_ID_SA1, LcsfAttDesc { is_optional: true, data_type: LcsfDataType::Uint8, subatt_desc_arr: Vec::new(), ..Default::default()}),
                     (CA11_ATT_ID_CA12, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::Subattributes, subatt_desc_arr: vec![
-                        (CA12_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new()}),
-                    ]}),
-                ]}),
-            ]}),
-        ]}),
-    ]};
+                        (CA12_ATT_ID_SA4, LcsfAttDesc { is_optional: false, data_type: LcsfDataType::ByteArray, subatt_desc_arr: Vec::new(), ..Default::default()}),
+                    ], ..Default::default()}),
+                ], ..Default::default()}),
+            ], ..Default::default()}),
+        ], ..Default::default()}),
+    ], ..Default::default()};
 }
 
 // Note: Unit tests will not be generated by Lcsf_Generator
@@ -1177,7 +1141,7 @@ mod tests {
                     payload: LcsfValidAttPayload::Data(vec![3]),
                 },
                 LcsfValidAtt {
-                    payload: LcsfValidAttPayload::Data(Vec::new()),
+                    payload: LcsfValidAttPayload::Absent,
                 },
                 LcsfValidAtt {
                     payload: LcsfValidAttPayload::Data(vec![0xef, 0x49, 0x02, 0x00]),
@@ -1229,7 +1193,7 @@ mod tests {
                     payload: LcsfValidAttPayload::Data(vec![0xef, 0x49, 0x02, 0x00]),
                 },
                 LcsfValidAtt {
-                    payload: LcsfValidAttPayload::Data(Vec::new()),
+                    payload: LcsfValidAttPayload::Absent,
                 },
                 LcsfValidAtt {
                     payload: LcsfValidAttPayload::Data(vec![0x50, 0x61, 0x75, 0x6c, 0x00]),
@@ -1262,14 +1226,14 @@ mod tests {
                             payload: LcsfValidAttPayload::Data(vec![0xd0, 0x07]),
                         },
                         LcsfValidAtt {
-                            payload: LcsfValidAttPayload::Data(Vec::new()),
+                            payload: LcsfValidAttPayload::Absent,
                         },
                     ]),
                 },
                 LcsfValidAtt {
                     payload: LcsfValidAttPayload::SubattArr(vec![
                         LcsfValidAtt {
-                            payload: LcsfValidAttPayload::Data(Vec::new()),
+                            payload: LcsfValidAttPayload::Absent,
                         },
                         LcsfValidAtt {
                             payload: LcsfValidAttPayload::SubattArr(vec![
@@ -1304,14 +1268,14 @@ mod tests {
                             payload: LcsfValidAttPayload::Data(vec![0xd0, 0x07]),
                         },
                         LcsfValidAtt {
-                            payload: LcsfValidAttPayload::Data(Vec::new()),
+                            payload: LcsfValidAttPayload::Absent,
                         },
                     ]),
                 },
                 LcsfValidAtt {
                     payload: LcsfValidAttPayload::SubattArr(vec![
                         LcsfValidAtt {
-                            payload: LcsfValidAttPayload::Data(Vec::new()),
+                            payload: LcsfValidAttPayload::Absent,
                         },
                         LcsfValidAtt {
                             payload: LcsfValidAttPayload::SubattArr(vec![
@@ -1463,7 +1427,7 @@ mod tests {
                     payload: LcsfValidAttPayload::Data(vec![4]),
                 },
                 LcsfValidAtt {
-                    payload: LcsfValidAttPayload::Data(Vec::new()),
+                    payload: LcsfValidAttPayload::Absent,
                 },
                 LcsfValidAtt {
                     payload: LcsfValidAttPayload::Data(vec![0xf0, 0x49, 0x02]),
@@ -1515,7 +1479,7 @@ mod tests {
                     payload: LcsfValidAttPayload::Data(vec![0xef, 0x49, 0x02]),
                 },
                 LcsfValidAtt {
-                    payload: LcsfValidAttPayload::Data(Vec::new()),
+                    payload: LcsfValidAttPayload::Absent,
                 },
                 LcsfValidAtt {
                     payload: LcsfValidAttPayload::Data(vec![0x50, 0x61, 0x75, 0x6c, 0x00]),
@@ -1548,14 +1512,14 @@ mod tests {
                             payload: LcsfValidAttPayload::Data(vec![0xd1, 0x07]),
                         },
                         LcsfValidAtt {
-                            payload: LcsfValidAttPayload::Data(Vec::new()),
+                            payload: LcsfValidAttPayload::Absent,
                         },
                     ]),
                 },
                 LcsfValidAtt {
                     payload: LcsfValidAttPayload::SubattArr(vec![
                         LcsfValidAtt {
-                            payload: LcsfValidAttPayload::Data(Vec::new()),
+                            payload: LcsfValidAttPayload::Absent,
                         },
                         LcsfValidAtt {
                             payload: LcsfValidAttPayload::SubattArr(vec![
@@ -1590,14 +1554,14 @@ mod tests {
                             payload: LcsfValidAttPayload::Data(vec![0xd0, 0x07]),
                         },
                         LcsfValidAtt {
-                            payload: LcsfValidAttPayload::Data(Vec::new()),
+                            payload: LcsfValidAttPayload::Absent,
                         },
                     ]),
                 },
                 LcsfValidAtt {
                     payload: LcsfValidAttPayload::SubattArr(vec![
                         LcsfValidAtt {
-                            payload: LcsfValidAttPayload::Data(Vec::new()),
+                            payload: LcsfValidAttPayload::Absent,
                         },
                         LcsfValidAtt {
                             payload: LcsfValidAttPayload::SubattArr(vec![
@@ -0,0 +1,1289 @@
+//! Hand-written test protocol covering the descriptor features the generator doesn't emit yet,
+//! with the payload conversions a generated abstraction layer would provide
+//!
+//! author: Jean-Roland Gosse
+//!
+//! This file is part of LCSF Stack Rust.
+//! Spec details at <https://jean-roland.github.io/LCSF_Doc/>
+//! You should have received a copy of the GNU Lesser General Public License
+//! along with this program. If not, see <https://www.gnu.org/licenses/>
+
+use crate::lcsf_lib::lcsf_validator;
+use lazy_static::lazy_static;
+use lcsf_validator::LcsfAttCardinality;
+use lcsf_validator::LcsfAttCond;
+use lcsf_validator::LcsfAttConstraints;
+use lcsf_validator::LcsfAttDesc;
+use lcsf_validator::LcsfAttGroup;
+use lcsf_validator::LcsfAttScale;
+use lcsf_validator::LcsfCmdDesc;
+use lcsf_validator::LcsfDataType;
+use lcsf_validator::LcsfElemType;
+use lcsf_validator::LcsfOneof;
+use lcsf_validator::LcsfProtDesc;
+use lcsf_validator::LcsfTimeRes;
+use lcsf_validator::LcsfValidAtt;
+use lcsf_validator::LcsfValidAttPayload;
+use lcsf_validator::LcsfValidCmd;
+use lcsf_validator::LcsfValidInfo;
+use std::time::Duration;
+use std::time::SystemTime;
+
+/// Test_Ext protocol id
+pub const PROT_ID: u16 = 0x56;
+
+// Command ids
+const CMD_ID_REPEATED: u16 = 0x01;
+const CMD_ID_ARRAY: u16 = 0x02;
+const CMD_ID_SCALED: u16 = 0x03;
+const CMD_ID_TIME: u16 = 0x04;
+const CMD_ID_ONEOF: u16 = 0x05;
+const CMD_ID_REQUIRED: u16 = 0x06;
+const CMD_ID_GROUP_A: u16 = 0x07;
+const CMD_ID_GROUP_B: u16 = 0x08;
+const CMD_ID_DEFAULT: u16 = 0x09;
+
+// Repeated attribute ids
+const REPEATED_ATT_ID_SA1: u16 = 0x01;
+const REPEATED_ATT_ID_CA1: u16 = 0x02;
+
+// Ca1 attribute ids
+const CA1_ATT_ID_SA2: u16 = 0x01;
+const CA1_ATT_ID_SA3: u16 = 0x02;
+
+// Array attribute ids
+const ARRAY_ATT_ID_SA1: u16 = 0x01;
+const ARRAY_ATT_ID_SA2: u16 = 0x02;
+
+// Scaled attribute ids
+const SCALED_ATT_ID_SA1: u16 = 0x01;
+const SCALED_ATT_ID_SA2: u16 = 0x02;
+
+// Time attribute ids
+const TIME_ATT_ID_SA1: u16 = 0x01;
+const TIME_ATT_ID_SA2: u16 = 0x02;
+
+// Oneof attribute ids
+const ONEOF_ATT_ID_CA1: u16 = 0x01;
+const ONEOF_ATT_ID_CA2: u16 = 0x02;
+
+// Oneof Ca1 attribute ids
+const ONEOF_CA1_ATT_ID_SA1: u16 = 0x01;
+const ONEOF_CA1_ATT_ID_SA2: u16 = 0x02;
+
+// Oneof Ca2 attribute ids
+const ONEOF_CA2_ATT_ID_SA3: u16 = 0x01;
+const ONEOF_CA2_ATT_ID_SA4: u16 = 0x02;
+
+// Required attribute ids
+const REQUIRED_ATT_ID_CA1: u16 = 0x01;
+
+// Required Ca1 attribute ids
+const REQUIRED_CA1_ATT_ID_SA1: u16 = 0x01;
+const REQUIRED_CA1_ATT_ID_SA2: u16 = 0x02;
+const REQUIRED_CA1_ATT_ID_SA3: u16 = 0x03;
+
+// Required Ca1 attribute values
+const REQUIRED_CA1_SA2_EXTENDED: u64 = 0xffff;
+
+// Pair group attribute ids
+const PAIR_ATT_ID_SA1: u16 = 0x01;
+const PAIR_ATT_ID_SA2: u16 = 0x02;
+
+// Group_b attribute ids
+const GROUP_B_ATT_ID_SA1: u16 = 0x01;
+const GROUP_B_ATT_ID_CA1: u16 = 0x02;
+
+// Default attribute ids
+const DEFAULT_ATT_ID_SA1: u16 = 0x01;
+const DEFAULT_ATT_ID_SA2: u16 = 0x02;
+const DEFAULT_ATT_ID_CA1: u16 = 0x03;
+
+// Default Ca1 attribute ids
+const DEFAULT_CA1_ATT_ID_SA3: u16 = 0x01;
+
+lazy_static! {
+    /// Test_Ext protocol descriptor
+    pub static ref PROT_DESC: LcsfProtDesc = LcsfProtDesc {
+        name: "TEST_EXT".into(),
+        group_arr: vec![LcsfAttGroup {
+            name: "PAIR".into(),
+            att_desc_arr: vec![
+                (
+                    PAIR_ATT_ID_SA1,
+                    LcsfAttDesc {
+                        name: "SA1".into(),
+                        data_type: LcsfDataType::Uint8,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    PAIR_ATT_ID_SA2,
+                    LcsfAttDesc {
+                        name: "SA2".into(),
+                        is_optional: true,
+                        data_type: LcsfDataType::Uint16,
+                        ..Default::default()
+                    },
+                ),
+            ],
+        }],
+        cmd_desc_arr: vec![
+            (
+                CMD_ID_REPEATED,
+                LcsfCmdDesc {
+                    name: "REPEATED".into(),
+                    att_desc_arr: vec![
+                        (
+                            REPEATED_ATT_ID_SA1,
+                            LcsfAttDesc {
+                                name: "SA1".into(),
+                                data_type: LcsfDataType::Uint16,
+                                cardinality: LcsfAttCardinality::Repeated {
+                                    min_count: Some(1),
+                                    max_count: Some(4),
+                                },
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            REPEATED_ATT_ID_CA1,
+                            LcsfAttDesc {
+                                name: "CA1".into(),
+                                is_optional: true,
+                                data_type: LcsfDataType::Subattributes,
+                                subatt_desc_arr: vec![
+                                    (
+                                        CA1_ATT_ID_SA2,
+                                        LcsfAttDesc {
+                                            name: "SA2".into(),
+                                            data_type: LcsfDataType::Uint8,
+                                            ..Default::default()
+                                        },
+                                    ),
+                                    (
+                                        CA1_ATT_ID_SA3,
+                                        LcsfAttDesc {
+                                            name: "SA3".into(),
+                                            is_optional: true,
+                                            data_type: LcsfDataType::Uint16,
+                                            ..Default::default()
+                                        },
+                                    ),
+                                ],
+                                cardinality: LcsfAttCardinality::Repeated {
+                                    min_count: None,
+                                    max_count: Some(8),
+                                },
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            ),
+            (
+                CMD_ID_ARRAY,
+                LcsfCmdDesc {
+                    name: "ARRAY".into(),
+                    att_desc_arr: vec![
+                        (
+                            ARRAY_ATT_ID_SA1,
+                            LcsfAttDesc {
+                                name: "SA1".into(),
+                                data_type: LcsfDataType::Array(LcsfElemType::Uint16),
+                                constraints: LcsfAttConstraints {
+                                    max_len: Some(4),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            ARRAY_ATT_ID_SA2,
+                            LcsfAttDesc {
+                                name: "SA2".into(),
+                                is_optional: true,
+                                data_type: LcsfDataType::Array(LcsfElemType::Float32),
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            ),
+            (
+                CMD_ID_SCALED,
+                LcsfCmdDesc {
+                    name: "SCALED".into(),
+                    att_desc_arr: vec![
+                        (
+                            SCALED_ATT_ID_SA1,
+                            LcsfAttDesc {
+                                name: "SA1".into(),
+                                data_type: LcsfDataType::Uint16,
+                                scale: Some(LcsfAttScale {
+                                    factor: 0.001,
+                                    offset: 0.0,
+                                    unit: "V".into(),
+                                }),
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            SCALED_ATT_ID_SA2,
+                            LcsfAttDesc {
+                                name: "SA2".into(),
+                                is_optional: true,
+                                data_type: LcsfDataType::Uint8,
+                                scale: Some(LcsfAttScale {
+                                    factor: 0.5,
+                                    offset: -40.0,
+                                    unit: "°C".into(),
+                                }),
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            ),
+            (
+                CMD_ID_TIME,
+                LcsfCmdDesc {
+                    name: "TIME".into(),
+                    att_desc_arr: vec![
+                        (
+                            TIME_ATT_ID_SA1,
+                            LcsfAttDesc {
+                                name: "SA1".into(),
+                                data_type: LcsfDataType::Timestamp {
+                                    res: LcsfTimeRes::Millis,
+                                    epoch: 1_600_000_000,
+                                },
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            TIME_ATT_ID_SA2,
+                            LcsfAttDesc {
+                                name: "SA2".into(),
+                                is_optional: true,
+                                data_type: LcsfDataType::Duration(LcsfTimeRes::Micros),
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            ),
+            (
+                CMD_ID_ONEOF,
+                LcsfCmdDesc {
+                    name: "ONEOF".into(),
+                    att_desc_arr: vec![
+                        (
+                            ONEOF_ATT_ID_CA1,
+                            LcsfAttDesc {
+                                name: "CA1".into(),
+                                data_type: LcsfDataType::Subattributes,
+                                subatt_desc_arr: vec![
+                                    (
+                                        ONEOF_CA1_ATT_ID_SA1,
+                                        LcsfAttDesc {
+                                            name: "SA1".into(),
+                                            is_optional: true,
+                                            data_type: LcsfDataType::Uint8,
+                                            ..Default::default()
+                                        },
+                                    ),
+                                    (
+                                        ONEOF_CA1_ATT_ID_SA2,
+                                        LcsfAttDesc {
+                                            name: "SA2".into(),
+                                            is_optional: true,
+                                            data_type: LcsfDataType::String,
+                                            ..Default::default()
+                                        },
+                                    ),
+                                ],
+                                oneof: LcsfOneof::ExactlyOne,
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            ONEOF_ATT_ID_CA2,
+                            LcsfAttDesc {
+                                name: "CA2".into(),
+                                data_type: LcsfDataType::Subattributes,
+                                subatt_desc_arr: vec![
+                                    (
+                                        ONEOF_CA2_ATT_ID_SA3,
+                                        LcsfAttDesc {
+                                            name: "SA3".into(),
+                                            is_optional: true,
+                                            data_type: LcsfDataType::Uint16,
+                                            ..Default::default()
+                                        },
+                                    ),
+                                    (
+                                        ONEOF_CA2_ATT_ID_SA4,
+                                        LcsfAttDesc {
+                                            name: "SA4".into(),
+                                            is_optional: true,
+                                            data_type: LcsfDataType::Uint32,
+                                            ..Default::default()
+                                        },
+                                    ),
+                                ],
+                                oneof: LcsfOneof::AtMostOne,
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            ),
+            (
+                CMD_ID_REQUIRED,
+                LcsfCmdDesc {
+                    name: "REQUIRED".into(),
+                    att_desc_arr: vec![(
+                        REQUIRED_ATT_ID_CA1,
+                        LcsfAttDesc {
+                            name: "CA1".into(),
+                            data_type: LcsfDataType::Subattributes,
+                            subatt_desc_arr: vec![
+                                (
+                                    REQUIRED_CA1_ATT_ID_SA1,
+                                    LcsfAttDesc {
+                                        name: "SA1".into(),
+                                        data_type: LcsfDataType::Uint8,
+                                        ..Default::default()
+                                    },
+                                ),
+                                (
+                                    REQUIRED_CA1_ATT_ID_SA2,
+                                    LcsfAttDesc {
+                                        name: "SA2".into(),
+                                        data_type: LcsfDataType::Uint16,
+                                        ..Default::default()
+                                    },
+                                ),
+                                (
+                                    REQUIRED_CA1_ATT_ID_SA3,
+                                    LcsfAttDesc {
+                                        name: "SA3".into(),
+                                        is_optional: true,
+                                        data_type: LcsfDataType::Uint32,
+                                        required_if: Some(LcsfAttCond::Equals(
+                                            REQUIRED_CA1_ATT_ID_SA2,
+                                            REQUIRED_CA1_SA2_EXTENDED,
+                                        )),
+                                        ..Default::default()
+                                    },
+                                ),
+                            ],
+                            ..Default::default()
+                        },
+                    )],
+                    ..Default::default()
+                },
+            ),
+            (
+                CMD_ID_GROUP_A,
+                LcsfCmdDesc {
+                    name: "GROUP_A".into(),
+                    group: "PAIR".into(),
+                    ..Default::default()
+                },
+            ),
+            (
+                CMD_ID_GROUP_B,
+                LcsfCmdDesc {
+                    name: "GROUP_B".into(),
+                    att_desc_arr: vec![
+                        (
+                            GROUP_B_ATT_ID_SA1,
+                            LcsfAttDesc {
+                                name: "SA1".into(),
+                                data_type: LcsfDataType::Uint8,
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            GROUP_B_ATT_ID_CA1,
+                            LcsfAttDesc {
+                                name: "CA1".into(),
+                                data_type: LcsfDataType::Subattributes,
+                                group: "PAIR".into(),
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            ),
+            (
+                CMD_ID_DEFAULT,
+                LcsfCmdDesc {
+                    name: "DEFAULT".into(),
+                    att_desc_arr: vec![
+                        (
+                            DEFAULT_ATT_ID_SA1,
+                            LcsfAttDesc {
+                                name: "SA1".into(),
+                                data_type: LcsfDataType::Uint8,
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            DEFAULT_ATT_ID_SA2,
+                            LcsfAttDesc {
+                                name: "SA2".into(),
+                                is_optional: true,
+                                data_type: LcsfDataType::Uint16,
+                                default_value: Some(vec![0x64]),
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            DEFAULT_ATT_ID_CA1,
+                            LcsfAttDesc {
+                                name: "CA1".into(),
+                                is_optional: true,
+                                data_type: LcsfDataType::Subattributes,
+                                subatt_desc_arr: vec![(
+                                    DEFAULT_CA1_ATT_ID_SA3,
+                                    LcsfAttDesc {
+                                        name: "SA3".into(),
+                                        is_optional: true,
+                                        data_type: LcsfDataType::Uint8,
+                                        default_value: Some(vec![0x07]),
+                                        ..Default::default()
+                                    },
+                                )],
+                                ..Default::default()
+                            },
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            ),
+        ],
+        ..Default::default()
+    }
+    .expand_groups();
+}
+
+/// Return an attribute descriptor from its path
+///
+/// cmd_id: command id value
+///
+/// path: attribute id path, from the command attributes
+fn att_desc(cmd_id: u16, path: &[u16]) -> &'static LcsfAttDesc {
+    let (first_id, subatt_path) = path.split_first().unwrap();
+    let cmd_desc = PROT_DESC.get_cmd_desc(cmd_id).unwrap();
+    let mut att_desc = cmd_desc.get_att_desc(*first_id).unwrap();
+    for att_id in subatt_path {
+        att_desc = att_desc.get_subatt_desc(*att_id).unwrap();
+    }
+    att_desc
+}
+
+/// Return an absent attribute
+fn absent_att() -> LcsfValidAtt {
+    LcsfValidAtt {
+        payload: LcsfValidAttPayload::Absent,
+    }
+}
+
+/// Return the items of a repeated attribute
+///
+/// att: repeated attribute reference
+fn get_items(att: &LcsfValidAtt) -> &[LcsfValidAtt] {
+    match &att.payload {
+        LcsfValidAttPayload::List(item_arr) => item_arr,
+        _ => &[],
+    }
+}
+
+/// Repeated command payload
+#[derive(Debug, PartialEq, Clone)]
+struct RepeatedPayload {
+    sa1: Vec<u16>,
+    ca1: Vec<Ca1Payload>,
+}
+
+/// Repeated command CA1 item payload
+#[derive(Debug, PartialEq, Clone)]
+struct Ca1Payload {
+    sa2: u8,
+    sa3: Option<u16>,
+}
+
+fn repeated_get_data(valid_cmd: &LcsfValidCmd) -> RepeatedPayload {
+    let sa1_type = att_desc(CMD_ID_REPEATED, &[REPEATED_ATT_ID_SA1]).data_type;
+    let sa2_type = att_desc(CMD_ID_REPEATED, &[REPEATED_ATT_ID_CA1, CA1_ATT_ID_SA2]).data_type;
+    let sa3_type = att_desc(CMD_ID_REPEATED, &[REPEATED_ATT_ID_CA1, CA1_ATT_ID_SA3]).data_type;
+    RepeatedPayload {
+        sa1: get_items(&valid_cmd.att_arr[0])
+            .iter()
+            .map(|item| item.as_u16(sa1_type).unwrap())
+            .collect(),
+        ca1: get_items(&valid_cmd.att_arr[1])
+            .iter()
+            .filter_map(|item| match &item.payload {
+                LcsfValidAttPayload::SubattArr(subatt_arr) => Some(Ca1Payload {
+                    sa2: subatt_arr[0].as_u8(sa2_type).unwrap(),
+                    sa3: subatt_arr[1].as_u16(sa3_type).ok(),
+                }),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+fn repeated_fill_att(payload: &RepeatedPayload) -> Vec<LcsfValidAtt> {
+    let sa1_type = att_desc(CMD_ID_REPEATED, &[REPEATED_ATT_ID_SA1]).data_type;
+    let sa2_type = att_desc(CMD_ID_REPEATED, &[REPEATED_ATT_ID_CA1, CA1_ATT_ID_SA2]).data_type;
+    let sa3_type = att_desc(CMD_ID_REPEATED, &[REPEATED_ATT_ID_CA1, CA1_ATT_ID_SA3]).data_type;
+    vec![
+        LcsfValidAtt {
+            payload: LcsfValidAttPayload::List(
+                payload
+                    .sa1
+                    .iter()
+                    .map(|sa1| LcsfValidAtt::from_u16(sa1_type, *sa1).unwrap())
+                    .collect(),
+            ),
+        },
+        LcsfValidAtt {
+            payload: LcsfValidAttPayload::List(
+                payload
+                    .ca1
+                    .iter()
+                    .map(|ca1| LcsfValidAtt {
+                        payload: LcsfValidAttPayload::SubattArr(vec![
+                            LcsfValidAtt::from_u8(sa2_type, ca1.sa2).unwrap(),
+                            match ca1.sa3 {
+                                Some(sa3) => LcsfValidAtt::from_u16(sa3_type, sa3).unwrap(),
+                                None => absent_att(),
+                            },
+                        ]),
+                    })
+                    .collect(),
+            ),
+        },
+    ]
+}
+
+/// Array command payload
+#[derive(Debug, PartialEq, Clone)]
+struct ArrayPayload {
+    sa1: Vec<u16>,
+    sa2: Option<Vec<f32>>,
+}
+
+fn array_get_data(valid_cmd: &LcsfValidCmd) -> ArrayPayload {
+    let sa1_type = att_desc(CMD_ID_ARRAY, &[ARRAY_ATT_ID_SA1]).data_type;
+    let sa2_type = att_desc(CMD_ID_ARRAY, &[ARRAY_ATT_ID_SA2]).data_type;
+    ArrayPayload {
+        sa1: valid_cmd.att_arr[0].as_array(sa1_type).unwrap(),
+        sa2: valid_cmd.att_arr[1].as_array(sa2_type).ok(),
+    }
+}
+
+fn array_fill_att(payload: &ArrayPayload) -> Vec<LcsfValidAtt> {
+    let sa1_type = att_desc(CMD_ID_ARRAY, &[ARRAY_ATT_ID_SA1]).data_type;
+    let sa2_type = att_desc(CMD_ID_ARRAY, &[ARRAY_ATT_ID_SA2]).data_type;
+    vec![
+        LcsfValidAtt::from_array(sa1_type, &payload.sa1).unwrap(),
+        match &payload.sa2 {
+            Some(sa2) => LcsfValidAtt::from_array(sa2_type, sa2).unwrap(),
+            None => absent_att(),
+        },
+    ]
+}
+
+/// Scaled command payload, physical values
+#[derive(Debug, PartialEq, Clone)]
+struct ScaledPayload {
+    sa1: f64,
+    sa2: Option<f64>,
+}
+
+fn scaled_get_data(valid_cmd: &LcsfValidCmd) -> ScaledPayload {
+    let sa1_desc = att_desc(CMD_ID_SCALED, &[SCALED_ATT_ID_SA1]);
+    let sa2_desc = att_desc(CMD_ID_SCALED, &[SCALED_ATT_ID_SA2]);
+    ScaledPayload {
+        sa1: valid_cmd.att_arr[0]
+            .as_scaled(sa1_desc.data_type, sa1_desc.scale.as_ref().unwrap())
+            .unwrap(),
+        sa2: valid_cmd.att_arr[1]
+            .as_scaled(sa2_desc.data_type, sa2_desc.scale.as_ref().unwrap())
+            .ok(),
+    }
+}
+
+fn scaled_fill_att(payload: &ScaledPayload) -> Vec<LcsfValidAtt> {
+    let sa1_desc = att_desc(CMD_ID_SCALED, &[SCALED_ATT_ID_SA1]);
+    let sa2_desc = att_desc(CMD_ID_SCALED, &[SCALED_ATT_ID_SA2]);
+    vec![
+        LcsfValidAtt::from_scaled(
+            sa1_desc.data_type,
+            sa1_desc.scale.as_ref().unwrap(),
+            payload.sa1,
+        )
+        .unwrap(),
+        match payload.sa2 {
+            Some(sa2) => {
+                LcsfValidAtt::from_scaled(sa2_desc.data_type, sa2_desc.scale.as_ref().unwrap(), sa2)
+                    .unwrap()
+            }
+            None => absent_att(),
+        },
+    ]
+}
+
+/// Time command payload
+#[derive(Debug, PartialEq, Clone)]
+struct TimePayload {
+    sa1: SystemTime,
+    sa2: Option<Duration>,
+}
+
+fn time_get_data(valid_cmd: &LcsfValidCmd) -> TimePayload {
+    let sa1_type = att_desc(CMD_ID_TIME, &[TIME_ATT_ID_SA1]).data_type;
+    let sa2_type = att_desc(CMD_ID_TIME, &[TIME_ATT_ID_SA2]).data_type;
+    TimePayload {
+        sa1: valid_cmd.att_arr[0].as_timestamp(sa1_type).unwrap(),
+        sa2: valid_cmd.att_arr[1].as_duration(sa2_type).ok(),
+    }
+}
+
+fn time_fill_att(payload: &TimePayload) -> Vec<LcsfValidAtt> {
+    let sa1_type = att_desc(CMD_ID_TIME, &[TIME_ATT_ID_SA1]).data_type;
+    let sa2_type = att_desc(CMD_ID_TIME, &[TIME_ATT_ID_SA2]).data_type;
+    vec![
+        LcsfValidAtt::from_timestamp(sa1_type, payload.sa1).unwrap(),
+        match payload.sa2 {
+            Some(sa2) => LcsfValidAtt::from_duration(sa2_type, sa2).unwrap(),
+            None => absent_att(),
+        },
+    ]
+}
+
+/// Oneof command CA1 choice payload
+#[derive(Debug, PartialEq, Clone)]
+enum OneofCa1Payload {
+    Sa1(u8),
+    Sa2(String),
+}
+
+/// Oneof command CA2 choice payload
+#[derive(Debug, PartialEq, Clone)]
+enum OneofCa2Payload {
+    Sa3(u16),
+    Sa4(u32),
+}
+
+/// Oneof command payload
+#[derive(Debug, PartialEq, Clone)]
+struct OneofPayload {
+    ca1: OneofCa1Payload,
+    ca2: Option<OneofCa2Payload>,
+}
+
+fn oneof_get_data(valid_cmd: &LcsfValidCmd) -> OneofPayload {
+    let sa1_type = att_desc(CMD_ID_ONEOF, &[ONEOF_ATT_ID_CA1, ONEOF_CA1_ATT_ID_SA1]).data_type;
+    let sa2_type = att_desc(CMD_ID_ONEOF, &[ONEOF_ATT_ID_CA1, ONEOF_CA1_ATT_ID_SA2]).data_type;
+    let sa3_type = att_desc(CMD_ID_ONEOF, &[ONEOF_ATT_ID_CA2, ONEOF_CA2_ATT_ID_SA3]).data_type;
+    let sa4_type = att_desc(CMD_ID_ONEOF, &[ONEOF_ATT_ID_CA2, ONEOF_CA2_ATT_ID_SA4]).data_type;
+    let (LcsfValidAttPayload::SubattArr(ca1_arr), LcsfValidAttPayload::SubattArr(ca2_arr)) =
+        (&valid_cmd.att_arr[0].payload, &valid_cmd.att_arr[1].payload)
+    else {
+        panic!("Oneof attributes have sub-attributes");
+    };
+    // Exactly one choice is present
+    let ca1 = match ca1_arr[0].as_u8(sa1_type) {
+        Ok(sa1) => OneofCa1Payload::Sa1(sa1),
+        Err(_) => OneofCa1Payload::Sa2(ca1_arr[1].as_str(sa2_type).unwrap().into()),
+    };
+    // At most one choice is present
+    let ca2 = match (ca2_arr[0].as_u16(sa3_type), ca2_arr[1].as_u32(sa4_type)) {
+        (Ok(sa3), _) => Some(OneofCa2Payload::Sa3(sa3)),
+        (_, Ok(sa4)) => Some(OneofCa2Payload::Sa4(sa4)),
+        _ => None,
+    };
+    OneofPayload { ca1, ca2 }
+}
+
+fn oneof_fill_att(payload: &OneofPayload) -> Vec<LcsfValidAtt> {
+    let sa1_type = att_desc(CMD_ID_ONEOF, &[ONEOF_ATT_ID_CA1, ONEOF_CA1_ATT_ID_SA1]).data_type;
+    let sa2_type = att_desc(CMD_ID_ONEOF, &[ONEOF_ATT_ID_CA1, ONEOF_CA1_ATT_ID_SA2]).data_type;
+    let sa3_type = att_desc(CMD_ID_ONEOF, &[ONEOF_ATT_ID_CA2, ONEOF_CA2_ATT_ID_SA3]).data_type;
+    let sa4_type = att_desc(CMD_ID_ONEOF, &[ONEOF_ATT_ID_CA2, ONEOF_CA2_ATT_ID_SA4]).data_type;
+    let ca1_arr = match &payload.ca1 {
+        OneofCa1Payload::Sa1(sa1) => {
+            vec![LcsfValidAtt::from_u8(sa1_type, *sa1).unwrap(), absent_att()]
+        }
+        OneofCa1Payload::Sa2(sa2) => {
+            vec![absent_att(), LcsfValidAtt::from_str(sa2_type, sa2).unwrap()]
+        }
+    };
+    let ca2_arr = match payload.ca2 {
+        Some(OneofCa2Payload::Sa3(sa3)) => {
+            vec![LcsfValidAtt::from_u16(sa3_type, sa3).unwrap(), absent_att()]
+        }
+        Some(OneofCa2Payload::Sa4(sa4)) => {
+            vec![absent_att(), LcsfValidAtt::from_u32(sa4_type, sa4).unwrap()]
+        }
+        None => vec![absent_att(), absent_att()],
+    };
+    vec![
+        LcsfValidAtt {
+            payload: LcsfValidAttPayload::SubattArr(ca1_arr),
+        },
+        LcsfValidAtt {
+            payload: LcsfValidAttPayload::SubattArr(ca2_arr),
+        },
+    ]
+}
+
+/// Required command payload
+#[derive(Debug, PartialEq, Clone)]
+struct RequiredPayload {
+    sa1: u8,
+    sa2: u16,
+    sa3: Option<u32>,
+}
+
+fn required_get_data(valid_cmd: &LcsfValidCmd) -> RequiredPayload {
+    let path = [REQUIRED_ATT_ID_CA1, REQUIRED_CA1_ATT_ID_SA1];
+    let sa1_type = att_desc(CMD_ID_REQUIRED, &path).data_type;
+    let path = [REQUIRED_ATT_ID_CA1, REQUIRED_CA1_ATT_ID_SA2];
+    let sa2_type = att_desc(CMD_ID_REQUIRED, &path).data_type;
+    let path = [REQUIRED_ATT_ID_CA1, REQUIRED_CA1_ATT_ID_SA3];
+    let sa3_type = att_desc(CMD_ID_REQUIRED, &path).data_type;
+    let LcsfValidAttPayload::SubattArr(ca1_arr) = &valid_cmd.att_arr[0].payload else {
+        panic!("Required attribute has sub-attributes");
+    };
+    RequiredPayload {
+        sa1: ca1_arr[0].as_u8(sa1_type).unwrap(),
+        sa2: ca1_arr[1].as_u16(sa2_type).unwrap(),
+        sa3: ca1_arr[2].as_u32(sa3_type).ok(),
+    }
+}
+
+fn required_fill_att(payload: &RequiredPayload) -> Vec<LcsfValidAtt> {
+    let path = [REQUIRED_ATT_ID_CA1, REQUIRED_CA1_ATT_ID_SA1];
+    let sa1_type = att_desc(CMD_ID_REQUIRED, &path).data_type;
+    let path = [REQUIRED_ATT_ID_CA1, REQUIRED_CA1_ATT_ID_SA2];
+    let sa2_type = att_desc(CMD_ID_REQUIRED, &path).data_type;
+    let path = [REQUIRED_ATT_ID_CA1, REQUIRED_CA1_ATT_ID_SA3];
+    let sa3_type = att_desc(CMD_ID_REQUIRED, &path).data_type;
+    vec![LcsfValidAtt {
+        payload: LcsfValidAttPayload::SubattArr(vec![
+            LcsfValidAtt::from_u8(sa1_type, payload.sa1).unwrap(),
+            LcsfValidAtt::from_u16(sa2_type, payload.sa2).unwrap(),
+            match payload.sa3 {
+                Some(sa3) => LcsfValidAtt::from_u32(sa3_type, sa3).unwrap(),
+                None => absent_att(),
+            },
+        ]),
+    }]
+}
+
+/// Pair group payload, shared by the commands and attributes using the group
+#[derive(Debug, PartialEq, Clone)]
+struct PairPayload {
+    sa1: u8,
+    sa2: Option<u16>,
+}
+
+/// Group_b command payload
+#[derive(Debug, PartialEq, Clone)]
+struct GroupBPayload {
+    sa1: u8,
+    ca1: PairPayload,
+}
+
+/// Retrieve a pair group payload
+///
+/// group_desc_arr: group attribute descriptors, as expanded in the command
+///
+/// att_arr: group attributes reference
+fn pair_get_data(group_desc_arr: &[(u16, LcsfAttDesc)], att_arr: &[LcsfValidAtt]) -> PairPayload {
+    PairPayload {
+        sa1: att_arr[0].as_u8(group_desc_arr[0].1.data_type).unwrap(),
+        sa2: att_arr[1].as_u16(group_desc_arr[1].1.data_type).ok(),
+    }
+}
+
+/// Fill the attributes of a pair group payload
+///
+/// group_desc_arr: group attribute descriptors, as expanded in the command
+///
+/// payload: pair group payload reference
+fn pair_fill_att(
+    group_desc_arr: &[(u16, LcsfAttDesc)],
+    payload: &PairPayload,
+) -> Vec<LcsfValidAtt> {
+    vec![
+        LcsfValidAtt::from_u8(group_desc_arr[0].1.data_type, payload.sa1).unwrap(),
+        match payload.sa2 {
+            Some(sa2) => LcsfValidAtt::from_u16(group_desc_arr[1].1.data_type, sa2).unwrap(),
+            None => absent_att(),
+        },
+    ]
+}
+
+fn group_a_get_data(valid_cmd: &LcsfValidCmd) -> PairPayload {
+    let cmd_desc = PROT_DESC.get_cmd_desc(CMD_ID_GROUP_A).unwrap();
+    pair_get_data(&cmd_desc.att_desc_arr, &valid_cmd.att_arr)
+}
+
+fn group_a_fill_att(payload: &PairPayload) -> Vec<LcsfValidAtt> {
+    let cmd_desc = PROT_DESC.get_cmd_desc(CMD_ID_GROUP_A).unwrap();
+    pair_fill_att(&cmd_desc.att_desc_arr, payload)
+}
+
+fn group_b_get_data(valid_cmd: &LcsfValidCmd) -> GroupBPayload {
+    let sa1_type = att_desc(CMD_ID_GROUP_B, &[GROUP_B_ATT_ID_SA1]).data_type;
+    let ca1_desc = att_desc(CMD_ID_GROUP_B, &[GROUP_B_ATT_ID_CA1]);
+    let LcsfValidAttPayload::SubattArr(ca1_arr) = &valid_cmd.att_arr[1].payload else {
+        panic!("Group attribute has sub-attributes");
+    };
+    GroupBPayload {
+        sa1: valid_cmd.att_arr[0].as_u8(sa1_type).unwrap(),
+        ca1: pair_get_data(&ca1_desc.subatt_desc_arr, ca1_arr),
+    }
+}
+
+fn group_b_fill_att(payload: &GroupBPayload) -> Vec<LcsfValidAtt> {
+    let sa1_type = att_desc(CMD_ID_GROUP_B, &[GROUP_B_ATT_ID_SA1]).data_type;
+    let ca1_desc = att_desc(CMD_ID_GROUP_B, &[GROUP_B_ATT_ID_CA1]);
+    vec![
+        LcsfValidAtt::from_u8(sa1_type, payload.sa1).unwrap(),
+        LcsfValidAtt {
+            payload: LcsfValidAttPayload::SubattArr(pair_fill_att(
+                &ca1_desc.subatt_desc_arr,
+                &payload.ca1,
+            )),
+        },
+    ]
+}
+
+/// Default command payload, attributes filled with their default value on reception are
+/// reported absent
+#[derive(Debug, PartialEq, Clone)]
+struct DefaultPayload {
+    sa1: u8,
+    sa2: Option<u16>,
+    ca1: Option<DefaultCa1Payload>,
+}
+
+/// Default command CA1 payload
+#[derive(Debug, PartialEq, Clone)]
+struct DefaultCa1Payload {
+    sa3: Option<u8>,
+}
+
+fn default_get_data(valid_cmd: &LcsfValidCmd, valid_info: &LcsfValidInfo) -> DefaultPayload {
+    let sa1_type = att_desc(CMD_ID_DEFAULT, &[DEFAULT_ATT_ID_SA1]).data_type;
+    let sa2_type = att_desc(CMD_ID_DEFAULT, &[DEFAULT_ATT_ID_SA2]).data_type;
+    let sa3_path = [DEFAULT_ATT_ID_CA1, DEFAULT_CA1_ATT_ID_SA3];
+    let sa3_type = att_desc(CMD_ID_DEFAULT, &sa3_path).data_type;
+    // Defaulted attributes weren't received
+    let is_here = |path: &[u16]| {
+        !valid_info
+            .default_att_arr
+            .iter()
+            .any(|att_path| att_path == path)
+    };
+    DefaultPayload {
+        sa1: valid_cmd.att_arr[0].as_u8(sa1_type).unwrap(),
+        sa2: valid_cmd.att_arr[1]
+            .as_u16(sa2_type)
+            .ok()
+            .filter(|_| is_here(&[DEFAULT_ATT_ID_SA2])),
+        ca1: match &valid_cmd.att_arr[2].payload {
+            LcsfValidAttPayload::SubattArr(ca1_arr) => Some(DefaultCa1Payload {
+                sa3: ca1_arr[0]
+                    .as_u8(sa3_type)
+                    .ok()
+                    .filter(|_| is_here(&sa3_path)),
+            }),
+            _ => None,
+        },
+    }
+}
+
+fn default_fill_att(payload: &DefaultPayload) -> Vec<LcsfValidAtt> {
+    let sa1_type = att_desc(CMD_ID_DEFAULT, &[DEFAULT_ATT_ID_SA1]).data_type;
+    let sa2_type = att_desc(CMD_ID_DEFAULT, &[DEFAULT_ATT_ID_SA2]).data_type;
+    let sa3_path = [DEFAULT_ATT_ID_CA1, DEFAULT_CA1_ATT_ID_SA3];
+    let sa3_type = att_desc(CMD_ID_DEFAULT, &sa3_path).data_type;
+    vec![
+        LcsfValidAtt::from_u8(sa1_type, payload.sa1).unwrap(),
+        match payload.sa2 {
+            Some(sa2) => LcsfValidAtt::from_u16(sa2_type, sa2).unwrap(),
+            None => absent_att(),
+        },
+        match &payload.ca1 {
+            Some(ca1) => LcsfValidAtt {
+                payload: LcsfValidAttPayload::SubattArr(vec![match ca1.sa3 {
+                    Some(sa3) => LcsfValidAtt::from_u8(sa3_type, sa3).unwrap(),
+                    None => absent_att(),
+                }]),
+            },
+            None => absent_att(),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lcsf_lib::lcsf_transcoder;
+    use lcsf_transcoder::LcsfModeEnum;
+    use lcsf_validator::LcsfRole;
+    use lcsf_validator::LcsfValidateError;
+    use lcsf_validator::LcsfValidateErrorEnum;
+    use std::collections::HashMap;
+    use std::time::UNIX_EPOCH;
+
+    /// Encode a valid command into a small mode buffer
+    fn encode(valid_cmd: &LcsfValidCmd) -> Result<Vec<u8>, LcsfValidateError> {
+        let prot_desc_map = HashMap::from([(PROT_ID, &*PROT_DESC)]);
+        let cmd_desc = PROT_DESC.get_cmd_desc(valid_cmd.cmd_id).unwrap();
+        let raw_msg = lcsf_validator::encode_valid_as(
+            &prot_desc_map,
+            LcsfRole::Any,
+            PROT_ID,
+            cmd_desc,
+            valid_cmd,
+        )?;
+        Ok(lcsf_transcoder::encode_buff(LcsfModeEnum::Small, &raw_msg))
+    }
+
+    /// Decode and validate a small mode buffer, with the command info
+    fn decode_with_info(buff: &[u8]) -> Result<(LcsfValidCmd, LcsfValidInfo), LcsfValidateError> {
+        let prot_desc_map = HashMap::from([(PROT_ID, &*PROT_DESC)]);
+        let raw_msg = lcsf_transcoder::decode_buff(LcsfModeEnum::Small, buff).unwrap();
+        let (valid_cmd, prot_id, valid_info) =
+            lcsf_validator::validate_msg(&prot_desc_map, &raw_msg)?;
+        assert_eq!(prot_id, PROT_ID);
+        Ok((valid_cmd, valid_info))
+    }
+
+    /// Decode and validate a small mode buffer
+    fn decode(buff: &[u8]) -> Result<LcsfValidCmd, LcsfValidateError> {
+        decode_with_info(buff).map(|(valid_cmd, _)| valid_cmd)
+    }
+
+    #[test]
+    fn test_desc() {
+        assert_eq!(PROT_DESC.check(LcsfModeEnum::Small), Ok(()));
+    }
+
+    #[test]
+    fn test_repeated() {
+        let payload = RepeatedPayload {
+            sa1: vec![1000, 5],
+            ca1: vec![
+                Ca1Payload {
+                    sa2: 1,
+                    sa3: Some(16),
+                },
+                Ca1Payload { sa2: 2, sa3: None },
+            ],
+        };
+        let mut valid_cmd = LcsfValidCmd {
+            cmd_id: CMD_ID_REPEATED,
+            att_arr: repeated_fill_att(&payload),
+        };
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![
+                0x56, 0x01, 0x04, 0x01, 0x02, 0xe8, 0x03, 0x01, 0x01, 0x05, 0x82, 0x02, 0x01, 0x01,
+                0x01, 0x02, 0x01, 0x10, 0x82, 0x01, 0x01, 0x01, 0x02,
+            ]
+        );
+        assert_eq!(repeated_get_data(&decode(&buff).unwrap()), payload);
+        // Test count limits
+        valid_cmd.att_arr = repeated_fill_att(&RepeatedPayload {
+            sa1: Vec::new(),
+            ca1: Vec::new(),
+        });
+        let err = encode(&valid_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::MissMandatoryAtt);
+        valid_cmd.att_arr = repeated_fill_att(&RepeatedPayload {
+            sa1: vec![1; 5],
+            ca1: Vec::new(),
+        });
+        let err = encode(&valid_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongAttCount);
+    }
+
+    #[test]
+    fn test_array() {
+        let mut payload = ArrayPayload {
+            sa1: vec![0x1234, 5],
+            sa2: Some(vec![1.5, -2.0]),
+        };
+        let mut valid_cmd = LcsfValidCmd {
+            cmd_id: CMD_ID_ARRAY,
+            att_arr: array_fill_att(&payload),
+        };
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![
+                0x56, 0x02, 0x02, 0x01, 0x04, 0x34, 0x12, 0x05, 0x00, 0x02, 0x08, 0x00, 0x00, 0xc0,
+                0x3f, 0x00, 0x00, 0x00, 0xc0,
+            ]
+        );
+        assert_eq!(array_get_data(&decode(&buff).unwrap()), payload);
+        payload.sa2 = None;
+        valid_cmd.att_arr = array_fill_att(&payload);
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![0x56, 0x02, 0x01, 0x01, 0x04, 0x34, 0x12, 0x05, 0x00]
+        );
+        assert_eq!(array_get_data(&decode(&buff).unwrap()), payload);
+        // Test length limit
+        payload.sa1 = vec![1; 5];
+        valid_cmd.att_arr = array_fill_att(&payload);
+        let err = encode(&valid_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::AttOutOfRange);
+    }
+
+    #[test]
+    fn test_scaled() {
+        let mut payload = ScaledPayload {
+            sa1: 3.5,
+            sa2: Some(21.5),
+        };
+        let mut valid_cmd = LcsfValidCmd {
+            cmd_id: CMD_ID_SCALED,
+            att_arr: scaled_fill_att(&payload),
+        };
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![0x56, 0x03, 0x02, 0x01, 0x02, 0xac, 0x0d, 0x02, 0x01, 0x7b]
+        );
+        assert_eq!(scaled_get_data(&decode(&buff).unwrap()), payload);
+        payload.sa2 = None;
+        valid_cmd.att_arr = scaled_fill_att(&payload);
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(buff, vec![0x56, 0x03, 0x01, 0x01, 0x02, 0xac, 0x0d]);
+        assert_eq!(scaled_get_data(&decode(&buff).unwrap()), payload);
+    }
+
+    #[test]
+    fn test_time() {
+        let epoch = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        let mut payload = TimePayload {
+            sa1: epoch + Duration::from_millis(0x1234),
+            sa2: Some(Duration::from_micros(1500)),
+        };
+        let mut valid_cmd = LcsfValidCmd {
+            cmd_id: CMD_ID_TIME,
+            att_arr: time_fill_att(&payload),
+        };
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![
+                0x56, 0x04, 0x02, 0x01, 0x02, 0x34, 0x12, 0x02, 0x02, 0xdc, 0x05
+            ]
+        );
+        assert_eq!(time_get_data(&decode(&buff).unwrap()), payload);
+        payload.sa2 = None;
+        valid_cmd.att_arr = time_fill_att(&payload);
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(buff, vec![0x56, 0x04, 0x01, 0x01, 0x02, 0x34, 0x12]);
+        assert_eq!(time_get_data(&decode(&buff).unwrap()), payload);
+    }
+
+    #[test]
+    fn test_oneof() {
+        let mut payload = OneofPayload {
+            ca1: OneofCa1Payload::Sa2("Bob".into()),
+            ca2: Some(OneofCa2Payload::Sa4(0x1234)),
+        };
+        let mut valid_cmd = LcsfValidCmd {
+            cmd_id: CMD_ID_ONEOF,
+            att_arr: oneof_fill_att(&payload),
+        };
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![
+                0x56, 0x05, 0x02, 0x81, 0x01, 0x02, 0x04, 0x42, 0x6f, 0x62, 0x00, 0x82, 0x01, 0x02,
+                0x02, 0x34, 0x12,
+            ]
+        );
+        assert_eq!(oneof_get_data(&decode(&buff).unwrap()), payload);
+        payload = OneofPayload {
+            ca1: OneofCa1Payload::Sa1(5),
+            ca2: None,
+        };
+        valid_cmd.att_arr = oneof_fill_att(&payload);
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![0x56, 0x05, 0x02, 0x81, 0x01, 0x01, 0x01, 0x05, 0x82, 0x00]
+        );
+        assert_eq!(oneof_get_data(&decode(&buff).unwrap()), payload);
+        // Test exclusive choices
+        valid_cmd.att_arr[0].payload =
+            LcsfValidAttPayload::SubattArr(vec![absent_att(), absent_att()]);
+        let err = encode(&valid_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongOneofNb);
+        valid_cmd.att_arr = oneof_fill_att(&payload);
+        valid_cmd.att_arr[1].payload = LcsfValidAttPayload::SubattArr(vec![
+            LcsfValidAtt::from_u16(LcsfDataType::Uint16, 3).unwrap(),
+            LcsfValidAtt::from_u32(LcsfDataType::Uint32, 4).unwrap(),
+        ]);
+        let err = encode(&valid_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongOneofNb);
+    }
+
+    #[test]
+    fn test_required() {
+        // Test SA3 present with SA2 == EXTENDED
+        let mut payload = RequiredPayload {
+            sa1: 1,
+            sa2: REQUIRED_CA1_SA2_EXTENDED as u16,
+            sa3: Some(0x12345),
+        };
+        let mut valid_cmd = LcsfValidCmd {
+            cmd_id: CMD_ID_REQUIRED,
+            att_arr: required_fill_att(&payload),
+        };
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![
+                0x56, 0x06, 0x01, 0x81, 0x03, 0x01, 0x01, 0x01, 0x02, 0x02, 0xff, 0xff, 0x03, 0x03,
+                0x45, 0x23, 0x01,
+            ]
+        );
+        assert_eq!(required_get_data(&decode(&buff).unwrap()), payload);
+        // Test SA3 missing with SA2 != EXTENDED
+        payload.sa2 = 0x1234;
+        payload.sa3 = None;
+        valid_cmd.att_arr = required_fill_att(&payload);
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![
+                0x56, 0x06, 0x01, 0x81, 0x02, 0x01, 0x01, 0x01, 0x02, 0x02, 0x34, 0x12
+            ]
+        );
+        assert_eq!(required_get_data(&decode(&buff).unwrap()), payload);
+        // Test SA3 missing with SA2 == EXTENDED
+        payload.sa2 = REQUIRED_CA1_SA2_EXTENDED as u16;
+        valid_cmd.att_arr = required_fill_att(&payload);
+        let err = encode(&valid_cmd).unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::MissRequiredAtt);
+        let err = decode(&[
+            0x56, 0x06, 0x01, 0x81, 0x02, 0x01, 0x01, 0x01, 0x02, 0x02, 0xff, 0xff,
+        ])
+        .unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::MissRequiredAtt);
+    }
+
+    #[test]
+    fn test_group() {
+        // Test group expansion
+        let group_desc_arr = &PROT_DESC.group_arr[0].att_desc_arr;
+        let cmd_desc = PROT_DESC.get_cmd_desc(CMD_ID_GROUP_A).unwrap();
+        assert_eq!(&cmd_desc.att_desc_arr, group_desc_arr);
+        let ca1_desc = att_desc(CMD_ID_GROUP_B, &[GROUP_B_ATT_ID_CA1]);
+        assert_eq!(&ca1_desc.subatt_desc_arr, group_desc_arr);
+        // Test shared payload
+        let pair_payload = PairPayload {
+            sa1: 1,
+            sa2: Some(0x1234),
+        };
+        let mut valid_cmd = LcsfValidCmd {
+            cmd_id: CMD_ID_GROUP_A,
+            att_arr: group_a_fill_att(&pair_payload),
+        };
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![0x56, 0x07, 0x02, 0x01, 0x01, 0x01, 0x02, 0x02, 0x34, 0x12]
+        );
+        assert_eq!(group_a_get_data(&decode(&buff).unwrap()), pair_payload);
+        let payload = GroupBPayload {
+            sa1: 2,
+            ca1: PairPayload { sa1: 3, sa2: None },
+        };
+        valid_cmd = LcsfValidCmd {
+            cmd_id: CMD_ID_GROUP_B,
+            att_arr: group_b_fill_att(&payload),
+        };
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![
+                0x56, 0x08, 0x02, 0x01, 0x01, 0x02, 0x82, 0x01, 0x01, 0x01, 0x03
+            ]
+        );
+        assert_eq!(group_b_get_data(&decode(&buff).unwrap()), payload);
+    }
+
+    #[test]
+    fn test_default() {
+        let mut payload = DefaultPayload {
+            sa1: 1,
+            sa2: None,
+            ca1: Some(DefaultCa1Payload { sa3: None }),
+        };
+        let mut valid_cmd = LcsfValidCmd {
+            cmd_id: CMD_ID_DEFAULT,
+            att_arr: default_fill_att(&payload),
+        };
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(buff, vec![0x56, 0x09, 0x02, 0x01, 0x01, 0x01, 0x83, 0x00]);
+        // Test defaulted attributes are filled but reported absent
+        let (rx_cmd, rx_info) = decode_with_info(&buff).unwrap();
+        assert_eq!(
+            rx_info.default_att_arr,
+            vec![
+                vec![DEFAULT_ATT_ID_SA2],
+                vec![DEFAULT_ATT_ID_CA1, DEFAULT_CA1_ATT_ID_SA3]
+            ]
+        );
+        assert_eq!(rx_cmd.att_arr[1].as_u16(LcsfDataType::Uint16), Ok(100));
+        assert_eq!(default_get_data(&rx_cmd, &rx_info), payload);
+        // Test sent attributes equal to their default are reported present
+        payload.sa2 = Some(100);
+        payload.ca1 = Some(DefaultCa1Payload { sa3: Some(7) });
+        valid_cmd.att_arr = default_fill_att(&payload);
+        let buff = encode(&valid_cmd).unwrap();
+        assert_eq!(
+            buff,
+            vec![
+                0x56, 0x09, 0x03, 0x01, 0x01, 0x01, 0x02, 0x01, 0x64, 0x83, 0x01, 0x01, 0x01, 0x07
+            ]
+        );
+        let (rx_cmd, rx_info) = decode_with_info(&buff).unwrap();
+        assert!(rx_info.default_att_arr.is_empty());
+        assert_eq!(default_get_data(&rx_cmd, &rx_info), payload);
+        // Test absent CA1
+        payload.ca1 = None;
+        valid_cmd.att_arr = default_fill_att(&payload);
+        let buff = encode(&valid_cmd).unwrap();
+        let (rx_cmd, rx_info) = decode_with_info(&buff).unwrap();
+        assert_eq!(default_get_data(&rx_cmd, &rx_info), payload);
+    }
+}
@@ -8,4 +8,6 @@
 //! along with this program. If not, see <https://www.gnu.org/licenses/>
 
 pub mod lcsf_protocol_test_a;
+#[cfg(test)]
+pub mod lcsf_protocol_test_ext;
 pub mod protocol_test_a;
@@ -15,7 +15,9 @@ use crate::lcsf_prot::protocol_test_a;
 use lazy_static::lazy_static;
 use lcsf_core::LcsfCore;
 use lcsf_transcoder::LcsfModeEnum;
+use lcsf_validator::LcsfRole;
 use lcsf_validator::LcsfValidCmd;
+use std::sync::Arc;
 use std::sync::RwLock;
 
 // *** Using Lcsf_Generator ***
@@ -69,50 +71,66 @@ pub fn example_use_gen() {
 }
 
 // *** Without Lcsf_Generator ***
+use crate::lcsf_lib::lcsf_builder::LcsfCmdBuilder;
 use crate::lcsf_lib::lcsf_validator::LcsfAttDesc;
 use crate::lcsf_lib::lcsf_validator::LcsfCmdDesc;
 use crate::lcsf_lib::lcsf_validator::LcsfDataType;
 use crate::lcsf_lib::lcsf_validator::LcsfProtDesc;
-use crate::lcsf_lib::lcsf_validator::LcsfValidAtt;
-use crate::lcsf_lib::lcsf_validator::LcsfValidAttPayload;
+use crate::lcsf_lib::lcsf_view::LcsfValue;
 
 lazy_static! {
     /// Example descriptor
     static ref EXAMPLE_DESC:LcsfProtDesc = LcsfProtDesc {
+        name: "EXAMPLE".into(),
         cmd_desc_arr: vec![
             (0x12, LcsfCmdDesc {
+                name: "SEND_DATA".into(),
                 att_desc_arr: vec![
                     (0x55, LcsfAttDesc {
+                        name: "DATA".into(),
                         is_optional: false,
                         data_type: LcsfDataType::ByteArray,
                         subatt_desc_arr: Vec::new(),
+                        ..Default::default()
                     }),
-                ]
+                ],
+                ..Default::default()
             }),
-        ]
+        ],
+        ..Default::default()
     };
 }
 
 /// Function called when a protocol received a valid command
 fn dummy_process(_: &LcsfCore, cmd: &LcsfValidCmd) {
-    if let LcsfValidAttPayload::Data(data) = &cmd.att_arr[0].payload {
+    let Some(cmd_desc) = EXAMPLE_DESC.get_cmd_desc(cmd.cmd_id) else {
+        return;
+    };
+    // Access the attributes by name through a command view
+    if let Some(LcsfValue::ByteArray(data)) = cmd
+        .view(cmd_desc)
+        .get_path("DATA")
+        .and_then(|att| att.value())
+    {
         println!(
             "[Protocol 0xab handle]: Command received:, id: {}, data: {:?}",
             cmd.cmd_id, data
         );
     };
+    // Print the command with its descriptor names
+    println!("[Protocol 0xab handle]: {}", cmd.display(cmd_desc));
 }
 
 /// Example use without Lcsf_Generator
 #[allow(dead_code)]
 pub fn example_no_gen() {
     // Example data
-    let example_valid_cmd = LcsfValidCmd {
-        cmd_id: 0x12,
-        att_arr: vec![LcsfValidAtt {
-            payload: LcsfValidAttPayload::Data(vec![0x00, 0x01, 0x02, 0x03, 0x04]),
-        }],
-    };
+    let example_valid_cmd = LcsfCmdBuilder::from_name(&EXAMPLE_DESC, "SEND_DATA")
+        .unwrap()
+        .set("DATA", &[0x00, 0x01, 0x02, 0x03, 0x04][..])
+        .unwrap()
+        .build()
+        .unwrap();
     let example_buff: Vec<u8> = vec![0xab, 0x12, 0x01, 0x55, 0x05, 0x00, 0x01, 0x02, 0x03, 0x04];
     let err_buff: Vec<u8> = vec![0xff, 0x00, 0x02, 0x00, 0x01, 0x00, 0x01, 0x01, 0x01];
     let bad_data: Vec<u8> = vec![0xab, 0x10, 0x00];
@@ -123,20 +141,46 @@ pub fn example_no_gen() {
     // Update err callbacks (optional, only if you want to handle error message)
     lcsf_core.update_err_cb(example_err_cb, example_send);
 
-    // Add protocol
-    lcsf_core.add_protocol(0xab, &EXAMPLE_DESC, dummy_process);
+    // Set local role (optional, commands in the wrong direction are then rejected)
+    lcsf_core.set_role(LcsfRole::Device);
+
+    // Add protocol, descriptors are owned or shared through an Arc
+    let example_desc = Arc::new(EXAMPLE_DESC.clone());
+    lcsf_core.add_protocol(0xab, Arc::clone(&example_desc), dummy_process);
+    // Adding an existing protocol is refused, replace it explicitly instead
+    if let Err(err) = lcsf_core.try_add_protocol(0xab, Arc::clone(&example_desc), dummy_process) {
+        println!("Protocol 0xab not added: {err:?}");
+    }
+    lcsf_core
+        .replace_protocol(0xab, EXAMPLE_DESC.clone(), dummy_process)
+        .unwrap();
+    println!("Registered protocols: {:?}", lcsf_core.get_prot_id_list());
+    // Size receive buffers from the largest encoded command
+    let size_bounds = EXAMPLE_DESC.size_bounds(LcsfModeEnum::Small);
+    println!("Protocol 0xab message size bounds: {size_bounds:?}");
     // Receive buffer
     println!("Input buffer: {example_buff:?}");
     lcsf_core.receive_buff(&example_buff);
     // Send command
     println!("Input command: {example_valid_cmd:?}");
-    lcsf_core.send_cmd(0xab, &example_valid_cmd);
+    match lcsf_core.try_send_cmd(0xab, &example_valid_cmd) {
+        Ok(buff) => println!("Output buffer: {buff:?}"),
+        Err(err) => println!("send_cmd failed with err {err}"),
+    }
     // Receive error
     println!("Input error: {err_buff:?}");
     lcsf_core.receive_buff(&err_buff);
     // Receive bad data
     println!("Input bad data: {bad_data:?}");
     lcsf_core.receive_buff(&bad_data);
+    // Diagnose bad data, listing every error
+    let bad_att_data: Vec<u8> = vec![0xab, 0x12, 0x02, 0x55, 0x00, 0x42, 0x01, 0x00];
+    println!("Diagnose bad data: {bad_att_data:?}");
+    for err in lcsf_core.diagnose_buff(&bad_att_data).unwrap() {
+        println!("  {err}");
+    }
+    // Remove protocol
+    lcsf_core.remove_protocol(0xab).unwrap();
 }
 
 // *** Without protocol handling ***
@@ -180,6 +224,11 @@ pub fn example_use_raw() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lcsf_prot::lcsf_protocol_test_a;
+    use lcsf_validator::LcsfCmdDirection;
+    use lcsf_validator::LcsfValidateErrorEnum;
+    use protocol_test_a::CmdEnum;
+    use protocol_test_a::CmdPayload;
     use std::sync::atomic::{AtomicUsize, Ordering};
 
     // Test status variables
@@ -594,4 +643,49 @@ mod tests {
         core.receive_buff(&CC6_MSG_IN);
         assert_eq!(SEND_TEST_STATUS.load(Ordering::SeqCst), 17);
     }
+
+    #[test]
+    fn test_cmd_direction() {
+        // Set SC1, CC1 and CC4 to the device, SC2, CC2 and CC5 from the device
+        let mut prot_desc = lcsf_protocol_test_a::PROT_DESC.clone();
+        for (idx, (_, cmd_desc)) in prot_desc.cmd_desc_arr.iter_mut().enumerate() {
+            cmd_desc.direction = match idx {
+                0 | 3 | 6 => LcsfCmdDirection::ToDevice,
+                1 | 4 | 7 => LcsfCmdDirection::FromDevice,
+                _ => LcsfCmdDirection::Both,
+            };
+        }
+        let mut core = LcsfCore::new(LcsfModeEnum::Small, false);
+        core.add_protocol(lcsf_protocol_test_a::PROT_ID, prot_desc, |_, _| {});
+        let sc1_cmd = lcsf_protocol_test_a::send_cmd(CmdEnum::Sc1, &CmdPayload::Empty);
+        let sc2_cmd = lcsf_protocol_test_a::send_cmd(CmdEnum::Sc2, &CmdPayload::Empty);
+        // Test host receives commands from the device and sends commands to it
+        core.set_role(LcsfRole::Host);
+        assert!(core.receive_buff(&CC2_MSG));
+        assert!(core.receive_buff(&CC5_MSG));
+        assert!(!core.receive_buff(&CC1_MSG));
+        assert!(!core.receive_buff(&CC4_MSG));
+        assert_eq!(
+            core.send_cmd(lcsf_protocol_test_a::PROT_ID, &sc1_cmd),
+            *SC1_MSG
+        );
+        let err = core
+            .try_send_cmd(lcsf_protocol_test_a::PROT_ID, &sc2_cmd)
+            .unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongDirection);
+        // Test device does the opposite
+        core.set_role(LcsfRole::Device);
+        assert!(core.receive_buff(&CC1_MSG));
+        assert!(core.receive_buff(&CC4_MSG));
+        assert!(!core.receive_buff(&CC2_MSG));
+        assert!(!core.receive_buff(&CC5_MSG));
+        assert_eq!(
+            core.send_cmd(lcsf_protocol_test_a::PROT_ID, &sc2_cmd),
+            *SC2_MSG
+        );
+        let err = core
+            .try_send_cmd(lcsf_protocol_test_a::PROT_ID, &sc1_cmd)
+            .unwrap_err();
+        assert_eq!(err.kind, LcsfValidateErrorEnum::WrongDirection);
+    }
 }